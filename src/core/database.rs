@@ -62,6 +62,11 @@ impl Database {
         self.catalog.flush()
     }
 
+    /// Changes the number of page cache frames, evicting pages when shrinking.
+    pub fn resize_page_cache(&self, cache_frames: usize) -> StorageResult<()> {
+        self.catalog.resize_page_cache(cache_frames)
+    }
+
     pub(crate) fn begin_transaction(&self) -> StorageResult<TxnId> {
         self.transactions.begin_transaction()
     }
@@ -277,6 +282,18 @@ mod tests {
         assert!(path.with_added_extension("wal").exists());
     }
 
+    #[test]
+    fn resized_page_cache_keeps_database_readable() {
+        let dir = tempdir().unwrap();
+        let database = Database::create(dir.path().join("test.db")).unwrap();
+
+        database.resize_page_cache(4).unwrap();
+        database.resize_page_cache(64).unwrap();
+
+        assert!(matches!(database.resize_page_cache(0), Err(StorageError::Internal(_))));
+        database.flush().unwrap();
+    }
+
     #[test]
     fn create_rejects_existing_file() {
         let file = NamedTempFile::new().unwrap();
@@ -3,6 +3,7 @@ use thiserror::Error;
 
 use crate::core::{PAGE_SIZE, PageId, TableKey};
 use crate::storage::{
+    database_header::DATABASE_HEADER_PAGE_ID,
     log_manager::{LogManagerError, LogManagerFlushError, Lsn, TxnId},
     page::{CellCorruption, PageCorruption, PageError},
 };
@@ -144,6 +145,8 @@ pub enum InvalidArgumentError {
     InvalidPrimaryKey { table: String, reason: String },
     #[error("cannot update primary key column {column} on table {table}")]
     PrimaryKeyUpdate { table: String, column: String },
+    #[error("unsupported page size: {page_size}")]
+    UnsupportedPageSize { page_size: usize },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Error)]
@@ -208,6 +211,10 @@ pub(crate) enum DiskManagerError {
     InvalidPageId { page_id: PageId },
     #[error("invalid file size (not multiple of page size): {size}")]
     InvalidFileSize { size: u64 },
+    #[error("unsupported page size: {page_size}")]
+    UnsupportedPageSize { page_size: usize },
+    #[error("page size mismatch: expected {expected}, file stores {actual}")]
+    PageSizeMismatch { expected: usize, actual: usize },
 }
 
 pub(crate) type DiskManagerResult<T> = Result<T, DiskManagerError>;
@@ -252,6 +259,12 @@ pub enum TupleAllocationError {
         #[source]
         source: TryReserveError,
     },
+    #[error("failed to allocate {byte_count} tuple blob bytes: {source}")]
+    BlobBytes {
+        byte_count: usize,
+        #[source]
+        source: TryReserveError,
+    },
 }
 
 impl From<DiskManagerError> for StorageError {
@@ -266,6 +279,16 @@ impl From<DiskManagerError> for StorageError {
                 page_id: None,
                 kind: CorruptionKind::InvalidFileSize { size, page_size: PAGE_SIZE },
             }),
+            DiskManagerError::UnsupportedPageSize { page_size } => {
+                Self::InvalidArgument(InvalidArgumentError::UnsupportedPageSize { page_size })
+            }
+            DiskManagerError::PageSizeMismatch { expected, actual } => {
+                Self::Corruption(CorruptionError {
+                    component: CorruptionComponent::DatabaseFile,
+                    page_id: Some(DATABASE_HEADER_PAGE_ID),
+                    kind: CorruptionKind::InvalidDatabasePageSize { expected, actual },
+                })
+            }
         }
     }
 }
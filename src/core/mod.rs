@@ -5,7 +5,7 @@ pub(crate) mod database;
 pub mod error;
 
 pub use crate::relational::catalog::{
-    ColumnSchema, DataType, IndexColumnSchema, IndexSchema, TableSchema, TupleSchema,
+    CatalogError, ColumnSchema, DataType, IndexColumnSchema, IndexSchema, TableSchema, TupleSchema,
 };
 pub use crate::relational::cursor::{
     IndexEntry, IndexEntryView, OwnedIndexEntry, OwnedTableRecord, TableRecord, TableRecordView,
@@ -3,10 +3,19 @@ use crate::{
         OwnedTableRecord, TableKey, TableSchema, Tuple, TupleView, Value, access::RecordAccess,
     },
     planner::{BoundColumn, PlannedExpression, UpdateAssignment},
-    sql_parser::parser::op::Op,
+    sql_parser::{
+        NumberKind,
+        parser::{
+            eval::{self, ConstEvalErrorKind, TruthValue},
+            expr::Expression,
+            ident::{IdentCmp, ident_eq},
+            op::Op,
+            stmt::create_table::Column,
+        },
+    },
 };
 
-use super::{ExecutionOutput, ExecutorError, ExecutorResult, ExecutorRow, RowStream};
+use super::{EvalError, ExecutionOutput, ExecutorError, ExecutorResult, ExecutorRow, RowStream};
 
 /// Evaluates one planned scalar expression against a record.
 ///
@@ -20,6 +29,107 @@ pub fn evaluate_expression(
     evaluate_expressions(std::slice::from_ref(expression), record)
 }
 
+impl Expression<'_> {
+    /// Evaluates an expression built only from literals and operators.
+    ///
+    /// This lets a table-less query such as `SELECT 1 + 2 * 3;` be answered
+    /// without planning. Operators follow the executor's semantics, including
+    /// short-circuiting `AND` and `OR`. Identifiers, wildcards, aggregates, and
+    /// subqueries produce [`EvalError::NonConstant`].
+    pub fn evaluate_const(&self) -> Result<Value, EvalError> {
+        evaluate_parsed(self, None)
+    }
+}
+
+/// Evaluates a parsed expression against one row whose columns are described by `schema`.
+///
+/// Identifiers resolve case-insensitively to the record value at the matching
+/// column's position. Operators follow [`Expression::evaluate_const`], so `NULL`
+/// columns propagate through arithmetic and comparisons. Aggregates produce
+/// [`EvalError::AggregateInRowContext`].
+pub fn evaluate_row(
+    expr: &Expression<'_>,
+    schema: &[Column<'_>],
+    record: &Tuple,
+) -> Result<Value, EvalError> {
+    evaluate_parsed(expr, Some(Row { schema, record }))
+}
+
+/// The row a parsed expression's identifiers are read from.
+#[derive(Clone, Copy)]
+struct Row<'r> {
+    schema: &'r [Column<'r>],
+    record: &'r Tuple,
+}
+
+impl Row<'_> {
+    fn value(&self, name: &str) -> Result<Value, EvalError> {
+        let ordinal = self
+            .schema
+            .iter()
+            .position(|column| ident_eq(column.name, name, IdentCmp::CaseInsensitive))
+            .ok_or_else(|| EvalError::UnknownColumn { column: name.to_owned() })?;
+        let len = self.record.len();
+        let value = self.record.values().get(ordinal).cloned().ok_or_else(|| {
+            ExecutorError::ColumnOrdinalOutOfBounds { column: name.to_owned(), ordinal, len }
+        })?;
+        Ok(value)
+    }
+}
+
+fn evaluate_parsed(expr: &Expression<'_>, row: Option<Row<'_>>) -> Result<Value, EvalError> {
+    match (expr, row) {
+        (Expression::Literal(literal), _) => Ok(Value::try_from(literal)?),
+        (Expression::Identifier(name), Some(row)) => row.value(name),
+        (Expression::UnaryOp((op, operand)), _) => {
+            Ok(evaluate_unary(*op, evaluate_parsed(operand, row)?)?)
+        }
+        (Expression::BinaryOp((left, op, right)), _) => {
+            let left = evaluate_parsed(left, row)?;
+            if !matches!(op, Op::And | Op::Or) {
+                return Ok(evaluate_binary(left, *op, evaluate_parsed(right, row)?)?);
+            }
+            evaluate_logical(left, *op, || evaluate_parsed(right, row))
+        }
+        (Expression::AggregateFunction(_), Some(_)) => {
+            Err(EvalError::AggregateInRowContext { expression: expr.to_string() })
+        }
+        (
+            Expression::Identifier(_)
+            | Expression::Wildcard
+            | Expression::AggregateFunction(_)
+            | Expression::Subquery(_),
+            None,
+        ) => Err(EvalError::NonConstant { expression: expr.to_string() }),
+        (Expression::Wildcard | Expression::Subquery(_) | Expression::Cast(_), _) => {
+            Err(EvalError::UnsupportedExpression { expression: expr.to_string() })
+        }
+    }
+}
+
+/// Returns the positions of the values that differ between two versions of a row.
+///
+/// This lets an update touch only the columns that actually changed. Floats are
+/// compared by bit pattern, so an unchanged `NaN` is not reported while a change
+/// from `0.0` to `-0.0` is.
+pub fn record_diff(old: &Tuple, new: &Tuple) -> Result<Vec<usize>, EvalError> {
+    if old.len() != new.len() {
+        return Err(EvalError::RecordLengthMismatch { old: old.len(), new: new.len() });
+    }
+    let changed = old
+        .values()
+        .iter()
+        .zip(new.values())
+        .enumerate()
+        .filter(|(_, (old, new))| match (old, new) {
+            (Value::Float(old), Value::Float(new)) => old.to_bits() != new.to_bits(),
+            (old, new) => old != new,
+        })
+        .map(|(ordinal, _)| ordinal)
+        .collect();
+    Ok(changed)
+}
+
 /// Executes a `VALUES` plan as a stream of evaluated literal rows.
 ///
 /// Each values row is evaluated against an empty synthetic record. The row's
@@ -170,7 +280,7 @@ pub(super) fn evaluate_value(
         PlannedExpression::Binary { left, op, right } => {
             let left = evaluate_value(left, context)?;
             if matches!(op, Op::And | Op::Or) {
-                return evaluate_logical_binary(left, *op, right, context);
+                return evaluate_logical(left, *op, || evaluate_value(right, context));
             }
             let right = evaluate_value(right, context)?;
             evaluate_binary(left, *op, right)
@@ -229,10 +339,12 @@ impl<'a> EvaluationContext<'a> {
     }
 }
 
-/// Evaluates a unary operator against one value.
+/// Evaluates a unary operator against one value. `NULL` operands yield `NULL`.
 fn evaluate_unary(op: Op, value: Value) -> ExecutorResult<Value> {
     match (op, value) {
         (Op::Not, Value::Boolean(value)) => Ok(Value::Boolean(!value)),
+        (Op::Not | Op::Add | Op::Sub, Value::Null) => Ok(Value::Null),
+        (Op::Add, value @ (Value::Integer(_) | Value::Float(_))) => Ok(value),
         (Op::Sub, Value::Integer(value)) => {
             value.checked_neg().map(Value::Integer).ok_or(ExecutorError::IntegerOverflow { op })
         }
@@ -242,105 +354,113 @@ fn evaluate_unary(op: Op, value: Value) -> ExecutorResult<Value> {
 }
 
 /// Evaluates a non-short-circuiting binary operator.
+///
+/// Arithmetic and comparisons with a `NULL` operand yield `NULL`.
 fn evaluate_binary(left: Value, op: Op, right: Value) -> ExecutorResult<Value> {
+    if !matches!(op, Op::And | Op::Or | Op::Not | Op::In)
+        && (left == Value::Null || right == Value::Null)
+    {
+        return Ok(Value::Null);
+    }
     match op {
         Op::And | Op::Or => evaluate_eager_boolean_binary(left, op, right),
-        Op::Add | Op::Sub | Op::Mul | Op::Div => evaluate_arithmetic(left, op, right),
-        Op::EqualsEquals | Op::NotEquals => evaluate_equality(left, op, right),
-        Op::LessThan | Op::GreaterThan | Op::LessThanOrEqual | Op::GreaterThanOrEqual => {
-            evaluate_ordering(left, op, right)
-        }
-        Op::Not => Err(ExecutorError::UnsupportedBinary { left, op, right }),
+        Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Pow => evaluate_arithmetic(left, op, right),
+        Op::EqualsEquals
+        | Op::NotEquals
+        | Op::LessThan
+        | Op::GreaterThan
+        | Op::LessThanOrEqual
+        | Op::GreaterThanOrEqual => evaluate_comparison(left, op, right),
+        Op::Not | Op::In => Err(ExecutorError::UnsupportedBinary { left, op, right }),
     }
 }
 
-/// Evaluates short-circuiting boolean `AND` and `OR`.
-fn evaluate_logical_binary(
+/// Evaluates short-circuiting `AND` and `OR` under three-valued logic.
+///
+/// `right` is only evaluated when `left` does not already decide the result.
+fn evaluate_logical<E: From<ExecutorError>>(
     left: Value,
     op: Op,
-    right: &PlannedExpression,
-    context: &EvaluationContext<'_>,
-) -> ExecutorResult<Value> {
-    match (left, op) {
-        (Value::Boolean(false), Op::And) => Ok(Value::Boolean(false)),
-        (Value::Boolean(true), Op::Or) => Ok(Value::Boolean(true)),
-        (Value::Boolean(_), op @ (Op::And | Op::Or)) => {
-            let right = evaluate_value(right, context)?;
-            match right {
-                Value::Boolean(right) => Ok(Value::Boolean(right)),
-                value => Err(ExecutorError::NonBooleanLogicalOperand { op, value }),
-            }
-        }
-        (value, op @ (Op::And | Op::Or)) => {
-            Err(ExecutorError::NonBooleanLogicalOperand { op, value })
-        }
-        (_, _) => unreachable!("evaluate_logical_binary only accepts logical operators"),
-    }
+    right: impl FnOnce() -> Result<Value, E>,
+) -> Result<Value, E> {
+    let truth = |value: Value| {
+        truth_value(&value).ok_or(ExecutorError::NonBooleanLogicalOperand { op, value })
+    };
+    let left = truth(left)?;
+    let value = eval::short_circuit(left, op, || Ok::<_, E>(truth(right()?)?))?;
+    Ok(value.into())
 }
 
 /// Evaluates boolean operators when both operands have already been evaluated.
 fn evaluate_eager_boolean_binary(left: Value, op: Op, right: Value) -> ExecutorResult<Value> {
-    match (left, op, right) {
-        (Value::Boolean(left), Op::And, Value::Boolean(right)) => Ok(Value::Boolean(left && right)),
-        (Value::Boolean(left), Op::Or, Value::Boolean(right)) => Ok(Value::Boolean(left || right)),
-        (left, op, right) => Err(ExecutorError::UnsupportedBinary { left, op, right }),
+    match (truth_value(&left), truth_value(&right)) {
+        (Some(lhs), Some(rhs)) => Ok(eval::combine_logical(lhs, op, rhs).into()),
+        _ => Err(ExecutorError::UnsupportedBinary { left, op, right }),
     }
 }
 
-/// Evaluates arithmetic over supported numeric value pairs.
-fn evaluate_arithmetic(left: Value, op: Op, right: Value) -> ExecutorResult<Value> {
-    match (left, op, right) {
-        (Value::Integer(left), Op::Add, Value::Integer(right)) => {
-            left.checked_add(right).map(Value::Integer).ok_or(ExecutorError::IntegerOverflow { op })
-        }
-        (Value::Integer(left), Op::Sub, Value::Integer(right)) => {
-            left.checked_sub(right).map(Value::Integer).ok_or(ExecutorError::IntegerOverflow { op })
-        }
-        (Value::Integer(left), Op::Mul, Value::Integer(right)) => {
-            left.checked_mul(right).map(Value::Integer).ok_or(ExecutorError::IntegerOverflow { op })
-        }
-        (Value::Integer(_), Op::Div, Value::Integer(0)) => Err(ExecutorError::DivisionByZero),
-        (Value::Integer(left), Op::Div, Value::Integer(right)) => {
-            left.checked_div(right).map(Value::Integer).ok_or(ExecutorError::IntegerOverflow { op })
-        }
-        (Value::Float(left), Op::Add, Value::Float(right)) => Ok(Value::Float(left + right)),
-        (Value::Float(left), Op::Sub, Value::Float(right)) => Ok(Value::Float(left - right)),
-        (Value::Float(left), Op::Mul, Value::Float(right)) => Ok(Value::Float(left * right)),
-        (Value::Float(_), Op::Div, Value::Float(0.0)) => Err(ExecutorError::DivisionByZero),
-        (Value::Float(left), Op::Div, Value::Float(right)) => Ok(Value::Float(left / right)),
-        (left, op, right) => Err(ExecutorError::UnsupportedBinary { left, op, right }),
+/// Reads a boolean or `NULL` as a truth value; other values are not booleans.
+pub(super) fn truth_value(value: &Value) -> Option<TruthValue> {
+    match value {
+        Value::Boolean(value) => Some(TruthValue::from(*value)),
+        Value::Null => Some(TruthValue::Unknown),
+        _ => None,
     }
 }
 
-/// Evaluates equality and inequality for same-type values.
-fn evaluate_equality(left: Value, op: Op, right: Value) -> ExecutorResult<Value> {
-    match (&left, &right) {
-        (Value::Null, Value::Null)
-        | (Value::String(_), Value::String(_))
-        | (Value::Boolean(_), Value::Boolean(_))
-        | (Value::Integer(_), Value::Integer(_))
-        | (Value::Float(_), Value::Float(_))
-        | (Value::UnsignedInteger(_), Value::UnsignedInteger(_)) => {
-            let equal = left == right;
-            Ok(Value::Boolean(if matches!(op, Op::EqualsEquals) { equal } else { !equal }))
-        }
-        _ => Err(comparison_type_mismatch(left, op, right)),
+impl From<TruthValue> for Value {
+    fn from(value: TruthValue) -> Self {
+        value.to_bool().map_or(Value::Null, Value::Boolean)
     }
 }
 
-/// Evaluates ordering comparisons for same-type ordered values.
-fn evaluate_ordering(left: Value, op: Op, right: Value) -> ExecutorResult<Value> {
-    let result = match (&left, &right) {
-        (Value::String(left), Value::String(right)) => compare_ordered(left, op, right),
-        (Value::Boolean(left), Value::Boolean(right)) => compare_ordered(left, op, right),
-        (Value::Integer(left), Value::Integer(right)) => compare_ordered(left, op, right),
-        (Value::Float(left), Value::Float(right)) => compare_ordered(left, op, right),
-        (Value::UnsignedInteger(left), Value::UnsignedInteger(right)) => {
-            compare_ordered(left, op, right)
+/// Reads an integer or float as a number of the constant evaluator.
+fn as_number(value: &Value) -> Option<NumberKind> {
+    match value {
+        Value::Integer(value) => Some(NumberKind::Integer(*value)),
+        Value::Float(value) => Some(NumberKind::Float(f64::from(*value))),
+        _ => None,
+    }
+}
+
+/// Evaluates arithmetic through the constant evaluator, so an integer combined
+/// with a float yields a float and integer arithmetic is checked.
+fn evaluate_arithmetic(left: Value, op: Op, right: Value) -> ExecutorResult<Value> {
+    let (Some(lhs), Some(rhs)) = (as_number(&left), as_number(&right)) else {
+        return Err(ExecutorError::UnsupportedBinary { left, op, right });
+    };
+    match eval::evaluate_arithmetic(lhs, op, rhs) {
+        Ok(NumberKind::Integer(value)) => Ok(Value::Integer(value)),
+        Ok(NumberKind::Float(value)) => Ok(Value::Float(value as f32)),
+        Err(ConstEvalErrorKind::DivisionByZero) => Err(ExecutorError::DivisionByZero),
+        Err(ConstEvalErrorKind::NegativeExponent) => Err(ExecutorError::NegativeExponent),
+        Err(ConstEvalErrorKind::IntegerOverflow { op }) => {
+            Err(ExecutorError::IntegerOverflow { op })
         }
-        _ => return Err(comparison_type_mismatch(left, op, right)),
+        Err(_) => Err(ExecutorError::UnsupportedBinary { left, op, right }),
+    }
+}
+
+/// Evaluates a comparison between same-type values or between two numbers.
+///
+/// An integer compared with a float is read as `f64`, which holds every `i32`
+/// and `f32` exactly, so `16777217 == 16777216.0` is false.
+fn evaluate_comparison(left: Value, op: Op, right: Value) -> ExecutorResult<Value> {
+    let ordering = match (&left, &right) {
+        (Value::String(lhs), Value::String(rhs)) => lhs.partial_cmp(rhs),
+        (Value::Boolean(lhs), Value::Boolean(rhs)) => lhs.partial_cmp(rhs),
+        (Value::UnsignedInteger(lhs), Value::UnsignedInteger(rhs)) => lhs.partial_cmp(rhs),
+        (Value::Blob(lhs), Value::Blob(rhs)) => lhs.partial_cmp(rhs),
+        (Value::Timestamp(lhs), Value::Timestamp(rhs)) => lhs.partial_cmp(rhs),
+        (lhs, rhs) => match (as_number(lhs), as_number(rhs)) {
+            (Some(lhs), Some(rhs)) => eval::compare_numbers(lhs, rhs),
+            _ => return Err(comparison_type_mismatch(left, op, right)),
+        },
     };
-    Ok(Value::Boolean(result))
+    match eval::compare(op, ordering) {
+        Some(holds) => Ok(Value::Boolean(holds)),
+        None => Err(ExecutorError::UnsupportedBinary { left, op, right }),
+    }
 }
 
 fn comparison_type_mismatch(left: Value, op: Op, right: Value) -> ExecutorError {
@@ -361,17 +481,8 @@ fn value_type_name(value: &Value) -> &'static str {
         Value::Integer(_) => "integer",
         Value::Float(_) => "float",
         Value::UnsignedInteger(_) => "unsigned integer",
-    }
-}
-
-/// Applies an ordering operator to values with a Rust [`PartialOrd`] relation.
-fn compare_ordered<T: PartialOrd>(left: &T, op: Op, right: &T) -> bool {
-    match op {
-        Op::LessThan => left < right,
-        Op::GreaterThan => left > right,
-        Op::LessThanOrEqual => left <= right,
-        Op::GreaterThanOrEqual => left >= right,
-        _ => unreachable!("compare_ordered only accepts ordering operators"),
+        Value::Blob(_) => "blob",
+        Value::Timestamp(_) => "timestamp",
     }
 }
 
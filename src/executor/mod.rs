@@ -17,19 +17,19 @@ use crate::{
         access::ExecutionAccess,
         error::{StorageError, StorageResult},
     },
-    planner::PhysicalPlan,
+    planner::{PhysicalPlan, PlannerError},
     sql_parser::parser::op::Op,
 };
 
 mod expression;
 
-pub use expression::evaluate_expression;
 #[cfg(test)]
 use expression::record_from_values;
 use expression::{
     EvaluationContext, empty_record, evaluate_expressions, evaluate_value, execute_insert_values,
-    execute_update, execute_values, offset_rows,
+    execute_update, execute_values, offset_rows, truth_value,
 };
+pub use expression::{evaluate_expression, evaluate_row, record_diff};
 
 /// Errors that can occur while executing a physical query plan.
 ///
@@ -111,6 +111,9 @@ pub enum ExecutorError {
     /// A division expression used zero as the divisor.
     #[error("division by zero")]
     DivisionByZero,
+    /// An integer was raised to a negative integer power.
+    #[error("negative integer exponent")]
+    NegativeExponent,
     /// A row operator received a non-row-producing child plan.
     #[error("{operator} expected its input plan to return rows")]
     ExpectedRows {
@@ -136,6 +139,49 @@ pub enum ExecutorError {
 /// Result type returned by executor operations.
 pub type ExecutorResult<T> = Result<T, ExecutorError>;
 
+/// Error returned when evaluating a parsed expression as a constant or against one row.
+#[derive(Debug, thiserror::Error)]
+pub enum EvalError {
+    /// The expression references a column, wildcard, aggregate, or subquery.
+    #[error("expression is not constant: {expression}")]
+    NonConstant {
+        /// Display form of the offending sub-expression.
+        expression: String,
+    },
+    /// The expression is constant but uses a construct the executor cannot evaluate yet.
+    #[error("unsupported expression: {expression}")]
+    UnsupportedExpression {
+        /// Display form of the offending sub-expression.
+        expression: String,
+    },
+    /// The expression names a column that the row schema does not have.
+    #[error("column {column} not found in row schema")]
+    UnknownColumn {
+        /// Name of the unresolved identifier.
+        column: String,
+    },
+    /// An aggregate appeared where a single row is being evaluated.
+    #[error("aggregate in row context: {expression}")]
+    AggregateInRowContext {
+        /// Display form of the aggregate call.
+        expression: String,
+    },
+    /// A literal has no stored value, such as a float beyond the 32-bit range.
+    #[error(transparent)]
+    Literal(#[from] PlannerError),
+    /// Two rows being compared do not have the same number of values.
+    #[error("cannot diff a record of {old} values against one of {new} values")]
+    RecordLengthMismatch {
+        /// Number of values in the old record.
+        old: usize,
+        /// Number of values in the new record.
+        new: usize,
+    },
+    /// Evaluating the constant operands failed.
+    #[error(transparent)]
+    Evaluation(#[from] ExecutorError),
+}
+
 /// Row produced by the query executor.
 ///
 /// Scan rows can borrow storage pages through cursor-backed records, while
@@ -348,10 +394,12 @@ impl<'db> Executor<'db> {
                         let result = EvaluationContext::with_record(&row, |context| {
                             evaluate_value(&predicate, context)
                         });
-                        match result {
-                            Ok(Value::Boolean(true)) => Some(Ok(row)),
-                            Ok(Value::Boolean(false)) => None,
-                            Ok(value) => Some(Err(ExecutorError::NonBooleanPredicate { value })),
+                        let truth = result.and_then(|value| {
+                            truth_value(&value).ok_or(ExecutorError::NonBooleanPredicate { value })
+                        });
+                        match truth {
+                            Ok(truth) if truth.is_true() => Some(Ok(row)),
+                            Ok(_) => None,
                             Err(error) => Some(Err(error)),
                         }
                     }
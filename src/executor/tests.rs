@@ -9,10 +9,10 @@ use crate::{
         error::{ConstraintError, InternalError, InvariantViolation, StorageError},
     },
     error::DatabaseError,
-    planner::{BoundColumn, PlannedExpression, Planner},
+    planner::{BoundColumn, PlannedExpression, Planner, PlannerError},
     relational::cursor::encode_index_entry_key,
     session::{Session, SessionError},
-    sql_parser::parser::Parser,
+    sql_parser::parser::{Parser, stmt::create_table::parse_create_table},
 };
 
 fn record(table_key: TableKey, values: Vec<Value>) -> ExecutorRow {
@@ -302,6 +302,37 @@ fn filter_keeps_only_rows_with_true_predicate() {
     assert_eq!(values(&rows[0]), vec![Value::Integer(1), Value::Boolean(true)]);
 }
 
+#[test]
+fn filter_drops_rows_whose_predicate_is_null() {
+    let dir = tempdir().unwrap();
+    let database = Database::create(dir.path().join("test.db")).unwrap();
+    let mut executor = Executor::new(&database);
+    let row = |id, active| vec![PlannedExpression::Literal(id), PlannedExpression::Literal(active)];
+    let plan = PhysicalPlan::Filter {
+        input: Box::new(PhysicalPlan::Values {
+            rows: vec![
+                row(Value::Integer(1), Value::Null),
+                row(Value::Integer(2), Value::Boolean(true)),
+                row(Value::Null, Value::Boolean(true)),
+            ],
+        }),
+        predicate: PlannedExpression::Binary {
+            left: Box::new(PlannedExpression::Column(bound("active", 1, DataType::Boolean))),
+            op: Op::And,
+            right: Box::new(PlannedExpression::Binary {
+                left: Box::new(PlannedExpression::Column(bound("id", 0, DataType::Integer))),
+                op: Op::GreaterThan,
+                right: Box::new(PlannedExpression::Literal(Value::Integer(0))),
+            }),
+        },
+    };
+
+    let rows = collect_rows(executor.execute(plan).unwrap()).unwrap();
+
+    assert_eq!(rows.len(), 1);
+    assert_eq!(values(&rows[0]), vec![Value::Integer(2), Value::Boolean(true)]);
+}
+
 #[test]
 fn full_table_scan_yields_borrowed_rows_that_can_be_materialized() {
     let dir = tempdir().unwrap();
@@ -658,7 +689,7 @@ fn invalid_type_combinations_return_executor_errors() {
     let expression = PlannedExpression::Binary {
         left: Box::new(PlannedExpression::Literal(Value::Integer(1))),
         op: Op::Add,
-        right: Box::new(PlannedExpression::Literal(Value::Float(1.0))),
+        right: Box::new(PlannedExpression::Literal(Value::Boolean(true))),
     };
 
     assert!(matches!(
@@ -666,7 +697,7 @@ fn invalid_type_combinations_return_executor_errors() {
         Err(ExecutorError::UnsupportedBinary {
             left: Value::Integer(1),
             op: Op::Add,
-            right: Value::Float(1.0),
+            right: Value::Boolean(true),
         })
     ));
 }
@@ -1404,7 +1435,7 @@ fn update_rejects_primary_key_assignment() {
 
     assert!(matches!(
         execute_sql(&database, "UPDATE users SET id = id + 1 WHERE id == 1;"),
-        Err(DatabaseError::Planner(crate::planner::PlannerError::PrimaryKeyUpdate { column }))
+        Err(DatabaseError::Planner(PlannerError::PrimaryKeyUpdate { column }))
             if column == "id"
     ));
     assert_user_row(&database, 1, "Ada");
@@ -2107,3 +2138,228 @@ fn select_without_from_executes_through_one_row_and_project() {
     assert_eq!(rows[0].table_key(), 0);
     assert_eq!(values(&rows[0]), vec![Value::Integer(3)]);
 }
+
+fn evaluate_const(source: &str) -> Result<Value, EvalError> {
+    Parser::new(source).expr().unwrap().evaluate_const()
+}
+
+#[test]
+fn evaluate_const_computes_arithmetic() {
+    assert_eq!(evaluate_const("1 + 2 * 3").unwrap(), Value::Integer(7));
+    assert_eq!(evaluate_const("-(10 - 4) / 4").unwrap(), Value::Integer(-1));
+    assert_eq!(evaluate_const("1.5 * 2.0").unwrap(), Value::Float(3.0));
+    assert_eq!(evaluate_const("+2 * -+3").unwrap(), Value::Integer(-6));
+    assert_eq!(evaluate_const("2 * 3 ^ 2").unwrap(), Value::Integer(18));
+    assert_eq!(evaluate_const("2.0 ^ 0.5 ^ 2.0").unwrap(), Value::Float(2.0f32.powf(0.25)));
+    assert!(matches!(
+        evaluate_const("2 ^ -1"),
+        Err(EvalError::Evaluation(ExecutorError::NegativeExponent))
+    ));
+    assert_eq!(evaluate_const("'Ada'").unwrap(), Value::String("Ada".to_owned()));
+    assert!(matches!(
+        evaluate_const("1 / 0"),
+        Err(EvalError::Evaluation(ExecutorError::DivisionByZero))
+    ));
+}
+
+#[test]
+fn evaluate_const_computes_comparisons_as_booleans() {
+    assert_eq!(evaluate_const("1 + 1 == 2").unwrap(), Value::Boolean(true));
+    assert_eq!(evaluate_const("3 < 2").unwrap(), Value::Boolean(false));
+    assert_eq!(evaluate_const("'a' != 'b'").unwrap(), Value::Boolean(true));
+    assert!(matches!(
+        evaluate_const("1 < 'a'"),
+        Err(EvalError::Evaluation(ExecutorError::ComparisonTypeMismatch { .. }))
+    ));
+}
+
+#[test]
+fn evaluate_const_applies_logical_operators() {
+    assert_eq!(evaluate_const("NOT true").unwrap(), Value::Boolean(false));
+    assert_eq!(evaluate_const("NOT (1 > 2) AND true").unwrap(), Value::Boolean(true));
+    assert_eq!(evaluate_const("false AND 1 / 0 == 1").unwrap(), Value::Boolean(false));
+    assert_eq!(evaluate_const("true OR 1 / 0 == 1").unwrap(), Value::Boolean(true));
+}
+
+#[test]
+fn evaluate_const_follows_three_valued_logic() {
+    for source in
+        ["NULL == NULL", "NOT NULL", "NULL + 1", "1 < NULL", "NULL AND true", "NULL OR false"]
+    {
+        assert_eq!(evaluate_const(source).unwrap(), Value::Null, "{source}");
+    }
+    assert_eq!(evaluate_const("NULL AND false").unwrap(), Value::Boolean(false));
+    assert_eq!(evaluate_const("NULL OR true").unwrap(), Value::Boolean(true));
+    assert!(matches!(
+        evaluate_const("NULL AND 1"),
+        Err(EvalError::Evaluation(ExecutorError::NonBooleanLogicalOperand { .. }))
+    ));
+}
+
+#[test]
+fn evaluate_const_agrees_with_constant_folding() {
+    for source in [
+        "7 / 2 + 1.5",
+        "2 ^ 10 - 3 * 4",
+        "16777217 == 16777216.0",
+        "'abc' < 'abd' OR 1 / 0 == 1",
+        "NULL AND 1 > 2",
+        "NOT (true AND NULL)",
+        "-3 <= -3.0",
+    ] {
+        let expression = Parser::new(source).expr().unwrap();
+        let folded = Value::try_from(&expression.evaluate().unwrap()).unwrap();
+        assert_eq!(expression.evaluate_const().unwrap(), folded, "{source}");
+    }
+}
+
+#[test]
+fn evaluate_const_rejects_non_constant_expressions() {
+    for source in ["a + 1", "COUNT(*)", "NOT active", "(SELECT 1)"] {
+        let result = evaluate_const(source);
+        assert!(
+            matches!(result, Err(EvalError::NonConstant { .. })),
+            "expected {source} to be non-constant, got {result:?}"
+        );
+    }
+    assert!(matches!(
+        evaluate_const("1 + a"),
+        Err(EvalError::NonConstant { expression }) if expression == "a"
+    ));
+}
+
+#[test]
+fn evaluate_const_reports_out_of_range_literals() {
+    let huge = format!("1{}.0", "0".repeat(39));
+    assert!(matches!(
+        evaluate_const(&format!("{huge} + 1.0")),
+        Err(EvalError::Literal(PlannerError::LiteralOutOfRange { .. }))
+    ));
+}
+
+#[test]
+fn evaluate_row_reads_columns_by_schema_position() {
+    let schema = parse_create_table(
+        "CREATE TABLE staff (id INT PRIMARY KEY, name TEXT, salary FLOAT, manager INT, active BOOLEAN);",
+    )
+    .unwrap()
+    .columns;
+    let record = tuple(&[
+        Value::Integer(7),
+        Value::String("Ada".to_owned()),
+        Value::Float(1500.5),
+        Value::Null,
+        Value::Boolean(true),
+    ]);
+    let evaluate = |source: &str| {
+        evaluate_row(&Parser::new(source).expr().unwrap(), &schema, &record).unwrap()
+    };
+
+    let cases = [
+        ("id", Value::Integer(7)),
+        ("ID * 2 + 1", Value::Integer(15)),
+        ("name", Value::String("Ada".to_owned())),
+        ("salary + id", Value::Float(1507.5)),
+        ("id / 2.0", Value::Float(3.5)),
+        ("salary > 1000", Value::Boolean(true)),
+        ("id == 7.0", Value::Boolean(true)),
+        ("name != 'Grace' AND active", Value::Boolean(true)),
+        ("NOT active OR id < 0", Value::Boolean(false)),
+        ("manager", Value::Null),
+        ("manager + 1", Value::Null),
+        ("manager == NULL", Value::Null),
+        ("manager > 1 AND active", Value::Null),
+        ("manager > 1 AND NOT active", Value::Boolean(false)),
+        ("manager > 1 OR active", Value::Boolean(true)),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(evaluate(source), expected, "{source}");
+    }
+}
+
+#[test]
+fn mixed_integer_float_comparisons_are_exact_past_f32_precision() {
+    let schema =
+        parse_create_table("CREATE TABLE t (id INT PRIMARY KEY, n INT, x FLOAT);").unwrap().columns;
+    let record =
+        tuple(&[Value::Integer(1), Value::Integer(16_777_217), Value::Float(16_777_216.0)]);
+    let evaluate = |source: &str| {
+        evaluate_row(&Parser::new(source).expr().unwrap(), &schema, &record).unwrap()
+    };
+
+    let cases = [
+        ("n == x", Value::Boolean(false)),
+        ("n != x", Value::Boolean(true)),
+        ("n > x", Value::Boolean(true)),
+        ("x < n", Value::Boolean(true)),
+        ("n - 1 == x", Value::Boolean(true)),
+        ("n + 0.0", Value::Float(16_777_216.0)),
+    ];
+    for (source, expected) in cases {
+        assert_eq!(evaluate(source), expected, "{source}");
+    }
+    assert_eq!(evaluate_const("16777217 == 16777216.0").unwrap(), Value::Boolean(false));
+}
+
+#[test]
+fn evaluate_row_reports_unknown_columns_and_aggregates() {
+    let schema = parse_create_table("CREATE TABLE t (id INT PRIMARY KEY);").unwrap().columns;
+    let record = tuple(&[Value::Integer(1)]);
+    let evaluate = |source: &str| {
+        evaluate_row(&Parser::new(source).expr().unwrap(), &schema, &record).unwrap_err()
+    };
+
+    assert!(matches!(
+        evaluate("id + missing"),
+        EvalError::UnknownColumn { column } if column == "missing"
+    ));
+    assert!(matches!(
+        evaluate("SUM(id) > 1"),
+        EvalError::AggregateInRowContext { expression } if expression == "SUM(id)"
+    ));
+    assert!(matches!(
+        evaluate("'a' < id"),
+        EvalError::Evaluation(ExecutorError::ComparisonTypeMismatch { .. })
+    ));
+
+    let short = tuple(&[]);
+    let err = evaluate_row(&Parser::new("id").expr().unwrap(), &schema, &short).unwrap_err();
+    assert!(matches!(
+        err,
+        EvalError::Evaluation(ExecutorError::ColumnOrdinalOutOfBounds { ordinal: 0, len: 0, .. })
+    ));
+}
+
+fn tuple(values: &[Value]) -> Tuple {
+    Tuple::new(values.to_vec())
+}
+
+#[test]
+fn record_diff_of_identical_records_is_empty() {
+    let row = tuple(&[Value::Integer(1), Value::String("Ada".to_owned()), Value::Float(f32::NAN)]);
+    assert_eq!(record_diff(&row, &row.clone()).unwrap(), Vec::<usize>::new());
+    assert_eq!(record_diff(&tuple(&[]), &tuple(&[])).unwrap(), Vec::<usize>::new());
+}
+
+#[test]
+fn record_diff_reports_changed_columns() {
+    let old = tuple(&[Value::Integer(1), Value::String("Ada".to_owned()), Value::Null]);
+    let new = tuple(&[Value::Integer(1), Value::String("Grace".to_owned()), Value::Null]);
+    assert_eq!(record_diff(&old, &new).unwrap(), vec![1]);
+
+    let new = tuple(&[Value::Float(1.0), Value::String("Ada".to_owned()), Value::Boolean(false)]);
+    assert_eq!(record_diff(&old, &new).unwrap(), vec![0, 2]);
+
+    let old = tuple(&[Value::Float(0.0)]);
+    assert_eq!(record_diff(&old, &tuple(&[Value::Float(-0.0)])).unwrap(), vec![0]);
+}
+
+#[test]
+fn record_diff_rejects_length_mismatch() {
+    let old = tuple(&[Value::Integer(1), Value::Integer(2)]);
+    let new = tuple(&[Value::Integer(1)]);
+    assert!(matches!(
+        record_diff(&old, &new),
+        Err(EvalError::RecordLengthMismatch { old: 2, new: 1 })
+    ));
+}
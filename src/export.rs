@@ -0,0 +1,137 @@
+//! Newline-delimited JSON export of result rows.
+//!
+//! Each row becomes one JSON object on its own line, keyed by column name, so
+//! results can be streamed into tools that read NDJSON. Values map to JSON as
+//! follows:
+//!
+//! ```text
+//! NULL                          null
+//! Text                          string
+//! Boolean                       true / false
+//! Integer, UnsignedInteger      number
+//! Float                         number, or null for NaN and infinities
+//! Timestamp                     number of microseconds since the Unix epoch
+//! Blob                          array of byte values
+//! ```
+
+use std::io::{self, Write};
+
+use crate::core::{Tuple, Value};
+
+/// Writes `records` to `out` as NDJSON, naming each value after its position in `schema`.
+///
+/// Records are written as they are pulled from the iterator. A record whose
+/// value count differs from `schema` fails with [`io::ErrorKind::InvalidInput`]
+/// after any earlier records have been written.
+pub fn records_to_ndjson<W: Write>(
+    records: impl Iterator<Item = Tuple>,
+    schema: &[&str],
+    out: &mut W,
+) -> io::Result<()> {
+    for record in records {
+        if record.len() != schema.len() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!("record has {} values for {} columns", record.len(), schema.len()),
+            ));
+        }
+        out.write_all(b"{")?;
+        for (index, (column, value)) in schema.iter().zip(record.values()).enumerate() {
+            if index > 0 {
+                out.write_all(b",")?;
+            }
+            write_json_string(out, column)?;
+            out.write_all(b":")?;
+            write_json_value(out, value)?;
+        }
+        out.write_all(b"}\n")?;
+    }
+    Ok(())
+}
+
+fn write_json_value<W: Write>(out: &mut W, value: &Value) -> io::Result<()> {
+    match value {
+        Value::Null => write!(out, "null"),
+        Value::String(text) => write_json_string(out, text),
+        Value::Boolean(value) => write!(out, "{value}"),
+        Value::Integer(value) => write!(out, "{value}"),
+        Value::UnsignedInteger(value) => write!(out, "{value}"),
+        Value::Timestamp(micros) => write!(out, "{micros}"),
+        Value::Float(value) if value.is_finite() => write!(out, "{value}"),
+        Value::Float(_) => write!(out, "null"),
+        Value::Blob(bytes) => {
+            out.write_all(b"[")?;
+            for (index, byte) in bytes.iter().enumerate() {
+                if index > 0 {
+                    out.write_all(b",")?;
+                }
+                write!(out, "{byte}")?;
+            }
+            out.write_all(b"]")
+        }
+    }
+}
+
+fn write_json_string<W: Write>(out: &mut W, text: &str) -> io::Result<()> {
+    out.write_all(b"\"")?;
+    for c in text.chars() {
+        match c {
+            '"' => out.write_all(b"\\\"")?,
+            '\\' => out.write_all(b"\\\\")?,
+            '\n' => out.write_all(b"\\n")?,
+            '\r' => out.write_all(b"\\r")?,
+            '\t' => out.write_all(b"\\t")?,
+            c if c < ' ' => write!(out, "\\u{:04x}", c as u32)?,
+            c => write!(out, "{c}")?,
+        }
+    }
+    out.write_all(b"\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ndjson(records: Vec<Vec<Value>>, schema: &[&str]) -> io::Result<String> {
+        let mut out = Vec::new();
+        records_to_ndjson(records.into_iter().map(Tuple::new), schema, &mut out)?;
+        Ok(String::from_utf8(out).unwrap())
+    }
+
+    #[test]
+    fn test_two_records_two_columns() {
+        let records = vec![
+            vec![Value::Integer(1), Value::String("Ada".to_owned())],
+            vec![Value::Integer(2), Value::Null],
+        ];
+        let expected = "{\"id\":1,\"name\":\"Ada\"}\n{\"id\":2,\"name\":null}\n";
+        assert_eq!(ndjson(records, &["id", "name"]).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_every_value_variant() {
+        let records = vec![vec![
+            Value::Boolean(true),
+            Value::Float(1.5),
+            Value::Float(f32::NAN),
+            Value::UnsignedInteger(u64::MAX),
+            Value::Timestamp(-1),
+            Value::Blob(vec![0, 255]),
+            Value::String("say \"hi\"\\\n\u{1}é".to_owned()),
+        ]];
+        let schema = ["b", "f", "nan", "u", "ts", "blob", "s"];
+        let expected = "{\"b\":true,\"f\":1.5,\"nan\":null,\"u\":18446744073709551615,\
+                        \"ts\":-1,\"blob\":[0,255],\"s\":\"say \\\"hi\\\"\\\\\\n\\u0001é\"}\n";
+        assert_eq!(ndjson(records, &schema).unwrap(), expected);
+    }
+
+    #[test]
+    fn test_rejects_record_that_does_not_match_schema() {
+        let records = vec![vec![Value::Integer(1)], vec![Value::Integer(2), Value::Integer(3)]];
+        let mut out = Vec::new();
+        let err =
+            records_to_ndjson(records.into_iter().map(Tuple::new), &["a"], &mut out).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidInput);
+        assert_eq!(out, b"{\"a\":1}\n");
+    }
+}
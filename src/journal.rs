@@ -0,0 +1,245 @@
+//! Append-only journal of submitted SQL statements.
+//!
+//! The journal is an audit trail, separate from the WAL: it records what was
+//! submitted, not the page changes it caused. Each record is laid out as
+//!
+//!   payload length (u32 LE) | payload CRC32 (u32 LE) | payload
+//!
+//! where the payload is the UTF-8 canonical SQL text of one [`OwnedStatement`].
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, Read, Write},
+    path::{Path, PathBuf},
+};
+
+use crc::{CRC_32_ISO_HDLC, Crc};
+use thiserror::Error;
+
+use crate::sql_parser::parser::stmt::OwnedStatement;
+
+const CRC32: Crc<u32> = Crc::<u32>::new(&CRC_32_ISO_HDLC);
+const RECORD_HEADER_LEN: usize = 4 + 4;
+
+/// Errors raised while appending to or replaying a [`StatementJournal`].
+#[derive(Debug, Error)]
+pub enum JournalError {
+    /// Underlying filesystem operation failed.
+    #[error("I/O error: {0}")]
+    Io(#[from] io::Error),
+    /// A statement is too long to be described by the record length field.
+    #[error("statement of {len} bytes is too long for a journal record")]
+    StatementTooLong { len: usize },
+    /// The journal ended in the middle of a record.
+    #[error("truncated journal record at offset {offset}")]
+    TruncatedRecord { offset: u64 },
+    /// A record payload failed CRC validation.
+    #[error("journal checksum mismatch at offset {offset}: expected {expected}, got {actual}")]
+    ChecksumMismatch { offset: u64, expected: u32, actual: u32 },
+    /// A record payload is not the SQL text of a single statement.
+    #[error("invalid journal record at offset {offset}: {reason}")]
+    InvalidRecord { offset: u64, reason: String },
+}
+
+/// A disk-backed, append-only log of submitted statements.
+#[derive(Debug)]
+pub struct StatementJournal {
+    path: PathBuf,
+    file: File,
+}
+
+impl StatementJournal {
+    /// Opens the journal at `path`, creating an empty one if it does not exist.
+    pub fn open(path: impl AsRef<Path>) -> Result<Self, JournalError> {
+        let path = path.as_ref().to_path_buf();
+        let file = OpenOptions::new().create(true).append(true).open(&path)?;
+        Ok(Self { path, file })
+    }
+
+    /// Appends `statement` and syncs it to disk before returning.
+    pub fn append(&mut self, statement: &OwnedStatement) -> Result<(), JournalError> {
+        let payload = statement.sql().as_bytes();
+        let len = u32::try_from(payload.len())
+            .map_err(|_| JournalError::StatementTooLong { len: payload.len() })?;
+
+        let mut record = Vec::with_capacity(RECORD_HEADER_LEN + payload.len());
+        record.extend_from_slice(&len.to_le_bytes());
+        record.extend_from_slice(&CRC32.checksum(payload).to_le_bytes());
+        record.extend_from_slice(payload);
+
+        self.file.write_all(&record)?;
+        self.file.sync_data()?;
+        Ok(())
+    }
+
+    /// Reads every journaled statement back, oldest first.
+    ///
+    /// Iteration stops after the first error, since the records after a
+    /// damaged one cannot be located reliably.
+    pub fn replay(&self) -> Replay {
+        match File::open(&self.path) {
+            Ok(file) => Replay { reader: Some(BufReader::new(file)), error: None, offset: 0 },
+            Err(error) => Replay { reader: None, error: Some(error.into()), offset: 0 },
+        }
+    }
+}
+
+/// Iterator over the statements of a [`StatementJournal`].
+#[derive(Debug)]
+pub struct Replay {
+    reader: Option<BufReader<File>>,
+    error: Option<JournalError>,
+    offset: u64,
+}
+
+impl Iterator for Replay {
+    type Item = Result<OwnedStatement, JournalError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(error) = self.error.take() {
+            return Some(Err(error));
+        }
+
+        let reader = self.reader.as_mut()?;
+        match read_record(reader, self.offset) {
+            Ok(Some((statement, record_len))) => {
+                self.offset += record_len;
+                Some(Ok(statement))
+            }
+            Ok(None) => {
+                self.reader = None;
+                None
+            }
+            Err(error) => {
+                self.reader = None;
+                Some(Err(error))
+            }
+        }
+    }
+}
+
+fn read_record(
+    reader: &mut BufReader<File>,
+    offset: u64,
+) -> Result<Option<(OwnedStatement, u64)>, JournalError> {
+    if reader.fill_buf()?.is_empty() {
+        return Ok(None);
+    }
+
+    let mut header = [0; RECORD_HEADER_LEN];
+    read_exact_at(reader, &mut header, offset)?;
+    let len = u32::from_le_bytes(header[..4].try_into().unwrap()) as usize;
+    let expected = u32::from_le_bytes(header[4..].try_into().unwrap());
+
+    // Read through `take` so that a corrupt length field cannot force a huge
+    // allocation up front; the buffer only grows as bytes actually arrive.
+    let mut payload = Vec::new();
+    reader.by_ref().take(len as u64).read_to_end(&mut payload)?;
+    if payload.len() < len {
+        return Err(JournalError::TruncatedRecord { offset });
+    }
+    let actual = CRC32.checksum(&payload);
+    if actual != expected {
+        return Err(JournalError::ChecksumMismatch { offset, expected, actual });
+    }
+
+    let invalid = |reason: String| JournalError::InvalidRecord { offset, reason };
+    let sql = String::from_utf8(payload).map_err(|error| invalid(error.to_string()))?;
+    let statement = OwnedStatement::parse(&sql).map_err(|error| invalid(error.to_string()))?;
+    Ok(Some((statement, (RECORD_HEADER_LEN + len) as u64)))
+}
+
+fn read_exact_at(reader: &mut impl Read, buf: &mut [u8], offset: u64) -> Result<(), JournalError> {
+    reader.read_exact(buf).map_err(|error| match error.kind() {
+        io::ErrorKind::UnexpectedEof => JournalError::TruncatedRecord { offset },
+        _ => error.into(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use tempfile::tempdir;
+
+    use super::*;
+    use crate::sql_parser::parser::Parser;
+
+    fn owned(sql: &str) -> OwnedStatement {
+        OwnedStatement::parse(sql).unwrap()
+    }
+
+    #[test]
+    fn replays_appended_statements_in_order() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("statements.journal");
+        let sources = [
+            "INSERT INTO users (id, name) VALUES (1, 'a;b'), (2, 'c');",
+            "SELECT name FROM users WHERE id >= 1 AND NOT name == 'x' ORDER BY name DESC LIMIT 5;",
+        ];
+
+        let mut journal = StatementJournal::open(&path).unwrap();
+        for source in sources {
+            journal.append(&owned(source)).unwrap();
+        }
+        drop(journal);
+
+        let journal = StatementJournal::open(&path).unwrap();
+        let replayed: Vec<_> = journal.replay().collect::<Result<_, _>>().unwrap();
+        assert_eq!(replayed.len(), sources.len());
+        for (replayed, source) in replayed.iter().zip(sources) {
+            assert_eq!(replayed.statement().unwrap(), Parser::new(source).stmt().unwrap());
+        }
+    }
+
+    #[test]
+    fn replay_of_empty_journal_yields_nothing() {
+        let dir = tempdir().unwrap();
+        let journal = StatementJournal::open(dir.path().join("empty.journal")).unwrap();
+        assert_eq!(journal.replay().count(), 0);
+    }
+
+    #[test]
+    fn replay_stops_at_corrupt_record() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("corrupt.journal");
+        let mut journal = StatementJournal::open(&path).unwrap();
+        journal.append(&owned("SELECT 1;")).unwrap();
+        journal.append(&owned("SELECT 2;")).unwrap();
+
+        let mut bytes = std::fs::read(&path).unwrap();
+        let last = bytes.len() - 1;
+        bytes[last] ^= 1;
+        std::fs::write(&path, &bytes).unwrap();
+
+        let mut replay = journal.replay();
+        assert_eq!(replay.next().unwrap().unwrap(), owned("SELECT 1;"));
+        let second_offset = (RECORD_HEADER_LEN + "SELECT 1;".len()) as u64;
+        assert!(matches!(
+            replay.next(),
+            Some(Err(JournalError::ChecksumMismatch { offset, .. })) if offset == second_offset
+        ));
+        assert!(replay.next().is_none());
+
+        std::fs::write(&path, &bytes[..bytes.len() - 3]).unwrap();
+        let mut replay = journal.replay();
+        assert!(replay.next().unwrap().is_ok());
+        assert!(matches!(
+            replay.next(),
+            Some(Err(JournalError::TruncatedRecord { offset })) if offset == second_offset
+        ));
+    }
+
+    #[test]
+    fn replay_rejects_length_beyond_end_of_file() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("oversized.journal");
+        let mut bytes = u32::MAX.to_le_bytes().to_vec();
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(b"SELECT 1;");
+        std::fs::write(&path, &bytes).unwrap();
+
+        let journal = StatementJournal::open(&path).unwrap();
+        let mut replay = journal.replay();
+        assert!(matches!(replay.next(), Some(Err(JournalError::TruncatedRecord { offset: 0 }))));
+        assert!(replay.next().is_none());
+    }
+}
@@ -1,6 +1,8 @@
 pub mod core;
 pub mod error;
 pub mod executor;
+pub mod export;
+pub mod journal;
 pub mod planner;
 pub(crate) mod relational;
 pub mod session;
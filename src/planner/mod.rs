@@ -32,8 +32,8 @@ use thiserror::Error;
 
 use crate::{
     core::{
-        ColumnSchema, DataType, Database, IndexKeyBound, IndexKeyRange, IndexSchema, TableKey,
-        TableKeyBound, TableKeyRange, TableSchema, Tuple, TupleSchema, Value,
+        CatalogError, ColumnSchema, DataType, Database, IndexKeyBound, IndexKeyRange, IndexSchema,
+        TableKey, TableKeyBound, TableKeyRange, TableSchema, Tuple, TupleSchema, Value,
         access::SchemaAccess,
         error::{InvalidArgumentError, StorageError},
     },
@@ -46,9 +46,9 @@ use crate::{
             stmt::{
                 Statement,
                 create_index::CreateIndexQuery,
-                create_table::CreateTableQuery,
+                create_table::{ColumnConstraint, CreateTableQuery},
                 delete::DeleteQuery,
-                insert::InsertQuery,
+                insert::{InsertQuery, InsertSource, Values},
                 select::{Ordering, SelectQuery},
                 update::UpdateQuery,
             },
@@ -576,12 +576,21 @@ pub enum PlannerError {
     /// A wildcard projection was used without a table to expand against.
     #[error("wildcard projection requires a FROM table")]
     WildcardRequiresTable,
+    /// A literal has no exact counterpart among the stored value types.
+    #[error("literal out of range: {literal}")]
+    LiteralOutOfRange { literal: String },
+    /// A value has no SQL literal spelling.
+    #[error("value has no literal form: {value:?}")]
+    UnrepresentableValue { value: Value },
     /// Physical planning found an insert input shape it cannot execute.
     #[error("invalid insert input: expected VALUES")]
     InvalidInsertInput,
     /// Storage or catalog access failed while planning.
     #[error("storage error: {0}")]
     Storage(#[from] StorageError),
+    /// A `CREATE TABLE` could not be described in the catalog.
+    #[error("catalog error: {0}")]
+    Catalog(#[from] CatalogError),
 }
 
 /// Planner bound to a database catalog.
@@ -637,6 +646,10 @@ impl<'db> Planner<'db> {
             Statement::Update(query) => self.plan_update(query),
             Statement::Delete(query) => self.plan_delete(query),
             Statement::Select(query) => self.plan_select(query),
+            Statement::Values(values) => self.plan_values(values),
+            Statement::With(_) => {
+                Err(PlannerError::UnsupportedStatement { statement: statement.to_string() })
+            }
         }
     }
 
@@ -658,9 +671,15 @@ impl<'db> Planner<'db> {
     }
 
     fn plan_create_table(&self, query: &CreateTableQuery<'_>) -> PlannerResult<LogicalPlan> {
+        if query.columns.iter().any(|column| {
+            column.default.is_some() || column.constraints.contains(&ColumnConstraint::Unique)
+        }) {
+            return Err(PlannerError::UnsupportedStatement { statement: query.to_string() });
+        }
+
         Ok(LogicalPlan::CreateTable {
             name: query.table_name.to_owned(),
-            schema: TupleSchema::from_create_table_query(query),
+            schema: TupleSchema::from_create_table_query(query)?,
         })
     }
 
@@ -691,25 +710,37 @@ impl<'db> Planner<'db> {
             columns.push(bind_column(&table, column)?);
         }
 
+        let InsertSource::Values(values) = &query.source else {
+            return Err(PlannerError::UnsupportedStatement { statement: query.to_string() });
+        };
         let mut rows = Vec::new();
-        for row in &query.values.0 {
+        for row in &values.0 {
             if row.0.len() != columns.len() {
                 return Err(PlannerError::InsertColumnValueCount {
                     columns: columns.len(),
                     values: row.0.len(),
                 });
             }
-            rows.push(
-                row.0
-                    .iter()
-                    .map(|expr| literal_expression(expr).ok_or_else(|| unsupported_expr(expr)))
-                    .collect::<PlannerResult<Vec<_>>>()?,
-            );
+            rows.push(row.0.iter().map(literal_expression).collect::<PlannerResult<Vec<_>>>()?);
         }
 
         Ok(LogicalPlan::Insert { table, columns, input: Box::new(LogicalPlan::Values { rows }) })
     }
 
+    fn plan_values(&self, values: &Values<'_>) -> PlannerResult<LogicalPlan> {
+        let rows = values
+            .0
+            .iter()
+            .map(|row| {
+                row.0
+                    .iter()
+                    .map(|expr| self.bind_expression(expr, None))
+                    .collect::<PlannerResult<_>>()
+            })
+            .collect::<PlannerResult<_>>()?;
+        Ok(LogicalPlan::Values { rows })
+    }
+
     fn plan_delete(&self, query: &DeleteQuery<'_>) -> PlannerResult<LogicalPlan> {
         let table = self.table_schema(query.table)?;
         let mut input = LogicalPlan::TableScan { table: table.clone() };
@@ -757,6 +788,10 @@ impl<'db> Planner<'db> {
     }
 
     fn plan_select(&self, query: &SelectQuery<'_>) -> PlannerResult<LogicalPlan> {
+        if query.distinct || query.group_by.is_some() || query.having.is_some() {
+            return Err(PlannerError::UnsupportedStatement { statement: query.to_string() });
+        }
+
         let table = query.table.map(|name| self.table_schema(name)).transpose()?;
         let mut plan = match &table {
             Some(table) => LogicalPlan::TableScan { table: table.clone() },
@@ -921,7 +956,7 @@ impl<'db> Planner<'db> {
         table: Option<&TableSchema>,
     ) -> PlannerResult<PlannedExpression> {
         match expression {
-            Expression::Literal(literal) => Ok(PlannedExpression::Literal(Value::from(literal))),
+            Expression::Literal(literal) => Ok(PlannedExpression::Literal(literal.try_into()?)),
             Expression::Identifier(column) => match table {
                 Some(table) => bind_column(table, column).map(PlannedExpression::Column),
                 None => Err(PlannerError::ColumnNotFound { column: (*column).to_owned() }),
@@ -939,6 +974,9 @@ impl<'db> Planner<'db> {
             Expression::AggregateFunction(aggregate) => {
                 Err(PlannerError::UnsupportedAggregate { function: aggregate.kind.to_string() })
             }
+            Expression::Subquery(_) | Expression::Cast(_) => {
+                Err(PlannerError::UnsupportedExpression { expression: expression.to_string() })
+            }
         }
     }
 
@@ -972,10 +1010,10 @@ fn bound_column(table: &TableSchema, ordinal: usize, column: &ColumnSchema) -> B
     }
 }
 
-fn literal_expression(expression: &Expression<'_>) -> Option<PlannedExpression> {
+fn literal_expression(expression: &Expression<'_>) -> PlannerResult<PlannedExpression> {
     match expression {
-        Expression::Literal(literal) => Some(PlannedExpression::Literal(Value::from(literal))),
-        _ => None,
+        Expression::Literal(literal) => Ok(PlannedExpression::Literal(literal.try_into()?)),
+        _ => Err(unsupported_expr(expression)),
     }
 }
 
@@ -1135,7 +1173,16 @@ fn index_comparison_from_operands<'a>(
         Op::GreaterThanOrEqual => IndexComparisonKind::GreaterThanOrEqual,
         Op::LessThan => IndexComparisonKind::LessThan,
         Op::LessThanOrEqual => IndexComparisonKind::LessThanOrEqual,
-        Op::And | Op::Or | Op::NotEquals | Op::Not | Op::Add | Op::Sub | Op::Mul | Op::Div => {
+        Op::And
+        | Op::Or
+        | Op::NotEquals
+        | Op::In
+        | Op::Not
+        | Op::Add
+        | Op::Sub
+        | Op::Mul
+        | Op::Div
+        | Op::Pow => {
             return None;
         }
     };
@@ -1164,7 +1211,16 @@ fn reverse_comparison_op(op: Op) -> Option<Op> {
         Op::GreaterThanOrEqual => Some(Op::LessThanOrEqual),
         Op::LessThan => Some(Op::GreaterThan),
         Op::LessThanOrEqual => Some(Op::GreaterThanOrEqual),
-        Op::And | Op::Or | Op::NotEquals | Op::Not | Op::Add | Op::Sub | Op::Mul | Op::Div => None,
+        Op::And
+        | Op::Or
+        | Op::NotEquals
+        | Op::In
+        | Op::Not
+        | Op::Add
+        | Op::Sub
+        | Op::Mul
+        | Op::Div
+        | Op::Pow => None,
     }
 }
 
@@ -1382,7 +1438,16 @@ fn range_from_column_comparison(op: Op, value: TableKey) -> Option<TableKeyRange
         Op::LessThanOrEqual => {
             Some(TableKeyRange { lower: None, upper: Some(TableKeyBound::Inclusive(value)) })
         }
-        Op::And | Op::Or | Op::NotEquals | Op::Not | Op::Add | Op::Sub | Op::Mul | Op::Div => None,
+        Op::And
+        | Op::Or
+        | Op::NotEquals
+        | Op::In
+        | Op::Not
+        | Op::Add
+        | Op::Sub
+        | Op::Mul
+        | Op::Div
+        | Op::Pow => None,
     }
 }
 
@@ -1404,7 +1469,16 @@ fn range_from_literal_comparison(op: Op, value: TableKey) -> Option<TableKeyRang
         Op::GreaterThanOrEqual => {
             Some(TableKeyRange { lower: None, upper: Some(TableKeyBound::Inclusive(value)) })
         }
-        Op::And | Op::Or | Op::NotEquals | Op::Not | Op::Add | Op::Sub | Op::Mul | Op::Div => None,
+        Op::And
+        | Op::Or
+        | Op::NotEquals
+        | Op::In
+        | Op::Not
+        | Op::Add
+        | Op::Sub
+        | Op::Mul
+        | Op::Div
+        | Op::Pow => None,
     }
 }
 
@@ -1455,14 +1529,50 @@ fn bound_is_exclusive(bound: TableKeyBound) -> bool {
     matches!(bound, TableKeyBound::Exclusive(_))
 }
 
-impl From<&Literal<'_>> for Value {
-    fn from(literal: &Literal) -> Self {
-        match literal {
-            Literal::String(value) => Value::String((*value).to_owned()),
+impl TryFrom<&Literal<'_>> for Value {
+    type Error = PlannerError;
+
+    /// Fails for a float literal whose magnitude exceeds the 32-bit stored float range.
+    fn try_from(literal: &Literal) -> PlannerResult<Self> {
+        Ok(match literal {
+            Literal::String(value) => Value::String(value.to_string()),
             Literal::Number(NumberKind::Integer(value)) => Value::Integer(*value),
-            Literal::Number(NumberKind::Float(value)) => Value::Float(*value),
+            Literal::Number(NumberKind::Float(value)) => {
+                let narrowed = *value as f32;
+                if value.is_finite() && !narrowed.is_finite() {
+                    return Err(PlannerError::LiteralOutOfRange { literal: literal.to_string() });
+                }
+                Value::Float(narrowed)
+            }
             Literal::Boolean(value) => Value::Boolean(*value),
-        }
+            Literal::Null => Value::Null,
+        })
+    }
+}
+
+impl TryFrom<&Value> for Literal<'static> {
+    type Error = PlannerError;
+
+    /// Maps a value back to the literal that would plan to it, so results can be
+    /// printed as SQL. Blobs, timestamps, unsigned integers beyond `i32` and
+    /// non-finite floats, which SQL has no literal for, fail.
+    fn try_from(value: &Value) -> PlannerResult<Self> {
+        Ok(match value {
+            Value::Null => Literal::Null,
+            Value::String(value) => Literal::String(value.clone().into()),
+            Value::Boolean(value) => Literal::Boolean(*value),
+            Value::Integer(value) => Literal::Number(NumberKind::Integer(*value)),
+            Value::Float(float) if float.is_finite() => {
+                Literal::Number(NumberKind::Float(f64::from(*float)))
+            }
+            Value::UnsignedInteger(unsigned) => match i32::try_from(*unsigned) {
+                Ok(value) => Literal::Number(NumberKind::Integer(value)),
+                Err(_) => return Err(PlannerError::UnrepresentableValue { value: value.clone() }),
+            },
+            Value::Float(_) | Value::Blob(_) | Value::Timestamp(_) => {
+                return Err(PlannerError::UnrepresentableValue { value: value.clone() });
+            }
+        })
     }
 }
 
@@ -1544,6 +1654,51 @@ mod tests {
         );
     }
 
+    #[test]
+    fn create_table_rejects_column_types_without_storage() {
+        let dir = tempdir().unwrap();
+        let database = Database::create(dir.path().join("test.db")).unwrap();
+        let planner = Planner::new(&database);
+
+        let plan =
+            planner.plan_statement(&parse("CREATE TABLE t (id INT PRIMARY KEY, b BOOLEAN);"));
+        let LogicalPlan::CreateTable { schema, .. } = plan.unwrap().logical else {
+            panic!("expected CREATE TABLE plan");
+        };
+        assert_eq!(schema.columns[1].data_type, DataType::Boolean);
+
+        let result =
+            planner.plan_statement(&parse("CREATE TABLE t (id INT PRIMARY KEY, n BIGINT);"));
+        assert!(matches!(
+            result,
+            Err(PlannerError::Catalog(CatalogError::UnsupportedColumnType { column, column_type }))
+                if column == "n" && column_type == "BIGINT"
+        ));
+    }
+
+    #[test]
+    fn insert_binds_null_literal_as_null_value() {
+        let (_dir, database) = database_with_users();
+        let planner = Planner::new(&database);
+        let statement = parse("INSERT INTO users (id, name, age) VALUES (1, 'Ada', NULL);");
+
+        let plan = planner.plan_statement(&statement).unwrap();
+
+        let LogicalPlan::Insert { input, .. } = &plan.logical else {
+            panic!("expected logical insert plan: {plan:?}");
+        };
+        assert_eq!(
+            input.as_ref(),
+            &LogicalPlan::Values {
+                rows: vec![vec![
+                    PlannedExpression::Literal(Value::Integer(1)),
+                    PlannedExpression::Literal(Value::String("Ada".to_owned())),
+                    PlannedExpression::Literal(Value::Null),
+                ]],
+            }
+        );
+    }
+
     #[test]
     fn create_index_binds_table_and_index_columns() {
         let (_dir, database) = database_with_users();
@@ -1617,6 +1772,41 @@ mod tests {
         assert_eq!(values.len(), 2);
     }
 
+    #[test]
+    fn values_statement_plans_inline_rows() {
+        let (_dir, database) = database_with_users();
+        let planner = Planner::new(&database);
+        let statement = parse("VALUES (1, 'Ada'), (2 + 3, NULL);");
+
+        let plan = planner.plan_statement(&statement).unwrap();
+
+        let rows = vec![
+            vec![
+                PlannedExpression::Literal(Value::Integer(1)),
+                PlannedExpression::Literal(Value::String("Ada".to_owned())),
+            ],
+            vec![
+                PlannedExpression::Binary {
+                    left: Box::new(PlannedExpression::Literal(Value::Integer(2))),
+                    op: Op::Add,
+                    right: Box::new(PlannedExpression::Literal(Value::Integer(3))),
+                },
+                PlannedExpression::Literal(Value::Null),
+            ],
+        ];
+        assert_eq!(plan.logical, LogicalPlan::Values { rows: rows.clone() });
+        assert_eq!(plan.physical, PhysicalPlan::Values { rows });
+    }
+
+    #[test]
+    fn values_statement_rejects_column_references() {
+        let (_dir, database) = database_with_users();
+        let planner = Planner::new(&database);
+        let statement = parse("VALUES (id);");
+
+        assert!(planner.plan_statement(&statement).is_err());
+    }
+
     #[test]
     fn update_all_plans_full_table_scan_under_update() {
         let (_dir, database) = database_with_users();
@@ -2421,10 +2611,77 @@ mod tests {
             planner.plan_statement(&parse("CREATE INDEX idx_duplicate ON users (name, name);")),
             Err(PlannerError::DuplicateIndexColumn { column }) if column == "name"
         ));
+        assert!(matches!(
+            planner.plan_statement(&parse("INSERT INTO users (id) SELECT id FROM users;")),
+            Err(PlannerError::UnsupportedStatement { statement }) if statement.starts_with("INSERT")
+        ));
         assert!(matches!(
             planner.plan_statement(&parse("EXPLAIN INSERT INTO users (id) VALUES (1);")),
             Err(PlannerError::UnsupportedStatement { statement }) if statement.starts_with("INSERT")
         ));
+        assert!(matches!(
+            planner.plan_statement(&parse("CREATE TABLE t (id INT PRIMARY KEY, n INT DEFAULT 0);")),
+            Err(PlannerError::UnsupportedStatement { statement }) if statement.starts_with("CREATE")
+        ));
+        assert!(matches!(
+            planner.plan_statement(&parse("CREATE TABLE t (id INT PRIMARY KEY, n INT UNIQUE);")),
+            Err(PlannerError::UnsupportedStatement { statement }) if statement.starts_with("CREATE")
+        ));
+    }
+
+    #[test]
+    fn literals_round_trip_through_values() {
+        let literals = [
+            Literal::String("it's".into()),
+            Literal::Number(NumberKind::Integer(-7)),
+            Literal::Number(NumberKind::Float(2.5)),
+            Literal::Boolean(true),
+            Literal::Null,
+        ];
+        let values = [
+            Value::String("it's".to_owned()),
+            Value::Integer(-7),
+            Value::Float(2.5),
+            Value::Boolean(true),
+            Value::Null,
+        ];
+        for (literal, value) in literals.iter().zip(&values) {
+            assert_eq!(&Value::try_from(literal).unwrap(), value);
+            assert_eq!(&Literal::try_from(value).unwrap(), literal);
+        }
+
+        let sql = values.iter().map(|value| Literal::try_from(value).unwrap().to_string());
+        assert_eq!(sql.collect::<Vec<_>>(), ["'it''s'", "-7", "2.5", "TRUE", "NULL"]);
+    }
+
+    #[test]
+    fn unrepresentable_literals_and_values_are_rejected() {
+        let huge = Literal::Number(NumberKind::Float(1e39));
+        assert!(matches!(
+            Value::try_from(&huge),
+            Err(PlannerError::LiteralOutOfRange { literal }) if literal == huge.to_string()
+        ));
+
+        assert_eq!(
+            Literal::try_from(&Value::UnsignedInteger(42)).unwrap(),
+            Literal::Number(NumberKind::Integer(42))
+        );
+        let values = [
+            Value::UnsignedInteger(u64::MAX),
+            Value::Blob(vec![1]),
+            Value::Timestamp(0),
+            Value::Float(f32::NAN),
+            Value::Float(f32::INFINITY),
+            Value::Float(f32::NEG_INFINITY),
+        ];
+        for value in values {
+            // Compared through Debug, since NaN is not equal to itself.
+            assert!(matches!(
+                Literal::try_from(&value),
+                Err(PlannerError::UnrepresentableValue { value: rejected })
+                    if format!("{rejected:?}") == format!("{value:?}")
+            ));
+        }
     }
 
     fn bound(table: &str, name: &str, ordinal: usize, data_type: DataType) -> BoundColumn {
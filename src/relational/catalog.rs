@@ -96,11 +96,15 @@ impl DataType {
     }
 
     /// Maps a parsed SQL column type onto the storage catalog type.
-    pub fn from_sql(column_type: &ColumnType) -> Self {
+    ///
+    /// Returns `None` for SQL types that storage cannot hold yet.
+    pub fn from_sql(column_type: &ColumnType) -> Option<Self> {
         match column_type {
-            ColumnType::Int => Self::Integer,
-            ColumnType::Float => Self::Float,
-            ColumnType::Text => Self::Text,
+            ColumnType::Int => Some(Self::Integer),
+            ColumnType::Float => Some(Self::Float),
+            ColumnType::Text(_) | ColumnType::Varchar(_) => Some(Self::Text),
+            ColumnType::Boolean => Some(Self::Boolean),
+            ColumnType::BigInt | ColumnType::Blob => None,
         }
     }
 }
@@ -297,6 +301,8 @@ pub enum CatalogError {
     InvalidObjectKind { actual: i32 },
     /// An index definition referenced a column absent from the target table.
     UnknownIndexColumn { table: String, column: String },
+    /// A `CREATE TABLE` column uses a SQL type with no storage representation.
+    UnsupportedColumnType { column: String, column_type: String },
 }
 
 impl fmt::Display for CatalogError {
@@ -315,6 +321,9 @@ impl fmt::Display for CatalogError {
             Self::UnknownIndexColumn { table, column } => {
                 write!(f, "index column {column} does not exist on table {table}")
             }
+            Self::UnsupportedColumnType { column, column_type } => {
+                write!(f, "column {column} has unsupported type {column_type}")
+            }
         }
     }
 }
@@ -327,13 +336,13 @@ impl TableSchema {
         table_id: CatalogId,
         root_page_id: PageId,
         query: &CreateTableQuery<'_>,
-    ) -> Self {
-        Self {
+    ) -> Result<Self, CatalogError> {
+        Ok(Self {
             table_id,
             name: query.table_name.to_owned(),
             root_page_id,
-            row: TupleSchema::from_create_table_query(query),
-        }
+            row: TupleSchema::from_create_table_query(query)?,
+        })
     }
 
     /// Returns the `sys_tables` row describing this table.
@@ -348,19 +357,27 @@ impl TableSchema {
 
 impl TupleSchema {
     /// Builds a tuple schema from a parsed `CREATE TABLE` statement.
-    pub fn from_create_table_query(query: &CreateTableQuery<'_>) -> Self {
+    pub fn from_create_table_query(query: &CreateTableQuery<'_>) -> Result<Self, CatalogError> {
         let columns = query
             .columns
             .iter()
-            .map(|column| ColumnSchema {
-                name: column.name.to_owned(),
-                data_type: DataType::from_sql(&column.column_type),
-                nullable: column.constraints.contains(&ColumnConstraint::Nullable),
-                primary_key: column.constraints.contains(&ColumnConstraint::PrimaryKey),
+            .map(|column| {
+                let data_type = DataType::from_sql(&column.column_type).ok_or_else(|| {
+                    CatalogError::UnsupportedColumnType {
+                        column: column.name.to_owned(),
+                        column_type: column.column_type.to_string(),
+                    }
+                })?;
+                Ok(ColumnSchema {
+                    name: column.name.to_owned(),
+                    data_type,
+                    nullable: column.constraints.contains(&ColumnConstraint::Nullable),
+                    primary_key: column.constraints.contains(&ColumnConstraint::PrimaryKey),
+                })
             })
-            .collect();
+            .collect::<Result<_, _>>()?;
 
-        Self { columns }
+        Ok(Self { columns })
     }
 }
 
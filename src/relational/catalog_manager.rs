@@ -44,6 +44,11 @@ impl CatalogManager {
         self.pager.flush()
     }
 
+    /// Changes the number of page cache frames.
+    pub fn resize_page_cache(&self, cache_frames: usize) -> StorageResult<()> {
+        self.pager.resize_cache(cache_frames)
+    }
+
     /// Creates a cataloged table, allocates its root page, and records its columns.
     pub fn create_table(&self, name: &str, row: TupleSchema) -> StorageResult<TableSchema> {
         validate_user_table_schema(name, &row)?;
@@ -80,7 +80,7 @@ impl RecordManager {
     ) -> StorageResult<OwnedTableRecord> {
         validate_table_row(table, &values)?;
 
-        let table_key = table_key_from_values(table, &values)?;
+        let table_key = record_key(table, &values)?;
         let record = Tuple::new(values).to_bytes()?;
         let mut table_cursor = self.catalog.table_cursor_by_name(&table.name)?;
         table_cursor.insert(table_key, &record)?;
@@ -107,7 +107,7 @@ impl RecordManager {
         values: Vec<Value>,
     ) -> StorageResult<OwnedTableRecord> {
         validate_table_row(table, &values)?;
-        let updated_table_key = table_key_from_values(table, &values)?;
+        let updated_table_key = record_key(table, &values)?;
         if updated_table_key != record.table_key {
             return Err(StorageError::InvalidArgument(InvalidArgumentError::PrimaryKeyUpdate {
                 table: table.name.clone(),
@@ -321,22 +321,28 @@ fn validate_table_row(table: &TableSchema, values: &[Value]) -> StorageResult<()
     Ok(())
 }
 
-fn table_key_from_values(table: &TableSchema, values: &[Value]) -> StorageResult<TableKey> {
-    match values.first() {
-        Some(Value::Integer(value)) => Ok(*value),
-        Some(Value::Null) => Err(StorageError::Constraint(ConstraintError::NullValue {
-            column: table.row.columns[0].name.clone(),
+/// Extracts the table key stored in the primary-key column, which is always the first column.
+///
+/// Table keys are signed, so any integer value is accepted; NULL and values of
+/// other types are rejected as constraint violations.
+pub(crate) fn record_key(table: &TableSchema, values: &[Value]) -> StorageResult<TableKey> {
+    let (Some(column), Some(value)) = (table.row.columns.first(), values.first()) else {
+        return Err(StorageError::InvalidArgument(InvalidArgumentError::TableRowValueCount {
+            table: table.name.clone(),
+            columns: table.row.columns.len(),
+            values: values.len(),
+        }));
+    };
+    match value {
+        Value::Integer(value) => Ok(*value),
+        Value::Null => Err(StorageError::Constraint(ConstraintError::NullValue {
+            column: column.name.clone(),
         })),
-        Some(value) => Err(StorageError::Constraint(ConstraintError::ColumnTypeMismatch {
-            column: table.row.columns[0].name.clone(),
+        value => Err(StorageError::Constraint(ConstraintError::ColumnTypeMismatch {
+            column: column.name.clone(),
             expected: DataType::Integer,
             actual: value_type_name(value),
         })),
-        None => Err(StorageError::InvalidArgument(InvalidArgumentError::TableRowValueCount {
-            table: table.name.clone(),
-            columns: table.row.columns.len(),
-            values: 0,
-        })),
     }
 }
 
@@ -359,6 +365,8 @@ fn value_type_name(value: &Value) -> &'static str {
         Value::Integer(_) => "integer",
         Value::Float(_) => "float",
         Value::UnsignedInteger(_) => "unsigned integer",
+        Value::Blob(_) => "blob",
+        Value::Timestamp(_) => "timestamp",
     }
 }
 
@@ -767,4 +775,62 @@ mod tests {
         let mut users = catalog.table_cursor_by_name("users").unwrap();
         assert!(users.get(1).unwrap().is_none());
     }
+
+    fn users_table() -> TableSchema {
+        TableSchema { table_id: 1, name: "users".to_owned(), root_page_id: 1, row: users_schema() }
+    }
+
+    #[test]
+    fn record_key_reads_integer_primary_key() {
+        let values =
+            vec![Value::Integer(42), Value::String("ada".to_owned()), Value::Boolean(true)];
+
+        assert_eq!(record_key(&users_table(), &values).unwrap(), 42);
+    }
+
+    #[test]
+    fn record_key_accepts_negative_keys() {
+        let values =
+            vec![Value::Integer(-7), Value::String("ada".to_owned()), Value::Boolean(true)];
+
+        assert_eq!(record_key(&users_table(), &values).unwrap(), -7);
+    }
+
+    #[test]
+    fn record_key_rejects_non_integer_key() {
+        let values = vec![Value::String("ada".to_owned()), Value::String("ada".to_owned())];
+
+        let error = record_key(&users_table(), &values).unwrap_err();
+        assert!(matches!(
+            error,
+            StorageError::Constraint(ConstraintError::ColumnTypeMismatch {
+                column,
+                expected: DataType::Integer,
+                actual: "text",
+            }) if column == "id"
+        ));
+    }
+
+    #[test]
+    fn record_key_rejects_empty_row() {
+        let error = record_key(&users_table(), &[]).unwrap_err();
+        assert!(matches!(
+            error,
+            StorageError::InvalidArgument(InvalidArgumentError::TableRowValueCount {
+                values: 0,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn record_key_rejects_null_key() {
+        let values = vec![Value::Null];
+
+        let error = record_key(&users_table(), &values).unwrap_err();
+        assert!(matches!(
+            error,
+            StorageError::Constraint(ConstraintError::NullValue { column }) if column == "id"
+        ));
+    }
 }
@@ -25,19 +25,36 @@
 //! Integer(i32)     tag 0x03, len 4, (value ^ i32::MIN).to_be_bytes()
 //! Float(f32)       tag 0x04, len 4, sortable IEEE-754 bits in big-endian order
 //! UnsignedInteger  tag 0x06, len 8, value.to_be_bytes()
+//! Blob             tag 0x07, len N, raw bytes
+//! Timestamp(i64)   tag 0x08, len 8, (micros ^ i64::MIN).to_be_bytes()
 //! ```
 //!
 //! Fixed-width numeric payloads are encoded so bytewise comparison of payloads
 //! matches their logical ascending order. Float values reject NaN and normalize
 //! both zero signs to `+0.0` during serialization and decoding. Strings keep
 //! length-based framing and compare by raw UTF-8 bytes when a caller slices out
-//! each payload.
+//! each payload. Blobs use the same framing and compare by raw bytes.
 //!
 //! [`TupleView`] validates all tags, lengths, UTF-8 text, boolean payloads,
 //! float payloads, and trailing bytes before exposing zero-copy borrowed values.
+//!
+//! Null-heavy tuples whose width is known from a schema can instead use the
+//! packed encoding, which replaces per-value NULL fields with a leading bitmap:
+//!
+//! ```text
+//! [u8; ceil(schema_len / 8)] null_bitmap   bit i set when value i is NULL
+//! repeat for each non-NULL value:
+//!   u8  value_tag
+//!   u32 payload_len_be
+//!   [u8; payload_len] canonical_payload
+//! ```
+//!
+//! Bitmap bits are assigned least-significant bit first within each byte. The
+//! packed form carries no value count, so decoding requires the schema length.
 
 use std::{
     cmp::Ordering,
+    collections::TryReserveError,
     fmt::Display,
     io::{self, Read, Write},
     ops::Range,
@@ -51,12 +68,15 @@ const TAG_INTEGER: u8 = 0x03;
 const TAG_FLOAT: u8 = 0x04;
 const TAG_NULL: u8 = 0x05;
 const TAG_UNSIGNED_INTEGER: u8 = 0x06;
+const TAG_BLOB: u8 = 0x07;
+const TAG_TIMESTAMP: u8 = 0x08;
 
 const NULL_LENGTH: u32 = 0;
 const BOOL_LENGTH: u32 = 1;
 const I32_LENGTH: u32 = size_of::<i32>() as u32;
 const F32_LENGTH: u32 = size_of::<f32>() as u32;
 const U64_LENGTH: u32 = size_of::<u64>() as u32;
+const I64_LENGTH: u32 = size_of::<i64>() as u32;
 
 /// A single typed value stored in a [`Tuple`].
 #[derive(Debug, Clone, PartialEq)]
@@ -67,6 +87,9 @@ pub enum Value {
     Integer(i32),
     Float(f32),
     UnsignedInteger(u64),
+    Blob(Vec<u8>),
+    /// Microseconds since the Unix epoch.
+    Timestamp(i64),
 }
 
 impl Display for Value {
@@ -78,6 +101,72 @@ impl Display for Value {
             Value::Integer(i) => write!(f, "{i}"),
             Value::Float(fl) => write!(f, "{fl}"),
             Value::UnsignedInteger(u) => write!(f, "{u}"),
+            Value::Blob(bytes) => {
+                write!(f, "x'")?;
+                for byte in bytes {
+                    write!(f, "{byte:02x}")?;
+                }
+                write!(f, "'")
+            }
+            Value::Timestamp(micros) => write!(f, "{micros}"),
+        }
+    }
+}
+
+impl Value {
+    /// Compares two values using a total order across value types.
+    ///
+    /// `NULL` sorts before every other value and equal to itself. Values of
+    /// different types are ordered by type: booleans, then numbers, then
+    /// timestamps, then text, then blobs. Integers, unsigned integers, and
+    /// floats all compare by numeric value; comparisons that involve a float
+    /// are done in `f64`, so very large integers may compare equal to a nearby
+    /// float. Text compares by UTF-8 bytes and blobs by raw bytes.
+    ///
+    /// Returns `None` when either value is a NaN float, which has no position
+    /// in the order.
+    pub fn cmp_value(&self, other: &Value) -> Option<Ordering> {
+        match (self, other) {
+            (Value::Float(value), _) | (_, Value::Float(value)) if value.is_nan() => None,
+            (Value::Integer(left), Value::Integer(right)) => Some(left.cmp(right)),
+            (Value::UnsignedInteger(left), Value::UnsignedInteger(right)) => Some(left.cmp(right)),
+            (Value::Integer(left), Value::UnsignedInteger(right)) => {
+                Some(i128::from(*left).cmp(&i128::from(*right)))
+            }
+            (Value::UnsignedInteger(left), Value::Integer(right)) => {
+                Some(i128::from(*left).cmp(&i128::from(*right)))
+            }
+            (Value::Float(_), _) | (_, Value::Float(_))
+                if self.numeric_value().is_some() && other.numeric_value().is_some() =>
+            {
+                self.numeric_value()?.partial_cmp(&other.numeric_value()?)
+            }
+            (Value::Null, Value::Null) => Some(Ordering::Equal),
+            (Value::Boolean(left), Value::Boolean(right)) => Some(left.cmp(right)),
+            (Value::Timestamp(left), Value::Timestamp(right)) => Some(left.cmp(right)),
+            (Value::String(left), Value::String(right)) => Some(left.cmp(right)),
+            (Value::Blob(left), Value::Blob(right)) => Some(left.cmp(right)),
+            _ => Some(self.type_rank().cmp(&other.type_rank())),
+        }
+    }
+
+    fn numeric_value(&self) -> Option<f64> {
+        match self {
+            Value::Integer(value) => Some(f64::from(*value)),
+            Value::UnsignedInteger(value) => Some(*value as f64),
+            Value::Float(value) => Some(f64::from(*value)),
+            _ => None,
+        }
+    }
+
+    fn type_rank(&self) -> u8 {
+        match self {
+            Value::Null => 0,
+            Value::Boolean(_) => 1,
+            Value::Integer(_) | Value::UnsignedInteger(_) | Value::Float(_) => 2,
+            Value::Timestamp(_) => 3,
+            Value::String(_) => 4,
+            Value::Blob(_) => 5,
         }
     }
 }
@@ -91,6 +180,9 @@ pub enum ValueRef<'a> {
     Integer(i32),
     Float(f32),
     UnsignedInteger(u64),
+    Blob(&'a [u8]),
+    /// Microseconds since the Unix epoch.
+    Timestamp(i64),
 }
 
 /// An ordered list of typed storage values.
@@ -191,6 +283,71 @@ impl Tuple {
         self.write_to(&mut bytes)?;
         Ok(bytes)
     }
+
+    /// Serializes this tuple using the null-bitmap packed encoding.
+    pub fn serialize_packed(&self) -> io::Result<Vec<u8>> {
+        let mut bytes = vec![0; null_bitmap_len(self.0.len())];
+        for (index, value) in self.0.iter().enumerate() {
+            if matches!(value, Value::Null) {
+                bytes[index / 8] |= 1 << (index % 8);
+            } else {
+                write_value_ref(&mut bytes, value.into())?;
+            }
+        }
+        Ok(bytes)
+    }
+
+    /// Deserializes a packed tuple of `schema_len` values from `bytes`.
+    pub fn deserialize_packed(bytes: &[u8], schema_len: usize) -> io::Result<Self> {
+        let bitmap_len = null_bitmap_len(schema_len);
+        let bitmap = bytes.get(..bitmap_len).ok_or_else(unexpected_eof)?;
+        let unused_bits = bitmap_len * 8 - schema_len;
+        if unused_bits > 0 && bitmap[bitmap_len - 1] >> (8 - unused_bits) != 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "null bitmap marks values beyond the schema length",
+            ));
+        }
+
+        let mut values = Vec::new();
+        values.try_reserve_exact(schema_len).map_err(|source| {
+            io::Error::new(
+                io::ErrorKind::OutOfMemory,
+                TupleAllocationError::Values { value_count: schema_len, source },
+            )
+        })?;
+
+        let mut reader = &bytes[bitmap_len..];
+        for index in 0..schema_len {
+            if bitmap[index / 8] & (1 << (index % 8)) != 0 {
+                values.push(Value::Null);
+                continue;
+            }
+
+            let tag = read_u8(&mut reader)?;
+            if tag == TAG_NULL {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    "packed tuple encodes NULL outside the null bitmap",
+                ));
+            }
+            let len = read_value_len(&mut reader)?;
+            values.push(read_value(&mut reader, tag, len)?);
+        }
+
+        if !reader.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "trailing bytes after packed tuple payload",
+            ));
+        }
+
+        Ok(Self(values))
+    }
+}
+
+fn null_bitmap_len(schema_len: usize) -> usize {
+    schema_len.div_ceil(8)
 }
 
 impl<'a> ValueRef<'a> {
@@ -317,6 +474,10 @@ fn value_ref_from_field<'a>(bytes: &'a [u8], field: &ValueField) -> ValueRef<'a>
         TAG_UNSIGNED_INTEGER => ValueRef::UnsignedInteger(u64::from_be_bytes(
             payload.try_into().expect("validated u64 payload"),
         )),
+        TAG_BLOB => ValueRef::Blob(payload),
+        TAG_TIMESTAMP => ValueRef::Timestamp(decode_ordered_i64(
+            payload.try_into().expect("validated i64 payload"),
+        )),
         _ => unreachable!("validated tuple value tag"),
     }
 }
@@ -360,6 +521,8 @@ impl<'a> From<&'a Value> for ValueRef<'a> {
             Value::Integer(value) => Self::Integer(*value),
             Value::Float(value) => Self::Float(*value),
             Value::UnsignedInteger(value) => Self::UnsignedInteger(*value),
+            Value::Blob(value) => Self::Blob(value),
+            Value::Timestamp(value) => Self::Timestamp(*value),
         }
     }
 }
@@ -373,6 +536,8 @@ impl<'a> From<ValueRef<'a>> for Value {
             ValueRef::Integer(value) => Self::Integer(value),
             ValueRef::Float(value) => Self::Float(value),
             ValueRef::UnsignedInteger(value) => Self::UnsignedInteger(value),
+            ValueRef::Blob(value) => Self::Blob(value.to_owned()),
+            ValueRef::Timestamp(value) => Self::Timestamp(value),
         }
     }
 }
@@ -464,6 +629,17 @@ fn write_value_ref<W: Write>(writer: &mut W, value: ValueRef<'_>) -> io::Result<
             write_tlv_header(writer, TAG_UNSIGNED_INTEGER, U64_LENGTH)?;
             writer.write_all(&value.to_be_bytes())
         }
+        ValueRef::Blob(value) => {
+            let len = u32::try_from(value.len()).map_err(|_| {
+                io::Error::new(io::ErrorKind::InvalidInput, "blob length exceeds u32::MAX")
+            })?;
+            write_tlv_header(writer, TAG_BLOB, len)?;
+            writer.write_all(value)
+        }
+        ValueRef::Timestamp(value) => {
+            write_tlv_header(writer, TAG_TIMESTAMP, I64_LENGTH)?;
+            writer.write_all(&encode_ordered_i64(value))
+        }
     }
 }
 
@@ -479,15 +655,9 @@ fn read_value<R: Read>(reader: &mut R, tag: u8, len: u32) -> io::Result<Value> {
             Ok(Value::Null)
         }
         TAG_STRING => {
-            let mut bytes = Vec::new();
-            bytes.try_reserve_exact(len as usize).map_err(|source| {
-                io::Error::new(
-                    io::ErrorKind::OutOfMemory,
-                    TupleAllocationError::StringBytes { byte_count: len as usize, source },
-                )
+            let bytes = read_payload(reader, len, |byte_count, source| {
+                TupleAllocationError::StringBytes { byte_count, source }
             })?;
-            bytes.resize(len as usize, 0);
-            reader.read_exact(&mut bytes)?;
             String::from_utf8(bytes).map(Value::String).map_err(invalid_data)
         }
         TAG_BOOLEAN => {
@@ -521,6 +691,16 @@ fn read_value<R: Read>(reader: &mut R, tag: u8, len: u32) -> io::Result<Value> {
             reader.read_exact(&mut bytes)?;
             Ok(Value::UnsignedInteger(u64::from_be_bytes(bytes)))
         }
+        TAG_BLOB => read_payload(reader, len, |byte_count, source| {
+            TupleAllocationError::BlobBytes { byte_count, source }
+        })
+        .map(Value::Blob),
+        TAG_TIMESTAMP => {
+            validate_len(tag, len, I64_LENGTH)?;
+            let mut bytes = [0; size_of::<i64>()];
+            reader.read_exact(&mut bytes)?;
+            Ok(Value::Timestamp(decode_ordered_i64(bytes)))
+        }
         actual => Err(io::Error::new(
             io::ErrorKind::InvalidData,
             format!("unknown tuple value tag: {actual}"),
@@ -551,6 +731,8 @@ fn validate_value_payload(tag: u8, payload: &[u8]) -> io::Result<()> {
             validate_float(decode_ordered_f32(payload.try_into().expect("validated f32 payload")))
         }
         TAG_UNSIGNED_INTEGER => validate_len(tag, payload.len() as u32, U64_LENGTH),
+        TAG_BLOB => Ok(()),
+        TAG_TIMESTAMP => validate_len(tag, payload.len() as u32, I64_LENGTH),
         actual => Err(io::Error::new(
             io::ErrorKind::InvalidData,
             format!("unknown tuple value tag: {actual}"),
@@ -558,6 +740,20 @@ fn validate_value_payload(tag: u8, payload: &[u8]) -> io::Result<()> {
     }
 }
 
+fn read_payload<R: Read>(
+    reader: &mut R,
+    len: u32,
+    allocation_error: fn(usize, TryReserveError) -> TupleAllocationError,
+) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    bytes.try_reserve_exact(len as usize).map_err(|source| {
+        io::Error::new(io::ErrorKind::OutOfMemory, allocation_error(len as usize, source))
+    })?;
+    bytes.resize(len as usize, 0);
+    reader.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
 fn validate_len(tag: u8, actual: u32, expected: u32) -> io::Result<()> {
     if actual == expected {
         Ok(())
@@ -620,6 +816,14 @@ fn decode_ordered_i32(bytes: [u8; size_of::<i32>()]) -> i32 {
     (u32::from_be_bytes(bytes) ^ 0x8000_0000) as i32
 }
 
+fn encode_ordered_i64(value: i64) -> [u8; size_of::<i64>()] {
+    ((value as u64) ^ 0x8000_0000_0000_0000).to_be_bytes()
+}
+
+fn decode_ordered_i64(bytes: [u8; size_of::<i64>()]) -> i64 {
+    (u64::from_be_bytes(bytes) ^ 0x8000_0000_0000_0000) as i64
+}
+
 fn encode_ordered_f32(value: f32) -> io::Result<[u8; size_of::<f32>()]> {
     validate_float(value)?;
     let value = if value == 0.0 { 0.0 } else { value };
@@ -767,6 +971,27 @@ mod tests {
         assert_eq!(error.kind(), io::ErrorKind::InvalidData);
     }
 
+    #[test]
+    fn rejects_value_count_smaller_than_encoded_values() {
+        let tuple = Tuple::new(vec![Value::Integer(1), Value::String("extra".to_owned())]);
+        let mut bytes = tuple.to_bytes().unwrap();
+        bytes[..size_of::<u32>()].copy_from_slice(&1u32.to_le_bytes());
+
+        let error = Tuple::from_bytes(&bytes).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        let error = TupleView::parse(&bytes).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn accepts_value_count_matching_encoded_values() {
+        let tuple = Tuple::new(vec![Value::Integer(1), Value::String("extra".to_owned())]);
+        let bytes = tuple.to_bytes().unwrap();
+
+        assert_eq!(Tuple::from_bytes(&bytes).unwrap(), tuple);
+        assert_eq!(TupleView::parse(&bytes).unwrap().len(), 2);
+    }
+
     #[test]
     fn empty_tuple_round_trips() {
         let tuple = Tuple::new(vec![]);
@@ -815,9 +1040,64 @@ mod tests {
         assert_eq!(error.kind(), io::ErrorKind::InvalidData);
     }
 
+    #[test]
+    fn blob_tuples_round_trip() {
+        let large = (0..100_000).map(|byte| byte as u8).collect::<Vec<_>>();
+        for blob in [Vec::new(), vec![0x00, 0xde, 0xad, 0xff], large] {
+            let tuple = Tuple::new(vec![Value::Blob(blob), Value::Integer(1)]);
+            let bytes = tuple.to_bytes().unwrap();
+
+            assert_eq!(read(&bytes).unwrap(), tuple);
+            assert_eq!(Tuple::from_bytes(&bytes).unwrap(), tuple);
+        }
+    }
+
+    #[test]
+    fn blob_writes_length_prefixed_raw_bytes() {
+        let mut expected = Vec::new();
+        expected.push(TAG_BLOB);
+        expected.extend_from_slice(&3u32.to_be_bytes());
+        expected.extend_from_slice(&[1, 2, 3]);
+
+        assert_eq!(encoded_value(ValueRef::Blob(&[1, 2, 3])), expected);
+    }
+
+    #[test]
+    fn timestamp_tuples_round_trip() {
+        for micros in [0, -1_000_000, i64::MIN, i64::MAX] {
+            let tuple = Tuple::new(vec![Value::Timestamp(micros)]);
+            let bytes = tuple.to_bytes().unwrap();
+
+            assert_eq!(read(&bytes).unwrap(), tuple);
+            assert_eq!(Tuple::from_bytes(&bytes).unwrap(), tuple);
+        }
+    }
+
+    #[test]
+    fn timestamp_uses_fixed_width_payload() {
+        let bytes = encoded_value(ValueRef::Timestamp(i64::MAX));
+
+        assert_eq!(bytes.len(), 1 + 4 + 8);
+        assert_eq!(bytes[0], TAG_TIMESTAMP);
+        assert_eq!(bytes[1..5], 8u32.to_be_bytes());
+    }
+
+    #[test]
+    fn rejects_unused_tag_after_timestamp() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.push(TAG_TIMESTAMP + 1);
+        bytes.extend_from_slice(&0u32.to_be_bytes());
+
+        assert_eq!(read(&bytes).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        assert_eq!(TupleView::parse(&bytes).unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+
     #[test]
     fn rejects_invalid_fixed_lengths() {
-        for tag in [TAG_NULL, TAG_BOOLEAN, TAG_INTEGER, TAG_FLOAT, TAG_UNSIGNED_INTEGER] {
+        for tag in
+            [TAG_NULL, TAG_BOOLEAN, TAG_INTEGER, TAG_FLOAT, TAG_UNSIGNED_INTEGER, TAG_TIMESTAMP]
+        {
             let mut bytes = Vec::new();
             bytes.extend_from_slice(&1u32.to_le_bytes());
             bytes.push(tag);
@@ -1000,6 +1280,12 @@ mod tests {
         );
         assert_encoded_value_order(ValueRef::Float(-1.5), ValueRef::Float(0.0), Ordering::Less);
         assert_encoded_value_order(ValueRef::Float(0.0), ValueRef::Float(2.25), Ordering::Less);
+        assert_encoded_value_order(ValueRef::Timestamp(-1), ValueRef::Timestamp(0), Ordering::Less);
+        assert_encoded_value_order(
+            ValueRef::Timestamp(0),
+            ValueRef::Timestamp(i64::MAX),
+            Ordering::Less,
+        );
         assert_encoded_value_order(ValueRef::String("a"), ValueRef::String("aa"), Ordering::Less);
         assert_encoded_value_order(ValueRef::String("aa"), ValueRef::String("b"), Ordering::Less);
     }
@@ -1034,6 +1320,132 @@ mod tests {
         assert_eq!(error.kind(), io::ErrorKind::InvalidData);
     }
 
+    #[test]
+    fn packed_null_heavy_tuple_round_trips_smaller_than_tagged_form() {
+        let mut values = vec![Value::Null; 12];
+        values[0] = Value::Integer(1);
+        values[5] = Value::String("five".to_owned());
+        values[11] = Value::Boolean(true);
+        let tuple = Tuple::new(values);
+
+        let packed = tuple.serialize_packed().unwrap();
+
+        assert_eq!(Tuple::deserialize_packed(&packed, 12).unwrap(), tuple);
+        assert!(packed.len() < tuple.to_bytes().unwrap().len());
+    }
+
+    #[test]
+    fn packed_tuple_writes_expected_bitmap() {
+        let tuple = Tuple::new(vec![Value::Null, Value::Integer(258), Value::Null]);
+
+        let mut expected = vec![0b0000_0101];
+        expected.push(TAG_INTEGER);
+        expected.extend_from_slice(&4u32.to_be_bytes());
+        expected.extend_from_slice(&encode_ordered_i32(258));
+
+        assert_eq!(tuple.serialize_packed().unwrap(), expected);
+    }
+
+    #[test]
+    fn packed_empty_tuple_round_trips() {
+        let tuple = Tuple::new(vec![]);
+
+        let packed = tuple.serialize_packed().unwrap();
+        assert!(packed.is_empty());
+        assert_eq!(Tuple::deserialize_packed(&packed, 0).unwrap(), tuple);
+    }
+
+    #[test]
+    fn packed_tuple_rejects_malformed_payloads() {
+        let tuple = Tuple::new(vec![Value::Integer(1), Value::Null]);
+        let packed = tuple.serialize_packed().unwrap();
+
+        let error = Tuple::deserialize_packed(&packed[..packed.len() - 1], 2).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::UnexpectedEof);
+
+        let mut trailing = packed.clone();
+        trailing.push(0);
+        let error = Tuple::deserialize_packed(&trailing, 2).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let mut stray_bit = packed.clone();
+        stray_bit[0] |= 0b1000_0000;
+        let error = Tuple::deserialize_packed(&stray_bit, 2).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+
+        let mut inline_null = vec![0];
+        inline_null.push(TAG_NULL);
+        inline_null.extend_from_slice(&0u32.to_be_bytes());
+        let error = Tuple::deserialize_packed(&inline_null, 1).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn cmp_value_compares_numbers_across_types() {
+        assert_eq!(Value::Integer(1).cmp_value(&Value::Float(1.5)), Some(Ordering::Less));
+        assert_eq!(Value::Float(2.0).cmp_value(&Value::Integer(2)), Some(Ordering::Equal));
+        assert_eq!(Value::Integer(-1).cmp_value(&Value::Float(-1.5)), Some(Ordering::Greater));
+        assert_eq!(Value::Integer(-1).cmp_value(&Value::UnsignedInteger(0)), Some(Ordering::Less));
+        assert_eq!(
+            Value::UnsignedInteger(u64::MAX).cmp_value(&Value::Integer(i32::MAX)),
+            Some(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn cmp_value_sorts_null_first() {
+        let values = [
+            Value::Boolean(false),
+            Value::Integer(i32::MIN),
+            Value::Float(f32::NEG_INFINITY),
+            Value::Timestamp(i64::MIN),
+            Value::String(String::new()),
+            Value::Blob(Vec::new()),
+        ];
+
+        assert_eq!(Value::Null.cmp_value(&Value::Null), Some(Ordering::Equal));
+        for value in &values {
+            assert_eq!(Value::Null.cmp_value(value), Some(Ordering::Less));
+            assert_eq!(value.cmp_value(&Value::Null), Some(Ordering::Greater));
+        }
+    }
+
+    #[test]
+    fn cmp_value_compares_strings_lexicographically() {
+        let a = Value::String("apple".to_owned());
+        let b = Value::String("banana".to_owned());
+
+        assert_eq!(a.cmp_value(&b), Some(Ordering::Less));
+        assert_eq!(b.cmp_value(&a), Some(Ordering::Greater));
+        assert_eq!(a.cmp_value(&a.clone()), Some(Ordering::Equal));
+        assert_eq!(
+            Value::String("a".to_owned()).cmp_value(&Value::String("aa".to_owned())),
+            Some(Ordering::Less)
+        );
+    }
+
+    #[test]
+    fn cmp_value_orders_different_types_by_type() {
+        assert_eq!(Value::Boolean(true).cmp_value(&Value::Integer(0)), Some(Ordering::Less));
+        assert_eq!(
+            Value::Integer(i32::MAX).cmp_value(&Value::String("0".to_owned())),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            Value::Blob(vec![]).cmp_value(&Value::String("z".to_owned())),
+            Some(Ordering::Greater)
+        );
+    }
+
+    #[test]
+    fn cmp_value_is_undefined_for_nan() {
+        let nan = Value::Float(f32::NAN);
+
+        assert_eq!(nan.cmp_value(&nan), None);
+        assert_eq!(nan.cmp_value(&Value::Integer(1)), None);
+        assert_eq!(Value::Null.cmp_value(&nan), None);
+    }
+
     #[test]
     fn rejects_nan_float_during_serialization_and_validation() {
         let error = Tuple::new(vec![Value::Float(f32::NAN)]).to_bytes().unwrap_err();
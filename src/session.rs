@@ -235,7 +235,8 @@ fn statement_is_mutating(statement: &Statement<'_>) -> bool {
         | Statement::Insert(_)
         | Statement::Update(_)
         | Statement::Delete(_) => true,
-        Statement::Select(_) | Statement::Explain(_) => false,
+        Statement::Select(_) | Statement::Values(_) | Statement::Explain(_) => false,
+        Statement::With(query) => statement_is_mutating(&query.statement),
     }
 }
 
@@ -1,17 +1,164 @@
 use crate::sql_parser::lexer::token_kind::TokenKind;
 use crate::sql_parser::parser::stmt::create_table::ColumnConstraint;
 
-use std::fmt::Display;
+use std::{fmt::Display, ops::Range};
 
 #[derive(Debug, PartialEq, Copy, Clone)]
 pub struct SQLError<'a> {
     pub kind: SQLErrorKind<'a>,
+    /// Byte offset where the offending input starts.
     pub pos: usize,
+    /// Exclusive byte offset where the offending input ends, when known.
+    pub end: Option<usize>,
 }
 
 impl<'a> SQLError<'a> {
     pub fn new(kind: SQLErrorKind<'a>, pos: usize) -> Self {
-        Self { kind, pos }
+        Self { kind, pos, end: None }
+    }
+
+    pub fn spanned(kind: SQLErrorKind<'a>, pos: usize, end: usize) -> Self {
+        Self { kind, pos, end: Some(end) }
+    }
+
+    /// Returns the byte range covered by this error, empty when only the start is known.
+    pub fn span(&self) -> Range<usize> {
+        self.pos..self.end.unwrap_or(self.pos)
+    }
+
+    /// Returns whether [`Parser::parse_script`](crate::sql_parser::parser::Parser::parse_script)
+    /// can find further statements in `source` after this error.
+    ///
+    /// This refines [`SQLErrorKind::is_recoverable`]: an end-of-input kind
+    /// reported on a `;`, as for `EXPLAIN;`, ended only its statement.
+    pub fn is_recoverable(&self, source: &str) -> bool {
+        self.kind.is_recoverable()
+            || source.get(self.pos..).is_some_and(|rest| rest.starts_with(';'))
+    }
+
+    /// Resolves the error's byte offset to a line and column in `source`.
+    ///
+    /// `source` must be the text the error was produced from. Lines and
+    /// columns are 1-based, and columns count characters rather than bytes.
+    pub fn with_source(&self, source: &str) -> RenderedError {
+        let mut pos = self.pos.min(source.len());
+        while !source.is_char_boundary(pos) {
+            pos -= 1;
+        }
+        let before = &source[..pos];
+        let line_start = before.rfind('\n').map_or(0, |newline| newline + 1);
+        RenderedError {
+            message: self.kind.to_string(),
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+        }
+    }
+
+    /// Copies the error out of the parsed input so it can outlive it.
+    pub fn into_owned(self) -> OwnedSQLError {
+        OwnedSQLError {
+            code: self.kind.code(),
+            message: self.kind.to_string(),
+            pos: self.pos,
+            end: self.end,
+        }
+    }
+}
+
+/// A [`SQLError`] located by line and column, from [`SQLError::with_source`].
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RenderedError {
+    pub message: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+impl Display for RenderedError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Error at line {}, column {}: {}.", self.line, self.column, self.message)
+    }
+}
+
+impl std::error::Error for RenderedError {}
+
+/// A [`SQLError`] whose message no longer borrows from the parsed input.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct OwnedSQLError {
+    pub code: ErrorCode,
+    pub message: String,
+    pub pos: usize,
+    pub end: Option<usize>,
+}
+
+impl OwnedSQLError {
+    /// Returns the byte range covered by this error, empty when only the start is known.
+    pub fn span(&self) -> Range<usize> {
+        self.pos..self.end.unwrap_or(self.pos)
+    }
+}
+
+impl Display for OwnedSQLError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Error at position {}: [{}] {}.", self.pos, self.code, self.message)
+    }
+}
+
+impl std::error::Error for OwnedSQLError {}
+
+/// A stable identifier for each [`SQLErrorKind`], so callers can branch on
+/// errors without matching messages.
+///
+/// Codes never change meaning once assigned. `E1xxx` are lexical errors,
+/// `E2xxx` syntax errors, `E3xxx` errors in otherwise well-formed statements
+/// and `E4xxx` exceeded parser limits.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone)]
+#[repr(u16)]
+pub enum ErrorCode {
+    UnterminatedString = 1001,
+    InvalidCharacter = 1002,
+    InvalidNumber = 1003,
+    UnterminatedIdentifier = 1004,
+    EmptyIdentifier = 1005,
+    UnexpectedEnd = 2001,
+    UnexpectedTokenKind = 2002,
+    ExpectedOneOf = 2003,
+    ExpectedOther = 2004,
+    ExpectedIdentifier = 2005,
+    ReservedKeywordAsIdentifier = 2006,
+    ExpectedInteger = 2007,
+    ExpectedNonNegativeInteger = 2008,
+    ExpectedExpression = 2009,
+    ExpectedPredicate = 2010,
+    ExpectedCommaOrSemicolon = 2011,
+    InvalidOperator = 2012,
+    InvalidPrefixOperator = 2013,
+    InvalidDataType = 2014,
+    UnclosedParenthesis = 2015,
+    UnterminatedStatement = 2016,
+    Other = 2017,
+    OffsetBeforeLimit = 2018,
+    DuplicateOffset = 2019,
+    DuplicateLimit = 2020,
+    ValuesArityMismatch = 3001,
+    DuplicateConstraint = 3002,
+    DuplicateColumn = 3003,
+    DistinctWildcard = 3004,
+    MultiplePrimaryKeys = 3005,
+    InvalidPrimaryKey = 3006,
+    ConflictingConstraint = 3007,
+    ExpressionTooDeep = 4001,
+    StatementTooLarge = 4002,
+}
+
+impl ErrorCode {
+    pub fn number(self) -> u16 {
+        self as u16
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "E{}", self.number())
     }
 }
 
@@ -19,23 +166,152 @@ impl<'a> SQLError<'a> {
 pub enum SQLErrorKind<'a> {
     ExpectedCommaOrSemicolon,
     ExpectedExpression,
-    ExpectedIdentifier { got: TokenKind<'a> },
-    ExpectedInteger { got: TokenKind<'a> },
-    ExpectedNonNegativeInteger { got: i32 },
-    ExpectedOther { expected: TokenKind<'a> },
-    InvalidCharacter { c: char },
+    ExpectedPredicate,
+    ExpressionTooDeep {
+        limit: usize,
+    },
+    StatementTooLarge {
+        limit: usize,
+    },
+    ValuesArityMismatch {
+        expected: usize,
+        got: usize,
+    },
+    ExpectedIdentifier {
+        got: TokenKind<'a>,
+    },
+    /// A keyword appeared where an identifier was expected, spelled as in the source.
+    ReservedKeywordAsIdentifier {
+        keyword: &'a str,
+    },
+    ExpectedInteger {
+        got: TokenKind<'a>,
+    },
+    ExpectedNonNegativeInteger {
+        got: i32,
+    },
+    ExpectedOther {
+        expected: TokenKind<'a>,
+    },
+    InvalidCharacter {
+        c: char,
+    },
     InvalidNumber,
-    InvalidOperator { op: TokenKind<'a> },
-    InvalidPrefixOperator { op: TokenKind<'a> },
-    InvalidDataType { got: TokenKind<'a> },
+    InvalidOperator {
+        op: TokenKind<'a>,
+    },
+    InvalidPrefixOperator {
+        op: TokenKind<'a>,
+    },
+    InvalidDataType {
+        got: TokenKind<'a>,
+    },
     Other(TokenKind<'a>),
     UnclosedParenthesis,
+    /// `LIMIT` was written after `OFFSET` rather than before it.
+    OffsetBeforeLimit,
+    /// `OFFSET` follows a `LIMIT offset, count` that already set the offset.
+    DuplicateOffset,
+    /// A query has more than one `LIMIT` clause.
+    DuplicateLimit,
     UnexpectedEnd,
-    UnexpectedTokenKind { expected: TokenKind<'a>, got: TokenKind<'a> },
+    UnexpectedTokenKind {
+        expected: TokenKind<'a>,
+        got: TokenKind<'a>,
+    },
+    /// None of the tokens or clauses that could come next was found.
+    ExpectedOneOf {
+        expected: &'static [&'static str],
+        got: TokenKind<'a>,
+    },
     UnterminatedStatement,
     UnterminatedString,
-    DuplicateConstraint { column: &'a str, constraint: ColumnConstraint },
-    InvalidPrimaryKey { reason: &'static str },
+    UnterminatedIdentifier,
+    /// A quoted identifier has nothing between its quotes, as in ``` `` ```.
+    EmptyIdentifier,
+    DuplicateConstraint {
+        column: &'a str,
+        constraint: ColumnConstraint,
+    },
+    /// A column is declared both `NULLABLE` and `NOT NULL`.
+    ConflictingConstraint {
+        column: &'a str,
+        constraint: ColumnConstraint,
+        conflicts_with: ColumnConstraint,
+    },
+    DuplicateColumn {
+        name: &'a str,
+    },
+    DistinctWildcard,
+    MultiplePrimaryKeys {
+        count: usize,
+    },
+    InvalidPrimaryKey {
+        reason: &'static str,
+    },
+}
+
+impl SQLErrorKind<'_> {
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            SQLErrorKind::UnterminatedString => ErrorCode::UnterminatedString,
+            SQLErrorKind::InvalidCharacter { .. } => ErrorCode::InvalidCharacter,
+            SQLErrorKind::InvalidNumber => ErrorCode::InvalidNumber,
+            SQLErrorKind::UnterminatedIdentifier => ErrorCode::UnterminatedIdentifier,
+            SQLErrorKind::EmptyIdentifier => ErrorCode::EmptyIdentifier,
+            SQLErrorKind::UnexpectedEnd => ErrorCode::UnexpectedEnd,
+            SQLErrorKind::UnexpectedTokenKind { .. } => ErrorCode::UnexpectedTokenKind,
+            SQLErrorKind::ExpectedOneOf { .. } => ErrorCode::ExpectedOneOf,
+            SQLErrorKind::ExpectedOther { .. } => ErrorCode::ExpectedOther,
+            SQLErrorKind::ExpectedIdentifier { .. } => ErrorCode::ExpectedIdentifier,
+            SQLErrorKind::ReservedKeywordAsIdentifier { .. } => {
+                ErrorCode::ReservedKeywordAsIdentifier
+            }
+            SQLErrorKind::ExpectedInteger { .. } => ErrorCode::ExpectedInteger,
+            SQLErrorKind::ExpectedNonNegativeInteger { .. } => {
+                ErrorCode::ExpectedNonNegativeInteger
+            }
+            SQLErrorKind::ExpectedExpression => ErrorCode::ExpectedExpression,
+            SQLErrorKind::ExpectedPredicate => ErrorCode::ExpectedPredicate,
+            SQLErrorKind::ExpectedCommaOrSemicolon => ErrorCode::ExpectedCommaOrSemicolon,
+            SQLErrorKind::InvalidOperator { .. } => ErrorCode::InvalidOperator,
+            SQLErrorKind::InvalidPrefixOperator { .. } => ErrorCode::InvalidPrefixOperator,
+            SQLErrorKind::InvalidDataType { .. } => ErrorCode::InvalidDataType,
+            SQLErrorKind::UnclosedParenthesis => ErrorCode::UnclosedParenthesis,
+            SQLErrorKind::UnterminatedStatement => ErrorCode::UnterminatedStatement,
+            SQLErrorKind::Other(_) => ErrorCode::Other,
+            SQLErrorKind::OffsetBeforeLimit => ErrorCode::OffsetBeforeLimit,
+            SQLErrorKind::DuplicateOffset => ErrorCode::DuplicateOffset,
+            SQLErrorKind::DuplicateLimit => ErrorCode::DuplicateLimit,
+            SQLErrorKind::ValuesArityMismatch { .. } => ErrorCode::ValuesArityMismatch,
+            SQLErrorKind::DuplicateConstraint { .. } => ErrorCode::DuplicateConstraint,
+            SQLErrorKind::ConflictingConstraint { .. } => ErrorCode::ConflictingConstraint,
+            SQLErrorKind::DuplicateColumn { .. } => ErrorCode::DuplicateColumn,
+            SQLErrorKind::DistinctWildcard => ErrorCode::DistinctWildcard,
+            SQLErrorKind::MultiplePrimaryKeys { .. } => ErrorCode::MultiplePrimaryKeys,
+            SQLErrorKind::InvalidPrimaryKey { .. } => ErrorCode::InvalidPrimaryKey,
+            SQLErrorKind::ExpressionTooDeep { .. } => ErrorCode::ExpressionTooDeep,
+            SQLErrorKind::StatementTooLarge { .. } => ErrorCode::StatementTooLarge,
+        }
+    }
+
+    /// Returns whether skipping to the next `;` and parsing on, as
+    /// [`Parser::parse_script`](crate::sql_parser::parser::Parser::parse_script)
+    /// does, can find further statements.
+    ///
+    /// Errors raised at the end of the input, including an unterminated string
+    /// or quoted identifier that runs to the end, leave nothing to recover.
+    pub fn is_recoverable(&self) -> bool {
+        !matches!(
+            self,
+            SQLErrorKind::UnterminatedString
+                | SQLErrorKind::UnterminatedIdentifier
+                | SQLErrorKind::UnexpectedEnd
+                | SQLErrorKind::ExpectedExpression
+                | SQLErrorKind::ExpectedCommaOrSemicolon
+                | SQLErrorKind::UnterminatedStatement
+        )
+    }
 }
 
 impl Display for SQLErrorKind<'_> {
@@ -44,6 +320,12 @@ impl Display for SQLErrorKind<'_> {
             SQLErrorKind::UnterminatedString => {
                 write!(f, "Unterminated string")
             }
+            SQLErrorKind::UnterminatedIdentifier => {
+                write!(f, "Unterminated quoted identifier")
+            }
+            SQLErrorKind::EmptyIdentifier => {
+                write!(f, "Quoted identifier is empty")
+            }
             SQLErrorKind::InvalidCharacter { c } => {
                 write!(f, "Invalid character '{c}'")
             }
@@ -56,6 +338,9 @@ impl Display for SQLErrorKind<'_> {
             SQLErrorKind::UnexpectedTokenKind { expected, got } => {
                 write!(f, "Unexpected token, got {got}, expected {expected}")
             }
+            SQLErrorKind::ExpectedOneOf { expected, got } => {
+                write!(f, "Expected one of {} but found {got}", expected.join(", "))
+            }
             SQLErrorKind::InvalidPrefixOperator { op } => {
                 write!(f, "Invalid prefix operator '{op}'")
             }
@@ -65,12 +350,33 @@ impl Display for SQLErrorKind<'_> {
             SQLErrorKind::UnclosedParenthesis => {
                 write!(f, "Parenthesis not closed")
             }
+            SQLErrorKind::OffsetBeforeLimit => {
+                write!(f, "LIMIT must come before OFFSET")
+            }
+            SQLErrorKind::DuplicateOffset => {
+                write!(f, "OFFSET cannot follow LIMIT with an offset")
+            }
+            SQLErrorKind::DuplicateLimit => {
+                write!(f, "LIMIT cannot appear more than once")
+            }
             SQLErrorKind::Other(token) => {
                 write!(f, "Bad token: {token}")
             }
             SQLErrorKind::ExpectedExpression => {
                 write!(f, "Unexpected end of input, expected expression")
             }
+            SQLErrorKind::ExpectedPredicate => {
+                write!(f, "Expected a boolean predicate")
+            }
+            SQLErrorKind::ExpressionTooDeep { limit } => {
+                write!(f, "Expression nesting exceeds the limit of {limit}")
+            }
+            SQLErrorKind::StatementTooLarge { limit } => {
+                write!(f, "Statement exceeds the limit of {limit} nodes")
+            }
+            SQLErrorKind::ValuesArityMismatch { expected, got } => {
+                write!(f, "VALUES row has {got} values, expected {expected}")
+            }
             SQLErrorKind::UnterminatedStatement => {
                 write!(f, "Unterminated statement, missing semicolon")
             }
@@ -80,6 +386,9 @@ impl Display for SQLErrorKind<'_> {
             SQLErrorKind::ExpectedIdentifier { got } => {
                 write!(f, "Expected identifier got token kind {got}")
             }
+            SQLErrorKind::ReservedKeywordAsIdentifier { keyword } => {
+                write!(f, "Expected identifier, got reserved keyword '{keyword}'")
+            }
             SQLErrorKind::ExpectedCommaOrSemicolon => {
                 write!(f, "Expected colon or semicolon")
             }
@@ -95,6 +404,21 @@ impl Display for SQLErrorKind<'_> {
             SQLErrorKind::DuplicateConstraint { column, constraint } => {
                 write!(f, "Duplicate constraint for column '{column}': {constraint}")
             }
+            SQLErrorKind::ConflictingConstraint { column, constraint, conflicts_with } => {
+                write!(
+                    f,
+                    "Constraint {constraint} for column '{column}' conflicts with {conflicts_with}"
+                )
+            }
+            SQLErrorKind::DuplicateColumn { name } => {
+                write!(f, "Duplicate column name '{name}'")
+            }
+            SQLErrorKind::MultiplePrimaryKeys { count } => {
+                write!(f, "Table declares {count} primary keys, expected one")
+            }
+            SQLErrorKind::DistinctWildcard => {
+                write!(f, "DISTINCT cannot be applied to * inside an aggregate")
+            }
             SQLErrorKind::InvalidPrimaryKey { reason } => {
                 write!(f, "Invalid primary key: {reason}")
             }
@@ -104,6 +428,125 @@ impl Display for SQLErrorKind<'_> {
 
 impl Display for SQLError<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Error at position {}: {}.", self.pos, self.kind)
+        write!(f, "Error at position {}: [{}] {}.", self.pos, self.kind.code(), self.kind)
+    }
+}
+
+impl std::error::Error for SQLError<'_> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_parser::parser::Parser;
+
+    #[test]
+    fn test_with_source_reports_line_and_column() {
+        let s = "SELECT a\nFROM t\nWHERE a > ;";
+        let err = Parser::new(s).stmt().unwrap_err();
+        let rendered = err.with_source(s);
+        assert_eq!((rendered.line, rendered.column), (3, 11));
+        assert_eq!(rendered.to_string(), format!("Error at line 3, column 11: {}.", err.kind));
+
+        let err = SQLError::new(SQLErrorKind::UnexpectedEnd, 0);
+        assert_eq!((err.with_source(s).line, err.with_source(s).column), (1, 1));
+
+        // Offset just past a newline is the first column of the next line.
+        let err = SQLError::new(SQLErrorKind::UnexpectedEnd, 9);
+        assert_eq!((err.with_source(s).line, err.with_source(s).column), (2, 1));
+    }
+
+    #[test]
+    fn test_with_source_counts_characters_not_bytes() {
+        let s = "SELECT 'é',\n  'ü' | 1;";
+        let err = SQLError::new(SQLErrorKind::InvalidCharacter { c: '|' }, s.find('|').unwrap());
+        let rendered = err.with_source(s);
+        assert_eq!((rendered.line, rendered.column), (2, 7));
+
+        // Offsets past the end or inside a character are clamped.
+        let err = SQLError::new(SQLErrorKind::UnexpectedEnd, s.len() + 10);
+        assert_eq!(err.with_source(s).column, 11);
+        let err = SQLError::new(SQLErrorKind::UnexpectedEnd, s.find('é').unwrap() + 1);
+        assert_eq!(err.with_source(s).column, 9);
+    }
+
+    #[test]
+    fn test_into_owned_outlives_source() {
+        let owned = {
+            let s = String::from("SELECT FROM t;");
+            let err = Parser::new(&s).stmt().unwrap_err();
+            assert_eq!(err.into_owned().to_string(), err.to_string());
+            err.into_owned()
+        };
+        assert_eq!(owned.span(), 7..7);
+        let boxed: Box<dyn std::error::Error> = Box::new(owned);
+        assert!(boxed.to_string().starts_with("Error at position 7: [E2017]"));
+    }
+
+    #[test]
+    fn test_every_kind_has_a_unique_code() {
+        let token = TokenKind::Semicolon;
+        let kinds = [
+            SQLErrorKind::ExpectedCommaOrSemicolon,
+            SQLErrorKind::ExpectedExpression,
+            SQLErrorKind::ExpectedPredicate,
+            SQLErrorKind::ExpressionTooDeep { limit: 1 },
+            SQLErrorKind::StatementTooLarge { limit: 1 },
+            SQLErrorKind::ValuesArityMismatch { expected: 1, got: 2 },
+            SQLErrorKind::ExpectedIdentifier { got: token },
+            SQLErrorKind::ReservedKeywordAsIdentifier { keyword: "table" },
+            SQLErrorKind::ExpectedInteger { got: token },
+            SQLErrorKind::ExpectedNonNegativeInteger { got: -1 },
+            SQLErrorKind::ExpectedOther { expected: token },
+            SQLErrorKind::InvalidCharacter { c: '|' },
+            SQLErrorKind::InvalidNumber,
+            SQLErrorKind::InvalidOperator { op: token },
+            SQLErrorKind::InvalidPrefixOperator { op: token },
+            SQLErrorKind::InvalidDataType { got: token },
+            SQLErrorKind::Other(token),
+            SQLErrorKind::UnclosedParenthesis,
+            SQLErrorKind::OffsetBeforeLimit,
+            SQLErrorKind::DuplicateOffset,
+            SQLErrorKind::DuplicateLimit,
+            SQLErrorKind::UnexpectedEnd,
+            SQLErrorKind::UnexpectedTokenKind { expected: token, got: token },
+            SQLErrorKind::ExpectedOneOf { expected: &["';'"], got: token },
+            SQLErrorKind::UnterminatedStatement,
+            SQLErrorKind::UnterminatedString,
+            SQLErrorKind::UnterminatedIdentifier,
+            SQLErrorKind::EmptyIdentifier,
+            SQLErrorKind::DuplicateConstraint {
+                column: "id",
+                constraint: ColumnConstraint::Unique,
+            },
+            SQLErrorKind::ConflictingConstraint {
+                column: "id",
+                constraint: ColumnConstraint::NotNull,
+                conflicts_with: ColumnConstraint::Nullable,
+            },
+            SQLErrorKind::DuplicateColumn { name: "id" },
+            SQLErrorKind::DistinctWildcard,
+            SQLErrorKind::MultiplePrimaryKeys { count: 2 },
+            SQLErrorKind::InvalidPrimaryKey { reason: "missing" },
+        ];
+        let codes: std::collections::HashSet<_> = kinds.iter().map(SQLErrorKind::code).collect();
+        assert_eq!(codes.len(), kinds.len());
+        assert_eq!(SQLErrorKind::UnterminatedString.code().to_string(), "E1001");
+    }
+
+    #[test]
+    fn test_is_recoverable() {
+        let (_, errors) = Parser::new("SELECT a b; SELECT 'open").parse_script();
+        assert_eq!(errors.len(), 2);
+        assert!(errors[0].kind.is_recoverable());
+        assert!(!errors[1].kind.is_recoverable());
+        assert!(!SQLErrorKind::UnexpectedEnd.is_recoverable());
+
+        let s = "EXPLAIN; SELECT 'open";
+        let (_, errors) = Parser::new(s).parse_script();
+        assert_eq!(errors.len(), 2);
+        assert!(!errors[0].kind.is_recoverable());
+        assert!(errors[0].is_recoverable(s));
+        assert!(!errors[1].is_recoverable(s));
+        assert!(SQLErrorKind::DuplicateColumn { name: "id" }.is_recoverable());
     }
 }
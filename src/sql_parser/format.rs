@@ -0,0 +1,317 @@
+//! Multi-line SQL formatting for parsed statements.
+//!
+//! The [`Display`](std::fmt::Display) impls on the AST keep a statement on one
+//! line. [`Formatter`] instead puts each clause on its own line, splits long
+//! lists one item per line and can print keywords in lowercase. Expressions
+//! are still written in their compact form.
+
+use crate::sql_parser::{
+    lexer::{Lexer, token_kind::TokenKind},
+    parser::{
+        ident::quote_ident,
+        stmt::{
+            Statement, create_table::CreateTableQuery, insert::InsertSource, select::SelectQuery,
+        },
+    },
+};
+
+/// How keywords are spelled in formatted output.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum KeywordCase {
+    #[default]
+    Upper,
+    Lower,
+}
+
+/// Layout settings for a [`Formatter`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FormatOptions {
+    /// Number of spaces per indentation level.
+    pub indent_width: usize,
+    pub keyword_case: KeywordCase,
+    /// A list that would make its line longer than this is split one item per line.
+    pub max_line_width: usize,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self { indent_width: 4, keyword_case: KeywordCase::Upper, max_line_width: 80 }
+    }
+}
+
+/// Pretty-printer for [`Statement`]s.
+///
+/// The output parses back to the same statement as the compact form.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Formatter {
+    pub options: FormatOptions,
+}
+
+impl Formatter {
+    pub fn new(options: FormatOptions) -> Self {
+        Self { options }
+    }
+
+    /// Formats `statement`, ending the last line with a semicolon.
+    pub fn format(&self, statement: &Statement<'_>) -> String {
+        let mut lines = Vec::new();
+        self.statement(statement, 0, &mut lines);
+        let mut sql = lines.join("\n");
+        sql.push(';');
+        match self.options.keyword_case {
+            KeywordCase::Upper => sql,
+            KeywordCase::Lower => lowercase_keywords(&sql),
+        }
+    }
+
+    fn indent(&self, level: usize) -> String {
+        " ".repeat(level * self.options.indent_width)
+    }
+
+    fn statement(&self, statement: &Statement<'_>, level: usize, lines: &mut Vec<String>) {
+        let pad = self.indent(level);
+        match statement {
+            Statement::Explain(statement) => {
+                let start = lines.len();
+                self.statement(statement, level, lines);
+                lines[start].insert_str(pad.len(), "EXPLAIN ");
+            }
+            Statement::With(query) => {
+                for (index, cte) in query.ctes.iter().enumerate() {
+                    let keyword = if index == 0 { "WITH " } else { "" };
+                    lines.push(format!("{pad}{keyword}{} AS (", quote_ident(cte.name)));
+                    self.select(&cte.query, level + 1, lines);
+                    let separator = if index + 1 < query.ctes.len() { "," } else { "" };
+                    lines.push(format!("{pad}){separator}"));
+                }
+                self.statement(&query.statement, level, lines);
+            }
+            Statement::Select(query) => self.select(query, level, lines),
+            Statement::Update(query) => {
+                lines.push(format!("{pad}UPDATE {}", quote_ident(query.table)));
+                let assignments = query.assignments.0.iter().map(ToString::to_string);
+                self.list_clause("SET", assignments, level, lines);
+                if let Some(where_clause) = &query.where_clause {
+                    lines.push(format!("{pad}WHERE {where_clause}"));
+                }
+            }
+            Statement::Delete(query) => {
+                lines.push(format!("{pad}DELETE FROM {}", quote_ident(query.table)));
+                if let Some(where_clause) = &query.where_clause {
+                    lines.push(format!("{pad}WHERE {where_clause}"));
+                }
+            }
+            Statement::Insert(query) => {
+                lines.push(format!(
+                    "{pad}INSERT INTO {} ({})",
+                    quote_ident(query.table),
+                    query.columns
+                ));
+                match &query.source {
+                    InsertSource::Values(values) => {
+                        let rows = values.0.iter().map(|row| format!("({row})"));
+                        self.list_clause("VALUES", rows, level, lines);
+                    }
+                    InsertSource::Select(query) => self.select(query, level, lines),
+                }
+            }
+            Statement::Values(values) => {
+                let rows = values.0.iter().map(|row| format!("({row})"));
+                self.list_clause("VALUES", rows, level, lines);
+            }
+            Statement::CreateTable(query) => self.create_table(query, level, lines),
+            Statement::CreateIndex(query) => lines.push(format!(
+                "{pad}CREATE INDEX {} ON {} ({})",
+                quote_ident(query.index_name),
+                quote_ident(query.table_name),
+                query.columns
+            )),
+        }
+    }
+
+    fn select(&self, query: &SelectQuery<'_>, level: usize, lines: &mut Vec<String>) {
+        let pad = self.indent(level);
+        let keyword = if query.distinct { "SELECT DISTINCT" } else { "SELECT" };
+        self.list_clause(keyword, query.columns.0.iter().map(ToString::to_string), level, lines);
+        if let Some(table) = query.table {
+            lines.push(format!("{pad}FROM {}", quote_ident(table)));
+        }
+        if let Some(where_clause) = &query.where_clause {
+            lines.push(format!("{pad}WHERE {where_clause}"));
+        }
+        if let Some(group_by) = &query.group_by {
+            self.list_clause("GROUP BY", group_by.0.iter().map(ToString::to_string), level, lines);
+        }
+        if let Some(having) = &query.having {
+            lines.push(format!("{pad}HAVING {having}"));
+        }
+        if let Some(order_by) = &query.order_by {
+            self.list_clause(
+                "ORDER BY",
+                order_by.terms.iter().map(ToString::to_string),
+                level,
+                lines,
+            );
+        }
+        if let Some(limit) = query.limit {
+            lines.push(format!("{pad}LIMIT {limit}"));
+        }
+        if let Some(offset) = query.offset {
+            lines.push(format!("{pad}OFFSET {offset}"));
+        }
+    }
+
+    fn create_table(&self, query: &CreateTableQuery<'_>, level: usize, lines: &mut Vec<String>) {
+        let pad = self.indent(level);
+        let item_pad = self.indent(level + 1);
+        lines.push(format!("{pad}CREATE TABLE {} (", quote_ident(query.table_name)));
+        for (index, column) in query.columns.iter().enumerate() {
+            let separator = if index + 1 < query.columns.len() { "," } else { "" };
+            lines.push(format!("{item_pad}{column}{separator}"));
+        }
+        lines.push(format!("{pad})"));
+    }
+
+    /// Writes `keyword` followed by `items`, on one line if it fits and one item per line otherwise.
+    fn list_clause(
+        &self,
+        keyword: &str,
+        items: impl Iterator<Item = String>,
+        level: usize,
+        lines: &mut Vec<String>,
+    ) {
+        let pad = self.indent(level);
+        let items: Vec<_> = items.collect();
+        let line = format!("{pad}{keyword} {}", items.join(", "));
+        if items.len() <= 1 || line.len() <= self.options.max_line_width {
+            lines.push(line);
+            return;
+        }
+
+        lines.push(format!("{pad}{keyword}"));
+        let item_pad = self.indent(level + 1);
+        let last = items.len() - 1;
+        for (index, item) in items.into_iter().enumerate() {
+            let separator = if index < last { "," } else { "" };
+            lines.push(format!("{item_pad}{item}{separator}"));
+        }
+    }
+}
+
+/// Lowercases every keyword token in `sql`, leaving identifiers and string literals alone.
+fn lowercase_keywords(sql: &str) -> String {
+    let mut output = String::with_capacity(sql.len());
+    let mut copied = 0;
+    for token in Lexer::new(sql).map_while(Result::ok) {
+        if let TokenKind::Keyword(_) = token.kind {
+            output.push_str(&sql[copied..token.offset]);
+            output.push_str(&token.lexeme.to_ascii_lowercase());
+            copied = token.offset + token.lexeme.len();
+        }
+    }
+    output.push_str(&sql[copied..]);
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_parser::parser::Parser;
+
+    fn format_with(source: &str, options: FormatOptions) -> String {
+        let statement = Parser::new(source).stmt().unwrap();
+        let formatted = Formatter::new(options).format(&statement);
+        assert_eq!(Parser::new(&formatted).stmt().unwrap(), statement);
+        formatted
+    }
+
+    #[test]
+    fn test_format_nested_expressions() {
+        let s = "select a from t where (a + 1) * 2 > 3 and not (b or c in (select d from u));";
+        let expected = "\
+SELECT a
+FROM t
+WHERE (a + 1) * 2 > 3 AND NOT (b OR c IN (SELECT d FROM u));";
+        assert_eq!(format_with(s, FormatOptions::default()), expected);
+    }
+
+    #[test]
+    fn test_format_create_table_in_lowercase() {
+        let s = "CREATE TABLE users (id INT PRIMARY KEY, name TEXT NOT NULL, \
+                 Nickname TEXT DEFAULT 'NULL AND' NULLABLE);";
+        let options = FormatOptions {
+            indent_width: 2,
+            keyword_case: KeywordCase::Lower,
+            ..Default::default()
+        };
+        let expected = "\
+create table users (
+  id int primary key,
+  name text not null,
+  Nickname text default 'NULL AND' nullable
+);";
+        assert_eq!(format_with(s, options), expected);
+    }
+
+    #[test]
+    fn test_format_select_with_every_clause() {
+        let s = "SELECT DISTINCT dept, role, COUNT(*), AVG(salary) FROM staff WHERE salary > 0 \
+                 GROUP BY dept, role HAVING COUNT(*) > 1 ORDER BY dept DESC, role \
+                 LIMIT 10 OFFSET 5;";
+        let expected = "\
+SELECT DISTINCT dept, role, COUNT(*), AVG(salary)
+FROM staff
+WHERE salary > 0
+GROUP BY dept, role
+HAVING COUNT(*) > 1
+ORDER BY dept DESC, role
+LIMIT 10
+OFFSET 5;";
+        assert_eq!(format_with(s, FormatOptions::default()), expected);
+
+        let options = FormatOptions { max_line_width: 30, ..Default::default() };
+        let expected = "\
+SELECT DISTINCT
+    dept,
+    role,
+    COUNT(*),
+    AVG(salary)
+FROM staff
+WHERE salary > 0
+GROUP BY dept, role
+HAVING COUNT(*) > 1
+ORDER BY dept DESC, role
+LIMIT 10
+OFFSET 5;";
+        assert_eq!(format_with(s, options), expected);
+    }
+
+    #[test]
+    fn test_format_indents_common_table_expressions() {
+        let s = "EXPLAIN WITH a AS (SELECT x FROM t), b AS (SELECT y FROM a WHERE y > 1) \
+                 INSERT INTO c (x) VALUES (1), (2);";
+        let expected = "\
+EXPLAIN WITH a AS (
+    SELECT x
+    FROM t
+),
+b AS (
+    SELECT y
+    FROM a
+    WHERE y > 1
+)
+INSERT INTO c (x)
+VALUES (1), (2);";
+        assert_eq!(format_with(s, FormatOptions::default()), expected);
+    }
+
+    #[test]
+    fn test_format_quotes_identifiers_in_lowercase() {
+        let s = r#"SELECT "order", "first name" FROM "my table" ORDER BY "order";"#;
+        let options = FormatOptions { keyword_case: KeywordCase::Lower, ..Default::default() };
+        let expected = r#"select "order", "first name"
+from "my table"
+order by "order";"#;
+        assert_eq!(format_with(s, options), expected);
+    }
+}
@@ -1,24 +1,57 @@
 pub mod token;
 pub mod token_kind;
+pub mod trivia;
+
+use std::borrow::Cow;
+use std::collections::VecDeque;
 
 use crate::sql_parser::error::{SQLError, SQLErrorKind};
 use token::Token;
-use token_kind::{NumberKind, TokenKind};
+use token_kind::{Keyword, NumberKind, TokenKind};
+
+/// Configuration for how a [`Lexer`] splits its input.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct LexerOptions<'a> {
+    /// Characters skipped between tokens. `None` skips Unicode whitespace.
+    pub treat_as_whitespace: Option<&'a [char]>,
+}
 
 #[derive(Debug)]
 pub struct Lexer<'a> {
-    pub rest: &'a str,
-    pub position: usize,
-
-    pub peeked: Option<Result<Token<'a>, SQLError<'a>>>,
+    rest: &'a str,
+    pub(crate) position: usize,
+    /// Byte offset just past the most recently consumed token, ignoring trailing
+    /// whitespace and comments. Unlike `position`, peeking does not move it.
+    pub(crate) token_end: usize,
+
+    /// Tokens lexed by [`Lexer::peek_nth`] but not yet returned by `next`, in order.
+    pub(crate) peeked: VecDeque<Result<Token<'a>, SQLError<'a>>>,
+    options: LexerOptions<'a>,
 }
 
 impl<'a> Lexer<'a> {
     pub fn new(source: &'a str) -> Self {
-        Self { rest: source, position: 0, peeked: None }
+        Self::with_options(source, LexerOptions::default())
+    }
+
+    pub fn with_options(source: &'a str, options: LexerOptions<'a>) -> Self {
+        Self { rest: source, position: 0, token_end: 0, peeked: VecDeque::new(), options }
     }
 
-    pub fn expect_where(
+    /// Lexes all of `source`, stopping at the first error.
+    pub fn tokenize(source: &'a str) -> Result<Vec<Token<'a>>, SQLError<'a>> {
+        Lexer::new(source).tokenize_all()
+    }
+
+    /// Drains the remaining input into a token vector, stopping at the first error.
+    ///
+    /// Unlike [`Lexer::tokenize`], this keeps the lexer's [`LexerOptions`] and
+    /// any tokens already peeked.
+    pub fn tokenize_all(self) -> Result<Vec<Token<'a>>, SQLError<'a>> {
+        self.collect()
+    }
+
+    pub(crate) fn expect_where(
         &mut self,
         check: impl Fn(TokenKind<'a>) -> bool,
     ) -> Result<(), SQLError<'a>> {
@@ -26,21 +59,41 @@ impl<'a> Lexer<'a> {
             Some(Ok(token)) if check(token.kind) => Ok(()),
             Some(Ok(token)) => Err(SQLError::new(SQLErrorKind::Other(token.kind), token.offset)),
             Some(Err(err)) => Err(err),
-            None => Err(SQLError::new(SQLErrorKind::UnexpectedEnd, self.position)),
+            None => Err(self.unexpected_end()),
         }
     }
 
-    pub fn expect_token(&mut self, expected: TokenKind<'a>) -> Result<(), SQLError<'a>> {
+    /// An [`SQLErrorKind::UnexpectedEnd`] placed right after the last token.
+    pub(crate) fn unexpected_end(&self) -> SQLError<'a> {
+        SQLError::new(SQLErrorKind::UnexpectedEnd, self.token_end)
+    }
+
+    pub(crate) fn expect_token(&mut self, expected: TokenKind<'a>) -> Result<(), SQLError<'a>> {
         match self.expect_where(|kind| kind == expected) {
-            Err(SQLError { kind: SQLErrorKind::Other(got), pos }) => {
-                Err(SQLError { kind: SQLErrorKind::UnexpectedTokenKind { expected, got }, pos })
+            Err(err @ SQLError { kind: SQLErrorKind::Other(got), .. }) => {
+                Err(SQLError { kind: SQLErrorKind::UnexpectedTokenKind { expected, got }, ..err })
             }
             other => other,
         }
     }
 
+    /// Consumes the next token if it is `keyword`, returning whether it did.
+    pub(crate) fn next_if_keyword(&mut self, keyword: Keyword) -> bool {
+        let matched = matches!(
+            self.peek(),
+            Some(Ok(Token { kind: TokenKind::Keyword(next), .. })) if *next == keyword
+        );
+        if matched {
+            self.next();
+        }
+        matched
+    }
+
     fn skip_whitespace(&mut self) {
-        let trimmed = self.rest.trim_start();
+        let trimmed = match self.options.treat_as_whitespace {
+            Some(whitespace) => self.rest.trim_start_matches(whitespace),
+            None => self.rest.trim_start(),
+        };
         let whitespace_skipped = self.rest.len() - trimmed.len();
         self.position += whitespace_skipped;
         self.rest = trimmed;
@@ -56,11 +109,26 @@ impl<'a> Lexer<'a> {
         }
     }
 
+    /// Skips a line comment up to and including its line ending.
+    ///
+    /// Both `\n` and `\r\n` end a comment, as does a bare `\r`.
+    fn skip_line_comment(&mut self) {
+        let comment_len = self.rest.find(['\r', '\n']).unwrap_or(self.rest.len());
+        let line_ending_len = if self.rest[comment_len..].starts_with("\r\n") {
+            2
+        } else {
+            usize::from(comment_len < self.rest.len())
+        };
+        let skipped = comment_len + line_ending_len;
+        self.position += skipped;
+        self.rest = &self.rest[skipped..];
+    }
+
     fn skip_whitespace_and_comments(&mut self) {
         loop {
             self.skip_whitespace();
             if self.rest.starts_with("--") {
-                self.skip_to_next("\n");
+                self.skip_line_comment();
             } else if self.rest.starts_with("/*") {
                 self.skip_to_next("*/");
             } else {
@@ -69,19 +137,25 @@ impl<'a> Lexer<'a> {
         }
     }
 
-    pub fn peek(&mut self) -> Option<&Result<Token<'a>, SQLError<'_>>> {
-        if self.peeked.is_some() {
-            return self.peeked.as_ref();
+    pub fn peek(&mut self) -> Option<&Result<Token<'a>, SQLError<'a>>> {
+        self.peek_nth(0)
+    }
+
+    /// Returns the token `n` places ahead without consuming it, so `peek_nth(0)`
+    /// is the token `next` returns. `position` moves past every token looked at.
+    pub fn peek_nth(&mut self, n: usize) -> Option<&Result<Token<'a>, SQLError<'a>>> {
+        while self.peeked.len() <= n {
+            let token = self.lex_next()?;
+            self.peeked.push_back(token);
         }
-        self.peeked = self.next();
-        self.peeked.as_ref()
+        self.peeked.get(n)
     }
 }
 
 enum Started {
     Number,
-    DoubleQuotedString,
-    SingleQuotedString,
+    String,
+    QuotedIdentifier(char),
     Keyword,
     MaybeEqualsOp(MaybeEquals),
 }
@@ -97,12 +171,32 @@ impl<'a> Iterator for Lexer<'a> {
     type Item = Result<Token<'a>, SQLError<'a>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        if let Some(next) = self.peeked.take() {
-            return Some(next);
+        let next = match self.peeked.pop_front() {
+            Some(next) => Some(next),
+            None => self.lex_next(),
+        };
+        if let Some(Ok(token)) = &next {
+            self.token_end = token.offset + token.lexeme.len();
         }
+        next
+    }
+}
 
+impl<'a> Lexer<'a> {
+    fn lex_next(&mut self) -> Option<Result<Token<'a>, SQLError<'a>>> {
         self.skip_whitespace_and_comments();
 
+        let offset = self.position;
+        let source = self.rest;
+        let token = self.lex_token_kind()?.map(|kind| Token {
+            kind,
+            offset,
+            lexeme: &source[..self.position - offset],
+        });
+        Some(token)
+    }
+
+    fn lex_token_kind(&mut self) -> Option<Result<TokenKind<'a>, SQLError<'a>>> {
         let mut chars = self.rest.chars();
         let c = chars.next()?;
         let c_at = self.position;
@@ -110,15 +204,14 @@ impl<'a> Iterator for Lexer<'a> {
         self.rest = chars.as_str();
         self.position += c.len_utf8();
 
-        let tok = |kind: TokenKind<'a>| -> Option<Result<Token<'a>, SQLError>> {
-            Some(Ok(Token { kind, offset: c_at }))
-        };
+        let tok = |kind: TokenKind<'a>| Some(Ok(kind));
 
         let started = match c {
             '0'..='9' => Started::Number,
-            '"' => Started::DoubleQuotedString,
-            '\'' => Started::SingleQuotedString,
-            c if c.is_alphabetic() => Started::Keyword,
+            '.' if self.rest.starts_with(|c: char| c.is_ascii_digit()) => Started::Number,
+            '\'' => Started::String,
+            quote @ ('"' | '`') => Started::QuotedIdentifier(quote),
+            c if c.is_alphabetic() || c == '_' => Started::Keyword,
             '<' => Started::MaybeEqualsOp(MaybeEquals::LessThan),
             '>' => Started::MaybeEqualsOp(MaybeEquals::GreaterThan),
             '!' => Started::MaybeEqualsOp(MaybeEquals::NotEquals),
@@ -129,12 +222,19 @@ impl<'a> Iterator for Lexer<'a> {
             '-' => return tok(TokenKind::Minus),
             '*' => return tok(TokenKind::Asterisk),
             '/' => return tok(TokenKind::Slash),
+            '^' => return tok(TokenKind::Caret),
             ',' => return tok(TokenKind::Comma),
             ';' => return tok(TokenKind::Semicolon),
+            ':' if self.rest.starts_with(':') => {
+                self.position += 1;
+                self.rest = &self.rest[1..];
+                return tok(TokenKind::DoubleColon);
+            }
 
             c => {
-                return Some(Err(SQLError::new(
+                return Some(Err(SQLError::spanned(
                     SQLErrorKind::InvalidCharacter { c },
+                    c_at,
                     self.position,
                 )));
             }
@@ -142,43 +242,90 @@ impl<'a> Iterator for Lexer<'a> {
 
         match started {
             Started::Number => {
-                let literal = c_rest.split(|c: char| !matches!(c, '.' | '0'..='9')).next()?;
+                // Digits with at most one decimal point, which may lead (`.5`) or trail (`12.`).
+                let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+                let mut len = digits(c_rest);
+                if c_rest[len..].starts_with('.') {
+                    len += 1 + digits(&c_rest[len + 1..]);
+                }
+                let literal = &c_rest[..len];
+
+                // A further point, as in `1.2.3` or `1..2`, makes the whole run one bad literal.
+                let run = c_rest.split(|c: char| !matches!(c, '.' | '0'..='9')).next()?;
+                let extra = run.len() - c.len_utf8();
+                self.position += extra;
+                self.rest = &self.rest[extra..];
 
-                let kind = if let Ok(parsed) = literal.parse::<i32>() {
-                    NumberKind::Integer(parsed)
-                } else if let Ok(parsed) = literal.parse::<f32>() {
-                    NumberKind::Float(parsed)
+                let kind = if run.len() > len {
+                    None
+                } else if let Ok(parsed) = literal.parse::<i32>() {
+                    Some(NumberKind::Integer(parsed))
                 } else {
-                    return Some(Err(SQLError::new(SQLErrorKind::InvalidNumber, c_at)));
+                    literal.parse::<f64>().ok().map(NumberKind::Float)
                 };
-
-                let token = Token { kind: TokenKind::Number(kind), offset: c_at };
-                let extra = literal.len() - c.len_utf8();
-                self.position += extra;
-                self.rest = &self.rest[extra..];
-                Some(Ok(token))
+                match kind {
+                    Some(kind) => tok(TokenKind::Number(kind)),
+                    None => Some(Err(SQLError::spanned(
+                        SQLErrorKind::InvalidNumber,
+                        c_at,
+                        c_at + run.len(),
+                    ))),
+                }
+            }
+            Started::String => {
+                // A doubled quote is an escaped quote and does not end the literal.
+                let mut len = 0;
+                loop {
+                    let Some(index) = self.rest[len..].find('\'') else {
+                        return Some(Err(SQLError::spanned(
+                            SQLErrorKind::UnterminatedString,
+                            c_at,
+                            self.position + self.rest.len(),
+                        )));
+                    };
+                    len += index + 1;
+                    if !self.rest[len..].starts_with('\'') {
+                        break;
+                    }
+                    len += 1;
+                }
+                let literal = &self.rest[..len - 1];
+                self.position += len;
+                self.rest = &self.rest[len..];
+                tok(TokenKind::String(literal))
             }
-            quote @ (Started::SingleQuotedString | Started::DoubleQuotedString) => {
-                let terminator = if let Started::SingleQuotedString = quote { '\'' } else { '"' };
-                let Some((literal, rest)) = self.rest.split_once(terminator) else {
-                    return Some(Err(SQLError::new(SQLErrorKind::UnterminatedString, c_at)));
+            Started::QuotedIdentifier(quote) => {
+                let Some((identifier, rest)) = self.rest.split_once(quote) else {
+                    return Some(Err(SQLError::spanned(
+                        SQLErrorKind::UnterminatedIdentifier,
+                        c_at,
+                        self.position + self.rest.len(),
+                    )));
                 };
-                let token = Token { kind: TokenKind::String(literal), offset: c_at };
-                self.position += literal.len() + 1;
+                self.position += identifier.len() + 1;
                 self.rest = rest;
-                Some(Ok(token))
+                if identifier.is_empty() {
+                    return Some(Err(SQLError::spanned(
+                        SQLErrorKind::EmptyIdentifier,
+                        c_at,
+                        self.position,
+                    )));
+                }
+                tok(TokenKind::Identifier(identifier))
             }
             Started::Keyword => {
-                let is_not_part_of_keyword = |c: char| !(c.is_alphabetic() || c == '_');
+                let is_not_part_of_keyword = |c: char| !(c.is_alphanumeric() || c == '_');
                 let literal = c_rest.split(is_not_part_of_keyword).next()?;
 
-                let kind = TokenKind::from(literal);
-                let token = Token { kind, offset: c_at };
-
                 let extra = literal.len() - c.len_utf8();
                 self.position += extra;
                 self.rest = &self.rest[extra..];
-                Some(Ok(token))
+                tok(TokenKind::from(literal))
+            }
+            Started::MaybeEqualsOp(MaybeEquals::LessThan) if self.rest.starts_with('>') => {
+                self.position += 1;
+                self.rest = &self.rest[1..];
+                tok(TokenKind::NotEquals)
             }
             Started::MaybeEqualsOp(maybe_equals) => {
                 let kind = if self.rest.starts_with('=') {
@@ -196,20 +343,38 @@ impl<'a> Iterator for Lexer<'a> {
                         MaybeEquals::GreaterThan => TokenKind::GreaterThan,
                         MaybeEquals::Equals => TokenKind::Equals,
                         MaybeEquals::NotEquals => {
-                            return Some(Err(SQLError::new(
+                            return Some(Err(SQLError::spanned(
                                 SQLErrorKind::InvalidCharacter { c: '!' },
+                                c_at,
                                 self.position,
                             )));
                         }
                     }
                 };
-                let token = Token { kind, offset: c_at };
-                Some(Ok(token))
+                tok(kind)
             }
         }
     }
 }
 
+/// Returns the value of a string literal from its text between the quotes,
+/// collapsing each doubled quote into one.
+pub(crate) fn unescape_string(literal: &str) -> Cow<'_, str> {
+    // The lexer only lets quotes through in doubled pairs.
+    if !literal.contains('\'') {
+        return Cow::Borrowed(literal);
+    }
+    let mut value = String::with_capacity(literal.len());
+    let mut chars = literal.chars();
+    while let Some(c) = chars.next() {
+        value.push(c);
+        if c == '\'' {
+            chars.next();
+        }
+    }
+    Cow::Owned(value)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -224,9 +389,8 @@ mod tests {
     impl LexerExt for Lexer<'_> {
         #[track_caller]
         fn expect(&mut self, kind: TokenKind, offset: usize) {
-            let expected = Token { kind, offset };
-            let got = self.next();
-            assert_eq!(Some(Ok(expected)), got);
+            let got = self.next().unwrap().unwrap();
+            assert_eq!((got.kind, got.offset), (kind, offset));
         }
     }
 
@@ -247,6 +411,168 @@ mod tests {
         lexer.expect(TokenKind::EqualsEquals, 0);
         lexer.expect(TokenKind::NotEquals, 3);
         lexer.expect(TokenKind::Equals, 6);
+
+        let s = "a<>b <> >";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Identifier("a"), 0);
+        lexer.expect(TokenKind::NotEquals, 1);
+        assert_eq!(lexer.token_end, 3);
+        lexer.expect(TokenKind::Identifier("b"), 3);
+        lexer.expect(TokenKind::NotEquals, 5);
+        lexer.expect(TokenKind::GreaterThan, 8);
+    }
+
+    #[test]
+    fn test_peek_nth_does_not_disturb_next() {
+        let s = "CREATE INDEX idx";
+        let mut lexer = Lexer::new(s);
+        let index = Token { kind: TokenKind::Keyword(Keyword::Index), offset: 7, lexeme: "INDEX" };
+        assert_eq!(lexer.peek_nth(1), Some(&Ok(index)));
+        assert_eq!(lexer.peek_nth(5), None);
+        assert_eq!(
+            lexer.peek(),
+            Some(&Ok(Token {
+                kind: TokenKind::Keyword(Keyword::Create),
+                offset: 0,
+                lexeme: "CREATE"
+            }))
+        );
+
+        assert_eq!(lexer.token_end, 0);
+        lexer.expect(TokenKind::Keyword(Keyword::Create), 0);
+        assert_eq!(lexer.token_end, "CREATE".len());
+        assert_eq!(lexer.expect_token(TokenKind::Keyword(Keyword::Index)), Ok(()));
+        assert_eq!(
+            lexer.expect_token(TokenKind::Keyword(Keyword::Table)),
+            Err(SQLError::new(
+                SQLErrorKind::UnexpectedTokenKind {
+                    expected: TokenKind::Keyword(Keyword::Table),
+                    got: TokenKind::Identifier("idx"),
+                },
+                13
+            ))
+        );
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[test]
+    fn test_lexemes_reconstruct_the_source() {
+        let s = r#"select "Full Name", `t`, x::Int FROM t WHERE a<>1.50 AND b = 'it''s' -- done"#;
+        let tokens = Lexer::tokenize(s).unwrap();
+        let lexemes: Vec<_> = tokens.iter().map(|token| token.lexeme).collect();
+        assert_eq!(
+            lexemes,
+            [
+                "select",
+                r#""Full Name""#,
+                ",",
+                "`t`",
+                ",",
+                "x",
+                "::",
+                "Int",
+                "FROM",
+                "t",
+                "WHERE",
+                "a",
+                "<>",
+                "1.50",
+                "AND",
+                "b",
+                "=",
+                "'it''s'"
+            ]
+        );
+        for token in tokens {
+            assert_eq!(&s[token.offset..token.offset + token.lexeme.len()], token.lexeme);
+        }
+    }
+
+    #[test]
+    fn test_tokenize() {
+        let kinds: Vec<_> =
+            Lexer::tokenize("SELECT a;").unwrap().into_iter().map(|token| token.kind).collect();
+        assert_eq!(
+            kinds,
+            [TokenKind::Keyword(Keyword::Select), TokenKind::Identifier("a"), TokenKind::Semicolon]
+        );
+        assert_eq!(
+            Lexer::tokenize("SELECT 'a"),
+            Err(SQLError::spanned(SQLErrorKind::UnterminatedString, 7, 9))
+        );
+    }
+
+    #[test]
+    fn test_tokenize_all() {
+        let s = "a|b ,1";
+        let options = LexerOptions { treat_as_whitespace: Some(&[' ', '|']) };
+        let mut lexer = Lexer::with_options(s, options);
+        assert!(lexer.peek().is_some());
+        let tokens = lexer.tokenize_all().unwrap();
+        let expected = [
+            (TokenKind::Identifier("a"), 0, "a"),
+            (TokenKind::Identifier("b"), 2, "b"),
+            (TokenKind::Comma, 4, ","),
+            (TokenKind::Number(Integer(1)), 5, "1"),
+        ];
+        let got: Vec<_> = tokens.iter().map(|t| (t.kind, t.offset, t.lexeme)).collect();
+        assert_eq!(got, expected);
+
+        let s = "SELECT a, # b, $ c;";
+        assert_eq!(
+            Lexer::new(s).tokenize_all(),
+            Err(SQLError::spanned(SQLErrorKind::InvalidCharacter { c: '#' }, 10, 11))
+        );
+        assert_eq!(Lexer::new("").tokenize_all(), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn test_double_colon() {
+        let s = "a::INT :: b";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Identifier("a"), 0);
+        lexer.expect(TokenKind::DoubleColon, 1);
+        lexer.expect(TokenKind::Keyword(Keyword::Int), 3);
+        lexer.expect(TokenKind::DoubleColon, 7);
+        lexer.expect(TokenKind::Identifier("b"), 10);
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[test]
+    fn test_single_colon_is_invalid() {
+        let s = "a: b";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Identifier("a"), 0);
+        assert_eq!(
+            lexer.next(),
+            Some(Err(SQLError::spanned(SQLErrorKind::InvalidCharacter { c: ':' }, 1, 2)))
+        );
+
+        let s = "a:::b";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Identifier("a"), 0);
+        lexer.expect(TokenKind::DoubleColon, 1);
+        assert_eq!(
+            lexer.next(),
+            Some(Err(SQLError::spanned(SQLErrorKind::InvalidCharacter { c: ':' }, 3, 4)))
+        );
+    }
+
+    #[test]
+    fn test_invalid_character_points_at_the_character() {
+        let s = "a ! b";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Identifier("a"), 0);
+        let err = lexer.next().unwrap().unwrap_err();
+        assert_eq!(err, SQLError::spanned(SQLErrorKind::InvalidCharacter { c: '!' }, 2, 3));
+        assert_eq!(&s[err.span()], "!");
+
+        let s = "a\u{a0}b";
+        let options = LexerOptions { treat_as_whitespace: Some(&[' ']) };
+        let mut lexer = Lexer::with_options(s, options);
+        lexer.expect(TokenKind::Identifier("a"), 0);
+        let err = lexer.next().unwrap().unwrap_err();
+        assert_eq!(&s[err.span()], "\u{a0}");
     }
 
     #[test]
@@ -271,11 +597,49 @@ mod tests {
     fn test_lex_floating_point_number() {
         let s = "12.345";
         let mut lexer = Lexer::new(s);
-        lexer.expect(TokenKind::Number(Float(12.345f32)), 0);
+        lexer.expect(TokenKind::Number(Float(12.345)), 0);
         assert!(lexer.rest.is_empty());
         assert_eq!(lexer.position, s.len());
     }
 
+    #[test]
+    fn test_lex_float_keeps_double_precision() {
+        let mut lexer = Lexer::new("0.1 16777217.5");
+        lexer.expect(TokenKind::Number(Float(0.1)), 0);
+        lexer.expect(TokenKind::Number(Float(16_777_217.5)), 4);
+    }
+
+    #[test]
+    fn test_lex_number_shapes() {
+        let s = "07 .5 12. 0.25";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Number(Integer(7)), 0);
+        lexer.expect(TokenKind::Number(Float(0.5)), 3);
+        lexer.expect(TokenKind::Number(Float(12.0)), 6);
+        lexer.expect(TokenKind::Number(Float(0.25)), 10);
+        assert_eq!(lexer.next(), None);
+
+        for s in ["1.2.3", "1..2", ".5.", "12.."] {
+            let mut lexer = Lexer::new(s);
+            let err = lexer.next().unwrap().unwrap_err();
+            assert_eq!(err, SQLError::spanned(SQLErrorKind::InvalidNumber, 0, s.len()), "{s}");
+            assert_eq!(lexer.next(), None);
+        }
+
+        let s = "SELECT 1.2.3 + 4";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Keyword(Keyword::Select), 0);
+        let err = lexer.next().unwrap().unwrap_err();
+        assert_eq!(&s[err.span()], "1.2.3");
+        lexer.expect(TokenKind::Plus, 13);
+
+        let s = "a . b";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Identifier("a"), 0);
+        let err = lexer.next().unwrap().unwrap_err();
+        assert_eq!(err.kind, SQLErrorKind::InvalidCharacter { c: '.' });
+    }
+
     #[test]
     fn test_lex_number_between_whitespace() {
         let s = " 1234 ";
@@ -286,10 +650,11 @@ mod tests {
     }
 
     #[test]
-    fn test_double_quoted_string() {
-        let s = r#""hello world""#;
+    fn test_double_quoted_identifier() {
+        let s = r#""hello world" "order""#;
         let mut lexer = Lexer::new(s);
-        lexer.expect(TokenKind::String("hello world"), 0);
+        lexer.expect(TokenKind::Identifier("hello world"), 0);
+        lexer.expect(TokenKind::Identifier("order"), 14);
     }
 
     #[test]
@@ -323,6 +688,11 @@ mod tests {
         lexer.expect(TokenKind::Identifier("some_table"), 12);
         lexer.expect(TokenKind::Keyword(Keyword::Values), 23);
 
+        let s = "integer bool";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Keyword(Keyword::Int), 0);
+        lexer.expect(TokenKind::Keyword(Keyword::Boolean), 8);
+
         let s = "bEgIn; cOmMiT; rOlLbAcK; sTdDeV(x);";
         let mut lexer = Lexer::new(s);
         lexer.expect(TokenKind::Keyword(Keyword::Begin), 0);
@@ -334,6 +704,104 @@ mod tests {
         lexer.expect(TokenKind::Keyword(Keyword::Aggregate(Aggregate::StdDev)), 25);
     }
 
+    #[test]
+    fn test_keywords_match_ascii_case_insensitively_only() {
+        let s = "SeLeCt select SELECT ſelect straße";
+        let kinds: Vec<_> = Lexer::tokenize(s).unwrap().into_iter().map(|t| t.kind).collect();
+        let select = TokenKind::Keyword(Keyword::Select);
+        // `ſ` uppercases to `S`, but only ASCII letters are folded when matching keywords.
+        let expected = [
+            select,
+            select,
+            select,
+            TokenKind::Identifier("ſelect"),
+            TokenKind::Identifier("straße"),
+        ];
+        assert_eq!(kinds, expected);
+    }
+
+    #[test]
+    #[ignore = "benchmark"]
+    fn bench_lex_large_script() {
+        let statement = "SELECT id, name, COUNT(*) FROM users WHERE age >= 18 AND active \
+                         GROUP BY name ORDER BY name DESC LIMIT 10; -- trailing comment\n";
+        let script = statement.repeat(4096);
+        let start = std::time::Instant::now();
+        let tokens = Lexer::tokenize(&script).unwrap();
+        let elapsed = start.elapsed();
+        println!("lexed {} tokens from {} bytes in {elapsed:?}", tokens.len(), script.len());
+        assert_eq!(tokens.len(), 27 * 4096);
+    }
+
+    #[test]
+    fn test_every_keyword_round_trips_through_as_str() {
+        use Keyword::*;
+        use token_kind::Aggregate::{Avg, Count, Max, Min, StdDev, Sum};
+        // Each keyword names the next one, so a new variant fails to compile
+        // until it is linked into the chain.
+        let next = |keyword: Keyword| match keyword {
+            Explain => Some(With),
+            With => Some(As),
+            As => Some(Select),
+            Select => Some(Distinct),
+            Distinct => Some(From),
+            From => Some(Where),
+            Where => Some(Group),
+            Group => Some(Having),
+            Having => Some(Order),
+            Order => Some(By),
+            By => Some(Asc),
+            Asc => Some(Desc),
+            Desc => Some(True),
+            True => Some(False),
+            False => Some(Null),
+            Null => Some(And),
+            And => Some(Or),
+            Or => Some(Not),
+            Not => Some(In),
+            In => Some(Limit),
+            Limit => Some(Offset),
+            Offset => Some(Update),
+            Update => Some(Set),
+            Set => Some(Delete),
+            Delete => Some(Insert),
+            Insert => Some(Into),
+            Into => Some(Values),
+            Values => Some(Create),
+            Create => Some(Table),
+            Table => Some(Index),
+            Index => Some(On),
+            On => Some(Int),
+            Int => Some(BigInt),
+            BigInt => Some(Float),
+            Float => Some(Text),
+            Text => Some(Varchar),
+            Varchar => Some(Boolean),
+            Boolean => Some(Blob),
+            Blob => Some(Primary),
+            Primary => Some(Key),
+            Key => Some(Nullable),
+            Nullable => Some(Unique),
+            Unique => Some(Default),
+            Default => Some(Begin),
+            Begin => Some(Commit),
+            Commit => Some(Rollback),
+            Rollback => Some(Aggregate(Sum)),
+            Aggregate(Sum) => Some(Aggregate(Avg)),
+            Aggregate(Avg) => Some(Aggregate(StdDev)),
+            Aggregate(StdDev) => Some(Aggregate(Min)),
+            Aggregate(Min) => Some(Aggregate(Max)),
+            Aggregate(Max) => Some(Aggregate(Count)),
+            Aggregate(Count) => None,
+        };
+
+        let keywords: Vec<_> = std::iter::successors(Some(Explain), |&k| next(k)).collect();
+        for keyword in keywords {
+            assert_eq!(TokenKind::from(keyword.as_str()), TokenKind::Keyword(keyword));
+            assert_eq!(keyword.to_string(), keyword.as_str());
+        }
+    }
+
     #[test]
     fn test_expression() {
         let s = "12 + 23 * (36 / 8)";
@@ -346,18 +814,127 @@ mod tests {
         lexer.expect(TokenKind::Number(Integer(36)), 11);
         lexer.expect(TokenKind::Slash, 14);
         lexer.expect(TokenKind::Number(Integer(8)), 16);
+
+        let s = "2^3";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Number(Integer(2)), 0);
+        lexer.expect(TokenKind::Caret, 1);
+        lexer.expect(TokenKind::Number(Integer(3)), 2);
+    }
+
+    #[test]
+    fn test_doubled_quote_does_not_end_string() {
+        let s = "'it''s ok' ''";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::String("it''s ok"), 0);
+        lexer.expect(TokenKind::String(""), 11);
+        assert_eq!(lexer.next(), None);
+
+        assert_eq!(unescape_string("it''s ok"), "it's ok");
+        assert_eq!(unescape_string("''''"), "''");
+        assert!(matches!(unescape_string(r#"say "hi""#), Cow::Borrowed(r#"say "hi""#)));
+
+        let s = "'it''s";
+        let mut lexer = Lexer::new(s);
+        assert_eq!(
+            lexer.next(),
+            Some(Err(SQLError::spanned(SQLErrorKind::UnterminatedString, 0, s.len())))
+        );
+    }
+
+    #[test]
+    fn test_quoted_identifiers() {
+        let s = "SELECT `select`, `first name` FROM t;";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Keyword(Keyword::Select), 0);
+        lexer.expect(TokenKind::Identifier("select"), 7);
+        lexer.expect(TokenKind::Comma, 15);
+        lexer.expect(TokenKind::Identifier("first name"), 17);
+        lexer.expect(TokenKind::Keyword(Keyword::From), 30);
+
+        let s = r#"SELECT "a`b", `c"d` FROM "my table";"#;
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Keyword(Keyword::Select), 0);
+        lexer.expect(TokenKind::Identifier("a`b"), 7);
+        lexer.expect(TokenKind::Comma, 12);
+        lexer.expect(TokenKind::Identifier(r#"c"d"#), 14);
+        lexer.expect(TokenKind::Keyword(Keyword::From), 20);
+        lexer.expect(TokenKind::Identifier("my table"), 25);
+
+        for s in ["SELECT `first name", r#"SELECT "first name"#] {
+            let mut lexer = Lexer::new(s);
+            lexer.expect(TokenKind::Keyword(Keyword::Select), 0);
+            assert_eq!(
+                lexer.next(),
+                Some(Err(SQLError::spanned(SQLErrorKind::UnterminatedIdentifier, 7, s.len())))
+            );
+        }
+
+        for s in ["SELECT `` FROM t;", r#"SELECT "" FROM t;"#] {
+            let mut lexer = Lexer::new(s);
+            lexer.expect(TokenKind::Keyword(Keyword::Select), 0);
+            assert_eq!(
+                lexer.next(),
+                Some(Err(SQLError::spanned(SQLErrorKind::EmptyIdentifier, 7, 9)))
+            );
+        }
     }
 
     #[test]
     fn test_unterminated_string() {
-        let s = r#""hello world"#;
+        let s = "'hello world";
         let mut lexer = Lexer::new(s);
         assert_eq!(
             lexer.next(),
-            Some(Err(SQLError { kind: SQLErrorKind::UnterminatedString, pos: 0 }))
+            Some(Err(SQLError::spanned(SQLErrorKind::UnterminatedString, 0, s.len())))
         );
     }
 
+    #[test]
+    fn test_unterminated_string_span_covers_remaining_input() {
+        let s = "SELECT 'abc, def FROM t";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Keyword(Keyword::Select), 0);
+
+        let err = lexer.next().unwrap().unwrap_err();
+        assert_eq!(err.kind, SQLErrorKind::UnterminatedString);
+        assert_eq!(err.span(), 7..s.len());
+        assert_eq!(&s[err.span()], "'abc, def FROM t");
+    }
+
+    #[test]
+    fn test_custom_whitespace_splits_tokens() {
+        let s = "a|b";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Identifier("a"), 0);
+        assert!(matches!(
+            lexer.next(),
+            Some(Err(SQLError { kind: SQLErrorKind::InvalidCharacter { c: '|' }, .. }))
+        ));
+
+        let options = LexerOptions { treat_as_whitespace: Some(&[' ', '|']) };
+        let mut lexer = Lexer::with_options(s, options);
+        lexer.expect(TokenKind::Identifier("a"), 0);
+        lexer.expect(TokenKind::Identifier("b"), 2);
+        assert_eq!(lexer.next(), None);
+    }
+
+    #[test]
+    fn test_custom_whitespace_can_exclude_unicode_spaces() {
+        let s = "1\u{a0}2";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Number(Integer(1)), 0);
+        lexer.expect(TokenKind::Number(Integer(2)), 3);
+
+        let options = LexerOptions { treat_as_whitespace: Some(&[' ']) };
+        let mut lexer = Lexer::with_options(s, options);
+        lexer.expect(TokenKind::Number(Integer(1)), 0);
+        assert!(matches!(
+            lexer.next(),
+            Some(Err(SQLError { kind: SQLErrorKind::InvalidCharacter { c: '\u{a0}' }, .. }))
+        ));
+    }
+
     #[test]
     fn test_line_comment() {
         let s = "3 -- 4 5";
@@ -371,6 +948,29 @@ mod tests {
         lexer.expect(TokenKind::Number(Integer(6)), 9);
     }
 
+    #[test]
+    fn test_line_comment_with_crlf() {
+        let s = "3 -- 4 5\r\n6";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Number(Integer(3)), 0);
+        lexer.expect(TokenKind::Number(Integer(6)), 10);
+        assert_eq!(lexer.next(), None);
+
+        let s = "-- hello world\r\n-- another comment\r\n123 * 456";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Number(Integer(123)), 36);
+        lexer.expect(TokenKind::Asterisk, 40);
+        lexer.expect(TokenKind::Number(Integer(456)), 42);
+    }
+
+    #[test]
+    fn test_line_comment_with_bare_carriage_return() {
+        let s = "3 -- 4 5\r6";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Number(Integer(3)), 0);
+        lexer.expect(TokenKind::Number(Integer(6)), 9);
+    }
+
     #[test]
     fn test_block_comment() {
         let s = "3 /* 4 5 */ 6";
@@ -401,7 +1001,20 @@ mod tests {
         let s = "åäö";
         let mut lexer = Lexer::new(s);
         let got = lexer.next();
-        let expected = Token { kind: TokenKind::Identifier("åäö"), offset: 0 };
+        let expected = Token { kind: TokenKind::Identifier("åäö"), offset: 0, lexeme: "åäö" };
         assert_eq!(Some(Ok(expected)), got);
     }
+
+    #[test]
+    fn test_identifiers_with_digits_and_underscores() {
+        let s = "col_1 _tmp a1b2 _ 1a";
+        let mut lexer = Lexer::new(s);
+        lexer.expect(TokenKind::Identifier("col_1"), 0);
+        lexer.expect(TokenKind::Identifier("_tmp"), 6);
+        lexer.expect(TokenKind::Identifier("a1b2"), 11);
+        lexer.expect(TokenKind::Identifier("_"), 16);
+        lexer.expect(TokenKind::Number(NumberKind::Integer(1)), 18);
+        lexer.expect(TokenKind::Identifier("a"), 19);
+        assert_eq!(lexer.next(), None);
+    }
 }
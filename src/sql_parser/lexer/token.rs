@@ -6,6 +6,8 @@ use crate::sql_parser::lexer::token_kind::TokenKind;
 pub struct Token<'a> {
     pub kind: TokenKind<'a>,
     pub offset: usize,
+    /// The token's exact source text, including any quotes and the original letter case.
+    pub lexeme: &'a str,
 }
 
 impl Display for Token<'_> {
@@ -1,12 +1,14 @@
 use std::fmt::Display;
 
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum NumberKind {
     Integer(i32),
-    Float(f32),
+    Float(f64),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum TokenKind<'a> {
     String(&'a str),
     Identifier(&'a str),
@@ -27,23 +29,33 @@ pub enum TokenKind<'a> {
     Comma,
     Semicolon,
     Slash,
+    Caret,
+    DoubleColon,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Keyword {
     Explain,
+    With,
+    As,
     Select,
+    Distinct,
     From,
     Where,
+    Group,
+    Having,
     Order,
     By,
     Asc,
     Desc,
     True,
     False,
+    Null,
     And,
     Or,
     Not,
+    In,
     Limit,
     Offset,
     Update,
@@ -57,18 +69,25 @@ pub enum Keyword {
     Index,
     On,
     Int,
+    BigInt,
     Float,
     Text,
+    Varchar,
+    Boolean,
+    Blob,
     Aggregate(Aggregate),
     Primary,
     Key,
     Nullable,
+    Unique,
+    Default,
     Begin,
     Commit,
     Rollback,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Aggregate {
     Sum,
     Avg,
@@ -78,55 +97,75 @@ pub enum Aggregate {
     Count,
 }
 
-impl Display for Keyword {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl Keyword {
+    /// The canonical uppercase spelling, which [`TokenKind::from`] maps back to this keyword.
+    pub fn as_str(&self) -> &'static str {
         match self {
-            Keyword::Explain => write!(f, "EXPLAIN"),
-            Keyword::Select => write!(f, "SELECT"),
-            Keyword::From => write!(f, "FROM"),
-            Keyword::Where => write!(f, "WHERE"),
-            Keyword::Order => write!(f, "ORDER"),
-            Keyword::By => write!(f, "BY"),
-            Keyword::Asc => write!(f, "ASC"),
-            Keyword::Desc => write!(f, "DESC"),
-            Keyword::And => write!(f, "AND"),
-            Keyword::Or => write!(f, "OR"),
-            Keyword::True => write!(f, "TRUE"),
-            Keyword::False => write!(f, "FALSE"),
-            Keyword::Not => write!(f, "NOT"),
-            Keyword::Limit => write!(f, "LIMIT"),
-            Keyword::Offset => write!(f, "OFFSET"),
-            Keyword::Update => write!(f, "UPDATE"),
-            Keyword::Set => write!(f, "SET"),
-            Keyword::Delete => write!(f, "DELETE"),
-            Keyword::Insert => write!(f, "INSERT"),
-            Keyword::Into => write!(f, "INTO"),
-            Keyword::Values => write!(f, "VALUES"),
-            Keyword::Create => write!(f, "CREATE"),
-            Keyword::Table => write!(f, "TABLE"),
-            Keyword::Index => write!(f, "INDEX"),
-            Keyword::On => write!(f, "ON"),
-            Keyword::Int => write!(f, "INT"),
-            Keyword::Float => write!(f, "FLOAT"),
-            Keyword::Text => write!(f, "TEXT"),
+            Keyword::Explain => "EXPLAIN",
+            Keyword::With => "WITH",
+            Keyword::As => "AS",
+            Keyword::Select => "SELECT",
+            Keyword::Distinct => "DISTINCT",
+            Keyword::From => "FROM",
+            Keyword::Where => "WHERE",
+            Keyword::Group => "GROUP",
+            Keyword::Having => "HAVING",
+            Keyword::Order => "ORDER",
+            Keyword::By => "BY",
+            Keyword::Asc => "ASC",
+            Keyword::Desc => "DESC",
+            Keyword::And => "AND",
+            Keyword::Or => "OR",
+            Keyword::True => "TRUE",
+            Keyword::False => "FALSE",
+            Keyword::Null => "NULL",
+            Keyword::Not => "NOT",
+            Keyword::In => "IN",
+            Keyword::Limit => "LIMIT",
+            Keyword::Offset => "OFFSET",
+            Keyword::Update => "UPDATE",
+            Keyword::Set => "SET",
+            Keyword::Delete => "DELETE",
+            Keyword::Insert => "INSERT",
+            Keyword::Into => "INTO",
+            Keyword::Values => "VALUES",
+            Keyword::Create => "CREATE",
+            Keyword::Table => "TABLE",
+            Keyword::Index => "INDEX",
+            Keyword::On => "ON",
+            Keyword::Int => "INT",
+            Keyword::Float => "FLOAT",
+            Keyword::Text => "TEXT",
+            Keyword::Varchar => "VARCHAR",
+            Keyword::BigInt => "BIGINT",
+            Keyword::Boolean => "BOOLEAN",
+            Keyword::Blob => "BLOB",
             Keyword::Aggregate(aggregate) => match aggregate {
-                Aggregate::Sum => write!(f, "SUM"),
-                Aggregate::Avg => write!(f, "AVG"),
-                Aggregate::StdDev => write!(f, "STDDEV"),
-                Aggregate::Min => write!(f, "MIN"),
-                Aggregate::Max => write!(f, "MAX"),
-                Aggregate::Count => write!(f, "COUNT"),
+                Aggregate::Sum => "SUM",
+                Aggregate::Avg => "AVG",
+                Aggregate::StdDev => "STDDEV",
+                Aggregate::Min => "MIN",
+                Aggregate::Max => "MAX",
+                Aggregate::Count => "COUNT",
             },
-            Keyword::Primary => write!(f, "PRIMARY"),
-            Keyword::Key => write!(f, "KEY"),
-            Keyword::Nullable => write!(f, "NULLABLE"),
-            Keyword::Begin => write!(f, "BEGIN"),
-            Keyword::Commit => write!(f, "COMMIT"),
-            Keyword::Rollback => write!(f, "ROLLBACK"),
+            Keyword::Primary => "PRIMARY",
+            Keyword::Key => "KEY",
+            Keyword::Nullable => "NULLABLE",
+            Keyword::Unique => "UNIQUE",
+            Keyword::Default => "DEFAULT",
+            Keyword::Begin => "BEGIN",
+            Keyword::Commit => "COMMIT",
+            Keyword::Rollback => "ROLLBACK",
         }
     }
 }
 
+impl Display for Keyword {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 impl Display for TokenKind<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -150,6 +189,8 @@ impl Display for TokenKind<'_> {
             TokenKind::Comma => write!(f, "COMMA"),
             TokenKind::Semicolon => write!(f, "SEMICOLON"),
             TokenKind::Slash => write!(f, "SLASH"),
+            TokenKind::Caret => write!(f, "CARET"),
+            TokenKind::DoubleColon => write!(f, "DOUBLECOLON"),
         }
     }
 }
@@ -160,9 +201,13 @@ impl<'a> From<&'a str> for TokenKind<'a> {
     }
 }
 
+/// Maps a word to its keyword. `BOOL` and `INTEGER` are accepted as aliases of
+/// `BOOLEAN` and `INT`, and display as the canonical spelling.
 fn keyword_from_str(value: &str) -> Option<Keyword> {
     match value.len() {
+        2 if value.eq_ignore_ascii_case("AS") => Some(Keyword::As),
         2 if value.eq_ignore_ascii_case("BY") => Some(Keyword::By),
+        2 if value.eq_ignore_ascii_case("IN") => Some(Keyword::In),
         2 if value.eq_ignore_ascii_case("ON") => Some(Keyword::On),
         2 if value.eq_ignore_ascii_case("OR") => Some(Keyword::Or),
         3 if value.eq_ignore_ascii_case("AND") => Some(Keyword::And),
@@ -175,31 +220,44 @@ fn keyword_from_str(value: &str) -> Option<Keyword> {
         3 if value.eq_ignore_ascii_case("NOT") => Some(Keyword::Not),
         3 if value.eq_ignore_ascii_case("SET") => Some(Keyword::Set),
         3 if value.eq_ignore_ascii_case("SUM") => Some(Keyword::Aggregate(Aggregate::Sum)),
+        4 if value.eq_ignore_ascii_case("BLOB") => Some(Keyword::Blob),
+        4 if value.eq_ignore_ascii_case("BOOL") => Some(Keyword::Boolean),
         4 if value.eq_ignore_ascii_case("DESC") => Some(Keyword::Desc),
         4 if value.eq_ignore_ascii_case("FROM") => Some(Keyword::From),
         4 if value.eq_ignore_ascii_case("INTO") => Some(Keyword::Into),
+        4 if value.eq_ignore_ascii_case("NULL") => Some(Keyword::Null),
         4 if value.eq_ignore_ascii_case("TEXT") => Some(Keyword::Text),
         4 if value.eq_ignore_ascii_case("TRUE") => Some(Keyword::True),
+        4 if value.eq_ignore_ascii_case("WITH") => Some(Keyword::With),
         5 if value.eq_ignore_ascii_case("BEGIN") => Some(Keyword::Begin),
         5 if value.eq_ignore_ascii_case("COUNT") => Some(Keyword::Aggregate(Aggregate::Count)),
         5 if value.eq_ignore_ascii_case("FALSE") => Some(Keyword::False),
         5 if value.eq_ignore_ascii_case("FLOAT") => Some(Keyword::Float),
+        5 if value.eq_ignore_ascii_case("GROUP") => Some(Keyword::Group),
         5 if value.eq_ignore_ascii_case("INDEX") => Some(Keyword::Index),
         5 if value.eq_ignore_ascii_case("LIMIT") => Some(Keyword::Limit),
         5 if value.eq_ignore_ascii_case("ORDER") => Some(Keyword::Order),
         5 if value.eq_ignore_ascii_case("TABLE") => Some(Keyword::Table),
         5 if value.eq_ignore_ascii_case("WHERE") => Some(Keyword::Where),
+        6 if value.eq_ignore_ascii_case("BIGINT") => Some(Keyword::BigInt),
         6 if value.eq_ignore_ascii_case("COMMIT") => Some(Keyword::Commit),
         6 if value.eq_ignore_ascii_case("CREATE") => Some(Keyword::Create),
         6 if value.eq_ignore_ascii_case("DELETE") => Some(Keyword::Delete),
+        6 if value.eq_ignore_ascii_case("HAVING") => Some(Keyword::Having),
         6 if value.eq_ignore_ascii_case("INSERT") => Some(Keyword::Insert),
         6 if value.eq_ignore_ascii_case("OFFSET") => Some(Keyword::Offset),
         6 if value.eq_ignore_ascii_case("SELECT") => Some(Keyword::Select),
         6 if value.eq_ignore_ascii_case("STDDEV") => Some(Keyword::Aggregate(Aggregate::StdDev)),
+        6 if value.eq_ignore_ascii_case("UNIQUE") => Some(Keyword::Unique),
         6 if value.eq_ignore_ascii_case("UPDATE") => Some(Keyword::Update),
         6 if value.eq_ignore_ascii_case("VALUES") => Some(Keyword::Values),
+        7 if value.eq_ignore_ascii_case("BOOLEAN") => Some(Keyword::Boolean),
+        7 if value.eq_ignore_ascii_case("DEFAULT") => Some(Keyword::Default),
         7 if value.eq_ignore_ascii_case("EXPLAIN") => Some(Keyword::Explain),
+        7 if value.eq_ignore_ascii_case("INTEGER") => Some(Keyword::Int),
         7 if value.eq_ignore_ascii_case("PRIMARY") => Some(Keyword::Primary),
+        7 if value.eq_ignore_ascii_case("VARCHAR") => Some(Keyword::Varchar),
+        8 if value.eq_ignore_ascii_case("DISTINCT") => Some(Keyword::Distinct),
         8 if value.eq_ignore_ascii_case("NULLABLE") => Some(Keyword::Nullable),
         8 if value.eq_ignore_ascii_case("ROLLBACK") => Some(Keyword::Rollback),
         _ => None,
@@ -0,0 +1,107 @@
+use crate::sql_parser::{
+    error::SQLError,
+    lexer::{Lexer, token::Token},
+};
+
+/// A comment, which the default [`Lexer`] skips along with whitespace.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum Trivia<'a> {
+    /// A `--` comment. `text` runs from `--` to the end of the line, excluding the line ending.
+    LineComment { text: &'a str, offset: usize },
+    /// A `/* */` comment, including its delimiters. An unclosed one runs to the end of input.
+    BlockComment { text: &'a str, offset: usize },
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum TokenOrTrivia<'a> {
+    Token(Token<'a>),
+    Trivia(Trivia<'a>),
+}
+
+/// A lexer that yields comments as [`Trivia`] between the tokens they sit among.
+///
+/// Apart from the comments, the tokens are those the wrapped [`Lexer`] yields.
+#[derive(Debug)]
+pub struct TriviaLexer<'a> {
+    lexer: Lexer<'a>,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn with_trivia(self) -> TriviaLexer<'a> {
+        TriviaLexer { lexer: self }
+    }
+}
+
+impl<'a> Iterator for TriviaLexer<'a> {
+    type Item = Result<TokenOrTrivia<'a>, SQLError<'a>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let lexer = &mut self.lexer;
+        if let Some(token) = lexer.peeked.pop_front() {
+            return Some(token.map(TokenOrTrivia::Token));
+        }
+        lexer.skip_whitespace();
+
+        let offset = lexer.position;
+        let start = lexer.rest;
+        let trivia = if start.starts_with("--") {
+            lexer.skip_line_comment();
+            let text = start[..lexer.position - offset].trim_end_matches(['\r', '\n']);
+            Trivia::LineComment { text, offset }
+        } else if start.starts_with("/*") {
+            lexer.skip_to_next("*/");
+            Trivia::BlockComment { text: &start[..lexer.position - offset], offset }
+        } else {
+            return lexer.lex_next().map(|token| token.map(TokenOrTrivia::Token));
+        };
+        Some(Ok(TokenOrTrivia::Trivia(trivia)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_parser::lexer::token_kind::{Keyword, TokenKind};
+
+    #[test]
+    fn test_comments_are_interleaved_with_tokens() {
+        let s = "-- registered users\r\nCREATE /* inline */ TABLE users (id INT);\n-- end";
+        let items: Vec<_> = Lexer::new(s).with_trivia().map(Result::unwrap).collect();
+
+        let comments: Vec<_> = items
+            .iter()
+            .enumerate()
+            .filter_map(|(index, item)| match item {
+                TokenOrTrivia::Trivia(trivia) => Some((index, *trivia)),
+                TokenOrTrivia::Token(_) => None,
+            })
+            .collect();
+        let expected = [
+            (0, Trivia::LineComment { text: "-- registered users", offset: 0 }),
+            (2, Trivia::BlockComment { text: "/* inline */", offset: 28 }),
+            (10, Trivia::LineComment { text: "-- end", offset: s.len() - 6 }),
+        ];
+        assert_eq!(comments, expected);
+
+        let tokens: Vec<_> = items
+            .into_iter()
+            .filter_map(|item| match item {
+                TokenOrTrivia::Token(token) => Some(token),
+                TokenOrTrivia::Trivia(_) => None,
+            })
+            .collect();
+        assert_eq!(tokens, Lexer::tokenize(s).unwrap());
+        assert_eq!(tokens[0].kind, TokenKind::Keyword(Keyword::Create));
+    }
+
+    #[test]
+    fn test_unclosed_block_comment_runs_to_end() {
+        let s = "1 /* open";
+        let items: Vec<_> = Lexer::new(s).with_trivia().map(Result::unwrap).collect();
+        assert_eq!(items.len(), 2);
+        assert_eq!(
+            items[1],
+            TokenOrTrivia::Trivia(Trivia::BlockComment { text: "/* open", offset: 2 })
+        );
+    }
+}
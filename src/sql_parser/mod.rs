@@ -1,5 +1,6 @@
 pub mod error;
-mod lexer;
+pub mod format;
+pub mod lexer;
 pub mod parser;
 
 pub use lexer::token_kind::NumberKind;
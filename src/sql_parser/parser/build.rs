@@ -0,0 +1,325 @@
+//! Helpers for constructing ASTs in code rather than by parsing SQL.
+//!
+//! Expressions are built from [`col`], [`lit`] and friends and combined with
+//! methods such as [`Expression::gt`] and [`Expression::and`]:
+//!
+//! ```
+//! use databas::sql_parser::parser::build::{col, lit};
+//!
+//! let expr = col("age").gt(lit(18)).and(col("active"));
+//! assert_eq!(expr.to_string(), "age > 18 AND active");
+//! ```
+
+use std::fmt::Display;
+
+use crate::sql_parser::parser::{
+    expr::{AggregateFunction, AggregateFunctionKind, Expression, Literal},
+    op::Op,
+    stmt::{
+        insert::{InsertQuery, InsertSource, Values},
+        lists::{ExpressionList, IdentifierList},
+        select::{OrderBy, OrderByTerm, Ordering, SelectQuery},
+    },
+};
+
+/// A column reference.
+pub fn col(name: &str) -> Expression<'_> {
+    Expression::Identifier(name)
+}
+
+/// A numeric or boolean literal.
+pub fn lit<'a>(value: impl Into<Expression<'a>>) -> Expression<'a> {
+    value.into()
+}
+
+/// A string literal.
+pub fn text(value: &str) -> Expression<'_> {
+    Expression::Literal(Literal::String(value.into()))
+}
+
+pub fn null<'a>() -> Expression<'a> {
+    Expression::Literal(Literal::Null)
+}
+
+pub fn not(expr: Expression<'_>) -> Expression<'_> {
+    Expression::UnaryOp((Op::Not, Box::new(expr)))
+}
+
+/// An aggregate call such as `SUM(expr)`; pass [`Expression::Wildcard`] for `COUNT(*)`.
+pub fn aggregate(kind: AggregateFunctionKind, expr: Expression<'_>) -> Expression<'_> {
+    Expression::AggregateFunction(AggregateFunction { kind, distinct: false, expr: Box::new(expr) })
+}
+
+impl<'a> Expression<'a> {
+    fn binary(self, op: Op, rhs: impl Into<Expression<'a>>) -> Expression<'a> {
+        Expression::BinaryOp((Box::new(self), op, Box::new(rhs.into())))
+    }
+
+    pub fn and(self, rhs: impl Into<Expression<'a>>) -> Expression<'a> {
+        self.binary(Op::And, rhs)
+    }
+
+    pub fn or(self, rhs: impl Into<Expression<'a>>) -> Expression<'a> {
+        self.binary(Op::Or, rhs)
+    }
+
+    pub fn equals(self, rhs: impl Into<Expression<'a>>) -> Expression<'a> {
+        self.binary(Op::EqualsEquals, rhs)
+    }
+
+    pub fn not_equals(self, rhs: impl Into<Expression<'a>>) -> Expression<'a> {
+        self.binary(Op::NotEquals, rhs)
+    }
+
+    pub fn lt(self, rhs: impl Into<Expression<'a>>) -> Expression<'a> {
+        self.binary(Op::LessThan, rhs)
+    }
+
+    pub fn le(self, rhs: impl Into<Expression<'a>>) -> Expression<'a> {
+        self.binary(Op::LessThanOrEqual, rhs)
+    }
+
+    pub fn gt(self, rhs: impl Into<Expression<'a>>) -> Expression<'a> {
+        self.binary(Op::GreaterThan, rhs)
+    }
+
+    pub fn ge(self, rhs: impl Into<Expression<'a>>) -> Expression<'a> {
+        self.binary(Op::GreaterThanOrEqual, rhs)
+    }
+
+    pub fn plus(self, rhs: impl Into<Expression<'a>>) -> Expression<'a> {
+        self.binary(Op::Add, rhs)
+    }
+
+    pub fn minus(self, rhs: impl Into<Expression<'a>>) -> Expression<'a> {
+        self.binary(Op::Sub, rhs)
+    }
+
+    pub fn times(self, rhs: impl Into<Expression<'a>>) -> Expression<'a> {
+        self.binary(Op::Mul, rhs)
+    }
+
+    pub fn divided_by(self, rhs: impl Into<Expression<'a>>) -> Expression<'a> {
+        self.binary(Op::Div, rhs)
+    }
+
+    /// `self IN (query)`.
+    pub fn in_subquery(self, query: SelectQuery<'a>) -> Expression<'a> {
+        self.binary(Op::In, Expression::Subquery(Box::new(query)))
+    }
+}
+
+/// An invariant a builder checks in `build()`.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum BuildError {
+    /// The select list or insert column list is empty.
+    EmptyColumnList,
+    /// An insert has no rows to insert.
+    EmptyValues,
+    /// An insert row does not have one value per column.
+    RowArity { row: usize, expected: usize, got: usize },
+}
+
+impl Display for BuildError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BuildError::EmptyColumnList => write!(f, "Column list is empty"),
+            BuildError::EmptyValues => write!(f, "INSERT has no rows"),
+            BuildError::RowArity { row, expected, got } => {
+                write!(f, "Row {} has {got} values, expected {expected}", row + 1)
+            }
+        }
+    }
+}
+
+impl std::error::Error for BuildError {}
+
+/// Builds a [`SelectQuery`] clause by clause.
+#[derive(Debug, Default)]
+pub struct SelectQueryBuilder<'a> {
+    distinct: bool,
+    columns: Vec<Expression<'a>>,
+    table: Option<&'a str>,
+    where_clause: Option<Expression<'a>>,
+    group_by: Option<Vec<Expression<'a>>>,
+    having: Option<Expression<'a>>,
+    order_by: Vec<OrderByTerm<'a>>,
+    limit: Option<u32>,
+    offset: Option<u32>,
+}
+
+impl<'a> SelectQueryBuilder<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn distinct(mut self) -> Self {
+        self.distinct = true;
+        self
+    }
+
+    pub fn column(mut self, expr: impl Into<Expression<'a>>) -> Self {
+        self.columns.push(expr.into());
+        self
+    }
+
+    pub fn from(mut self, table: &'a str) -> Self {
+        self.table = Some(table);
+        self
+    }
+
+    /// Sets the `WHERE` predicate.
+    pub fn filter(mut self, predicate: Expression<'a>) -> Self {
+        self.where_clause = Some(predicate);
+        self
+    }
+
+    pub fn group_by(mut self, expr: Expression<'a>) -> Self {
+        self.group_by.get_or_insert_with(Vec::new).push(expr);
+        self
+    }
+
+    pub fn having(mut self, predicate: Expression<'a>) -> Self {
+        self.having = Some(predicate);
+        self
+    }
+
+    pub fn order_by(mut self, column: &'a str, order: Option<Ordering>) -> Self {
+        self.order_by.push(OrderByTerm { column, order });
+        self
+    }
+
+    pub fn limit(mut self, limit: u32) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    pub fn offset(mut self, offset: u32) -> Self {
+        self.offset = Some(offset);
+        self
+    }
+
+    pub fn build(self) -> Result<SelectQuery<'a>, BuildError> {
+        if self.columns.is_empty() {
+            return Err(BuildError::EmptyColumnList);
+        }
+        Ok(SelectQuery {
+            distinct: self.distinct,
+            columns: ExpressionList(self.columns),
+            table: self.table,
+            where_clause: self.where_clause,
+            group_by: self.group_by.map(ExpressionList),
+            having: self.having,
+            order_by: (!self.order_by.is_empty()).then_some(OrderBy { terms: self.order_by }),
+            limit: self.limit,
+            offset: self.offset,
+        })
+    }
+}
+
+/// Builds an [`InsertQuery`] row by row.
+#[derive(Debug)]
+pub struct InsertQueryBuilder<'a> {
+    table: &'a str,
+    columns: Vec<&'a str>,
+    rows: Vec<ExpressionList<'a>>,
+}
+
+impl<'a> InsertQueryBuilder<'a> {
+    pub fn new(table: &'a str) -> Self {
+        Self { table, columns: Vec::new(), rows: Vec::new() }
+    }
+
+    pub fn column(mut self, name: &'a str) -> Self {
+        self.columns.push(name);
+        self
+    }
+
+    pub fn row<I, E>(mut self, values: I) -> Self
+    where
+        I: IntoIterator<Item = E>,
+        E: Into<Expression<'a>>,
+    {
+        self.rows.push(ExpressionList(values.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    pub fn build(self) -> Result<InsertQuery<'a>, BuildError> {
+        if self.columns.is_empty() {
+            return Err(BuildError::EmptyColumnList);
+        }
+        if self.rows.is_empty() {
+            return Err(BuildError::EmptyValues);
+        }
+        let expected = self.columns.len();
+        if let Some((row, values)) =
+            self.rows.iter().enumerate().find(|(_, values)| values.0.len() != expected)
+        {
+            return Err(BuildError::RowArity { row, expected, got: values.0.len() });
+        }
+        Ok(InsertQuery {
+            table: self.table,
+            columns: IdentifierList(self.columns),
+            source: InsertSource::Values(Values(self.rows)),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_parser::parser::{Parser, stmt::Statement};
+
+    #[test]
+    fn test_expression_dsl_matches_parsed_expression() {
+        let expr = col("age").gt(lit(18)).and(col("active"));
+        assert_eq!(expr, Parser::new("age > 18 AND active").expr().unwrap());
+
+        let expr = col("a").plus(lit(1)).times(lit(2.5)).le(col("b")).or(not(col("c")));
+        assert_eq!(expr, Parser::new("(a + 1) * 2.5 <= b OR NOT c").expr().unwrap());
+        assert_eq!(expr.to_string(), "(a + 1) * 2.5 <= b OR NOT c");
+    }
+
+    #[test]
+    fn test_select_builder_produces_parsed_query() {
+        let subquery = SelectQueryBuilder::new().column(col("id")).from("admins").build().unwrap();
+        let query = SelectQueryBuilder::new()
+            .distinct()
+            .column(col("name"))
+            .column(aggregate(AggregateFunctionKind::Count, Expression::Wildcard))
+            .from("users")
+            .filter(col("id").in_subquery(subquery).and(col("name").not_equals(text("root"))))
+            .group_by(col("name"))
+            .having(aggregate(AggregateFunctionKind::Count, Expression::Wildcard).gt(1))
+            .order_by("name", Some(Ordering::Descending))
+            .limit(10)
+            .offset(5)
+            .build()
+            .unwrap();
+
+        let s = "SELECT DISTINCT name, COUNT(*) FROM users \
+                 WHERE id IN (SELECT id FROM admins) AND name != 'root' \
+                 GROUP BY name HAVING COUNT(*) > 1 ORDER BY name DESC LIMIT 10 OFFSET 5;";
+        assert_eq!(Statement::Select(query), Parser::new(s).stmt().unwrap());
+    }
+
+    #[test]
+    fn test_builders_reject_missing_columns() {
+        assert_eq!(SelectQueryBuilder::new().from("t").build(), Err(BuildError::EmptyColumnList));
+        assert_eq!(
+            InsertQueryBuilder::new("t").row([lit(1)]).build(),
+            Err(BuildError::EmptyColumnList)
+        );
+        assert_eq!(InsertQueryBuilder::new("t").column("a").build(), Err(BuildError::EmptyValues));
+
+        let err = InsertQueryBuilder::new("t")
+            .column("a")
+            .column("b")
+            .row([1, 2])
+            .row([3])
+            .build()
+            .unwrap_err();
+        assert_eq!(err, BuildError::RowArity { row: 1, expected: 2, got: 1 });
+        assert_eq!(err.to_string(), "Row 2 has 1 values, expected 2");
+    }
+}
@@ -0,0 +1,513 @@
+use std::{cmp::Ordering, fmt::Display};
+
+use crate::sql_parser::{
+    lexer::token_kind::NumberKind,
+    parser::{
+        expr::{Expression, Literal},
+        op::Op,
+    },
+};
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum ConstEvalErrorKind {
+    /// The expression references a column, wildcard, aggregate, or subquery.
+    NonConstant,
+    DivisionByZero,
+    /// An integer was raised to a negative integer power.
+    NegativeExponent,
+    IntegerOverflow {
+        op: Op,
+    },
+    /// The operator is not defined for the operand types, as in `'a' + 1`.
+    TypeMismatch {
+        op: Op,
+    },
+    /// Casts are parsed but not yet evaluated.
+    UnsupportedCast,
+}
+
+impl Display for ConstEvalErrorKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConstEvalErrorKind::NonConstant => write!(f, "Expression is not constant"),
+            ConstEvalErrorKind::DivisionByZero => write!(f, "Division by zero"),
+            ConstEvalErrorKind::NegativeExponent => write!(f, "Negative integer exponent"),
+            ConstEvalErrorKind::IntegerOverflow { op } => write!(f, "Integer overflow in {op}"),
+            ConstEvalErrorKind::TypeMismatch { op } => {
+                write!(f, "Operator {} does not apply to these operands", op.to_string().trim())
+            }
+            ConstEvalErrorKind::UnsupportedCast => write!(f, "Casts cannot be evaluated"),
+        }
+    }
+}
+
+/// An error raised while folding a constant expression.
+///
+/// The AST carries no source offsets, so the error is positioned by the
+/// rendered sub-expression that failed, e.g. `1 / 0` inside `2 + 1 / 0`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConstEvalError {
+    pub kind: ConstEvalErrorKind,
+    pub expression: String,
+}
+
+impl ConstEvalError {
+    fn new(kind: ConstEvalErrorKind, expression: &Expression<'_>) -> Self {
+        Self { kind, expression: expression.to_string() }
+    }
+}
+
+impl Display for ConstEvalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} in `{}`", self.kind, self.expression)
+    }
+}
+
+impl std::error::Error for ConstEvalError {}
+
+/// The outcome of a boolean test under SQL's three-valued logic.
+///
+/// `NULL` in a boolean position is [`TruthValue::Unknown`]: `NOT` keeps it
+/// unknown, `AND` is false if either side is false and `OR` is true if either
+/// side is true, and otherwise an unknown operand makes the result unknown.
+/// Both this evaluator and the executor combine predicates through this type.
+/// Only a row filter collapses `Unknown` to false, via [`TruthValue::is_true`].
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub enum TruthValue {
+    True,
+    False,
+    Unknown,
+}
+
+impl TruthValue {
+    pub fn and(self, other: TruthValue) -> TruthValue {
+        match (self, other) {
+            (TruthValue::False, _) | (_, TruthValue::False) => TruthValue::False,
+            (TruthValue::True, TruthValue::True) => TruthValue::True,
+            _ => TruthValue::Unknown,
+        }
+    }
+
+    pub fn or(self, other: TruthValue) -> TruthValue {
+        match (self, other) {
+            (TruthValue::True, _) | (_, TruthValue::True) => TruthValue::True,
+            (TruthValue::False, TruthValue::False) => TruthValue::False,
+            _ => TruthValue::Unknown,
+        }
+    }
+
+    /// Whether a `WHERE` clause keeps the row: `Unknown` is treated as false.
+    pub fn is_true(self) -> bool {
+        self == TruthValue::True
+    }
+
+    /// The known boolean, or `None` for `Unknown`, which is represented as `NULL`.
+    pub fn to_bool(self) -> Option<bool> {
+        match self {
+            TruthValue::True => Some(true),
+            TruthValue::False => Some(false),
+            TruthValue::Unknown => None,
+        }
+    }
+}
+
+impl std::ops::Not for TruthValue {
+    type Output = TruthValue;
+
+    fn not(self) -> TruthValue {
+        match self {
+            TruthValue::True => TruthValue::False,
+            TruthValue::False => TruthValue::True,
+            TruthValue::Unknown => TruthValue::Unknown,
+        }
+    }
+}
+
+impl From<bool> for TruthValue {
+    fn from(value: bool) -> Self {
+        if value { TruthValue::True } else { TruthValue::False }
+    }
+}
+
+impl Literal<'_> {
+    /// Reads a boolean or `NULL` as a truth value; other literals are not booleans.
+    fn truth_value(&self) -> Option<TruthValue> {
+        match self {
+            Literal::Boolean(value) => Some(TruthValue::from(*value)),
+            Literal::Null => Some(TruthValue::Unknown),
+            _ => None,
+        }
+    }
+}
+
+impl From<TruthValue> for Literal<'_> {
+    fn from(value: TruthValue) -> Self {
+        value.to_bool().map_or(Literal::Null, Literal::Boolean)
+    }
+}
+
+impl<'a> Expression<'a> {
+    /// Folds a constant expression down to a single literal.
+    ///
+    /// Integer arithmetic is checked, and an integer combined with a float is
+    /// promoted to a float. `AND` and `OR` short-circuit, so `false AND 1 / 0`
+    /// is `false`. Comparisons apply to numbers, strings and booleans of the
+    /// same type. `NULL` follows three-valued logic (see [`TruthValue`]) and
+    /// propagates through arithmetic and comparisons, so `NULL == NULL` is `NULL`.
+    pub fn evaluate(&self) -> Result<Literal<'a>, ConstEvalError> {
+        match self {
+            Expression::Literal(literal) => Ok(literal.clone()),
+            Expression::UnaryOp((op, operand)) => match (op, operand.evaluate()?) {
+                (Op::Not, Literal::Boolean(value)) => Ok(Literal::Boolean(!value)),
+                (Op::Not | Op::Add | Op::Sub, Literal::Null) => Ok(Literal::Null),
+                (Op::Add, Literal::Number(number)) => Ok(Literal::Number(number)),
+                (Op::Sub, Literal::Number(number)) => negate(number)
+                    .map(Literal::Number)
+                    .map_err(|kind| ConstEvalError::new(kind, self)),
+                (op, _) => {
+                    Err(ConstEvalError::new(ConstEvalErrorKind::TypeMismatch { op: *op }, self))
+                }
+            },
+            Expression::BinaryOp((left, op @ (Op::And | Op::Or), right)) => {
+                let mismatch =
+                    || ConstEvalError::new(ConstEvalErrorKind::TypeMismatch { op: *op }, self);
+                let left = left.evaluate()?.truth_value().ok_or_else(mismatch)?;
+                let value = short_circuit(left, *op, || {
+                    right.evaluate()?.truth_value().ok_or_else(mismatch)
+                })?;
+                Ok(value.into())
+            }
+            Expression::BinaryOp((left, op, right)) => {
+                evaluate_binary(left.evaluate()?, *op, right.evaluate()?)
+                    .map_err(|kind| ConstEvalError::new(kind, self))
+            }
+            Expression::Identifier(_)
+            | Expression::Wildcard
+            | Expression::AggregateFunction(_)
+            | Expression::Subquery(_) => {
+                Err(ConstEvalError::new(ConstEvalErrorKind::NonConstant, self))
+            }
+            Expression::Cast(_) => {
+                Err(ConstEvalError::new(ConstEvalErrorKind::UnsupportedCast, self))
+            }
+        }
+    }
+}
+
+fn evaluate_binary<'a>(
+    left: Literal<'a>,
+    op: Op,
+    right: Literal<'a>,
+) -> Result<Literal<'a>, ConstEvalErrorKind> {
+    let mismatch = ConstEvalErrorKind::TypeMismatch { op };
+    if matches!(left, Literal::Null) || matches!(right, Literal::Null) {
+        return match op {
+            Op::And | Op::Or | Op::Not | Op::In => Err(mismatch),
+            _ => Ok(Literal::Null),
+        };
+    }
+    match op {
+        Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Pow => match (left, right) {
+            (Literal::Number(lhs), Literal::Number(rhs)) => {
+                evaluate_arithmetic(lhs, op, rhs).map(Literal::Number)
+            }
+            _ => Err(mismatch),
+        },
+        Op::And | Op::Or | Op::Not | Op::In => Err(mismatch),
+        Op::EqualsEquals
+        | Op::NotEquals
+        | Op::LessThan
+        | Op::GreaterThan
+        | Op::LessThanOrEqual
+        | Op::GreaterThanOrEqual => {
+            let ordering = match (left, right) {
+                (Literal::Number(lhs), Literal::Number(rhs)) => compare_numbers(lhs, rhs),
+                (Literal::String(lhs), Literal::String(rhs)) => Some(lhs.cmp(&rhs)),
+                (Literal::Boolean(lhs), Literal::Boolean(rhs)) => Some(lhs.cmp(&rhs)),
+                _ => return Err(mismatch),
+            };
+            compare(op, ordering).map(Literal::Boolean).ok_or(mismatch)
+        }
+    }
+}
+
+// The executor evaluates rows through the same operator core below, so both
+// evaluators agree on short-circuiting, numeric promotion and comparisons.
+
+/// Combines two known operands of `AND` or `OR`.
+pub(crate) fn combine_logical(left: TruthValue, op: Op, right: TruthValue) -> TruthValue {
+    if op == Op::Or { left.or(right) } else { left.and(right) }
+}
+
+/// Evaluates `AND` or `OR`, calling `right` only when `left` does not decide the result.
+pub(crate) fn short_circuit<E>(
+    left: TruthValue,
+    op: Op,
+    right: impl FnOnce() -> Result<TruthValue, E>,
+) -> Result<TruthValue, E> {
+    let decisive = if op == Op::Or { TruthValue::True } else { TruthValue::False };
+    if left == decisive {
+        return Ok(left);
+    }
+    Ok(combine_logical(left, op, right()?))
+}
+
+/// Applies a comparison operator to the ordering of its operands, or returns
+/// `None` if `op` is not a comparison. Unordered operands satisfy only `!=`.
+pub(crate) fn compare(op: Op, ordering: Option<Ordering>) -> Option<bool> {
+    let holds = match op {
+        Op::EqualsEquals => ordering.is_some_and(Ordering::is_eq),
+        Op::NotEquals => !ordering.is_some_and(Ordering::is_eq),
+        Op::LessThan => ordering.is_some_and(Ordering::is_lt),
+        Op::GreaterThan => ordering.is_some_and(Ordering::is_gt),
+        Op::LessThanOrEqual => ordering.is_some_and(Ordering::is_le),
+        Op::GreaterThanOrEqual => ordering.is_some_and(Ordering::is_ge),
+        _ => return None,
+    };
+    Some(holds)
+}
+
+/// Orders two numbers: integers exactly, anything involving a float as `f64`.
+pub(crate) fn compare_numbers(lhs: NumberKind, rhs: NumberKind) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (NumberKind::Integer(lhs), NumberKind::Integer(rhs)) => Some(lhs.cmp(&rhs)),
+        (lhs, rhs) => as_float(lhs).partial_cmp(&as_float(rhs)),
+    }
+}
+
+/// Applies an arithmetic operator to two numbers, promoting to float when either is one.
+pub(crate) fn evaluate_arithmetic(
+    lhs: NumberKind,
+    op: Op,
+    rhs: NumberKind,
+) -> Result<NumberKind, ConstEvalErrorKind> {
+    match (lhs, rhs) {
+        (NumberKind::Integer(_), NumberKind::Integer(0)) if op == Op::Div => {
+            Err(ConstEvalErrorKind::DivisionByZero)
+        }
+        (NumberKind::Integer(_), NumberKind::Integer(rhs)) if op == Op::Pow && rhs < 0 => {
+            Err(ConstEvalErrorKind::NegativeExponent)
+        }
+        (NumberKind::Integer(lhs), NumberKind::Integer(rhs)) => {
+            let value = match op {
+                Op::Add => lhs.checked_add(rhs),
+                Op::Sub => lhs.checked_sub(rhs),
+                Op::Mul => lhs.checked_mul(rhs),
+                Op::Div => lhs.checked_div(rhs),
+                Op::Pow => lhs.checked_pow(rhs.unsigned_abs()),
+                _ => return Err(ConstEvalErrorKind::TypeMismatch { op }),
+            };
+            value.map(NumberKind::Integer).ok_or(ConstEvalErrorKind::IntegerOverflow { op })
+        }
+        (lhs, rhs) => {
+            let (lhs, rhs) = (as_float(lhs), as_float(rhs));
+            let value = match op {
+                Op::Add => lhs + rhs,
+                Op::Sub => lhs - rhs,
+                Op::Mul => lhs * rhs,
+                Op::Div if rhs == 0.0 => return Err(ConstEvalErrorKind::DivisionByZero),
+                Op::Div => lhs / rhs,
+                Op::Pow => lhs.powf(rhs),
+                _ => return Err(ConstEvalErrorKind::TypeMismatch { op }),
+            };
+            Ok(NumberKind::Float(value))
+        }
+    }
+}
+
+pub(crate) fn negate(number: NumberKind) -> Result<NumberKind, ConstEvalErrorKind> {
+    match number {
+        NumberKind::Integer(value) => value
+            .checked_neg()
+            .map(NumberKind::Integer)
+            .ok_or(ConstEvalErrorKind::IntegerOverflow { op: Op::Sub }),
+        NumberKind::Float(value) => Ok(NumberKind::Float(-value)),
+    }
+}
+
+fn as_float(number: NumberKind) -> f64 {
+    match number {
+        NumberKind::Integer(value) => f64::from(value),
+        NumberKind::Float(value) => value,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fastrand::Rng;
+
+    use super::*;
+    use crate::sql_parser::parser::Parser;
+
+    fn evaluate(source: &str) -> Result<Literal<'_>, ConstEvalError> {
+        Parser::new(source).expr().unwrap().evaluate()
+    }
+
+    fn error(kind: ConstEvalErrorKind, expression: &str) -> ConstEvalError {
+        ConstEvalError { kind, expression: expression.to_owned() }
+    }
+
+    #[test]
+    fn test_evaluate_arithmetic() {
+        assert_eq!(evaluate("1 + 2 * 3"), Ok(Literal::Number(NumberKind::Integer(7))));
+        assert_eq!(evaluate("-(7 - 10) / 2"), Ok(Literal::Number(NumberKind::Integer(1))));
+        assert_eq!(evaluate("1 + 0.5"), Ok(Literal::Number(NumberKind::Float(1.5))));
+        assert_eq!(evaluate("+-(2 * 3)"), Ok(Literal::Number(NumberKind::Integer(-6))));
+        assert_eq!(evaluate("2 ^ 3 ^ 2"), Ok(Literal::Number(NumberKind::Integer(512))));
+        assert_eq!(evaluate("4 ^ 0.5"), Ok(Literal::Number(NumberKind::Float(2.0))));
+        assert_eq!(evaluate("2 ^ -1"), Err(error(ConstEvalErrorKind::NegativeExponent, "2 ^ -1")));
+        assert_eq!(
+            evaluate("2 ^ 31"),
+            Err(error(ConstEvalErrorKind::IntegerOverflow { op: Op::Pow }, "2 ^ 31"))
+        );
+        assert_eq!(
+            evaluate("+'a'"),
+            Err(error(ConstEvalErrorKind::TypeMismatch { op: Op::Add }, "+'a'"))
+        );
+    }
+
+    #[test]
+    fn test_evaluate_comparisons_and_string_equality() {
+        assert_eq!(evaluate("1 + 1 == 2"), Ok(Literal::Boolean(true)));
+        assert_eq!(evaluate("2 > 2.5"), Ok(Literal::Boolean(false)));
+        assert_eq!(evaluate("'abc' == 'abc'"), Ok(Literal::Boolean(true)));
+        assert_eq!(evaluate("'abc' != 'abd'"), Ok(Literal::Boolean(true)));
+        assert_eq!(evaluate("'abc' < 'abd'"), Ok(Literal::Boolean(true)));
+        assert_eq!(evaluate("NULL == NULL"), Ok(Literal::Null));
+    }
+
+    const UNKNOWN: TruthValue = TruthValue::Unknown;
+    const TRUE: TruthValue = TruthValue::True;
+    const FALSE: TruthValue = TruthValue::False;
+
+    #[test]
+    fn test_truth_tables() {
+        let and = [
+            (TRUE, TRUE, TRUE),
+            (TRUE, FALSE, FALSE),
+            (TRUE, UNKNOWN, UNKNOWN),
+            (FALSE, FALSE, FALSE),
+            (FALSE, UNKNOWN, FALSE),
+            (UNKNOWN, UNKNOWN, UNKNOWN),
+        ];
+        let or = [
+            (TRUE, TRUE, TRUE),
+            (TRUE, FALSE, TRUE),
+            (TRUE, UNKNOWN, TRUE),
+            (FALSE, FALSE, FALSE),
+            (FALSE, UNKNOWN, UNKNOWN),
+            (UNKNOWN, UNKNOWN, UNKNOWN),
+        ];
+        let source = |value: TruthValue| Literal::from(value).to_string();
+        for (table, op, combine) in [
+            (and, "AND", TruthValue::and as fn(TruthValue, TruthValue) -> TruthValue),
+            (or, "OR", TruthValue::or),
+        ] {
+            for (left, right, expected) in table {
+                assert_eq!(combine(left, right), expected, "{left:?} {op} {right:?}");
+                assert_eq!(combine(right, left), expected, "{right:?} {op} {left:?}");
+                for s in [
+                    format!("{} {op} {}", source(left), source(right)),
+                    format!("{} {op} {}", source(right), source(left)),
+                ] {
+                    assert_eq!(evaluate(&s), Ok(expected.into()), "{s}");
+                }
+            }
+        }
+
+        for (value, expected) in [(TRUE, FALSE), (FALSE, TRUE), (UNKNOWN, UNKNOWN)] {
+            assert_eq!(!value, expected);
+            assert_eq!(evaluate(&format!("NOT {}", source(value))), Ok(expected.into()));
+        }
+        assert!(TRUE.is_true() && !FALSE.is_true() && !UNKNOWN.is_true());
+    }
+
+    #[test]
+    fn test_null_propagates_through_arithmetic_and_comparisons() {
+        for s in ["NULL + 1", "2.5 * NULL", "-NULL", "NULL == NULL", "1 != NULL", "'a' < NULL"] {
+            assert_eq!(evaluate(s), Ok(Literal::Null), "{s}");
+        }
+        assert_eq!(evaluate("NULL AND false"), Ok(Literal::Boolean(false)));
+        assert_eq!(evaluate("NULL == NULL OR 1 < 2"), Ok(Literal::Boolean(true)));
+        assert_eq!(
+            evaluate("NULL OR 1"),
+            Err(error(ConstEvalErrorKind::TypeMismatch { op: Op::Or }, "NULL OR 1"))
+        );
+    }
+
+    #[test]
+    fn test_evaluate_boolean_logic_short_circuits() {
+        assert_eq!(evaluate("NOT true OR 1 < 2"), Ok(Literal::Boolean(true)));
+        assert_eq!(evaluate("false AND 1 / 0 == 1"), Ok(Literal::Boolean(false)));
+        assert_eq!(evaluate("true OR 'a' + 1"), Ok(Literal::Boolean(true)));
+        assert_eq!(
+            evaluate("true AND 1"),
+            Err(error(ConstEvalErrorKind::TypeMismatch { op: Op::And }, "TRUE AND 1"))
+        );
+    }
+
+    #[test]
+    fn test_evaluate_reports_failing_sub_expression() {
+        assert_eq!(evaluate("2 + 1 / 0"), Err(error(ConstEvalErrorKind::DivisionByZero, "1 / 0")));
+        assert_eq!(evaluate("1.5 / 0"), Err(error(ConstEvalErrorKind::DivisionByZero, "1.5 / 0")));
+        assert_eq!(
+            evaluate("'a' + 1"),
+            Err(error(ConstEvalErrorKind::TypeMismatch { op: Op::Add }, "'a' + 1"))
+        );
+        assert_eq!(
+            evaluate("2147483647 * 2"),
+            Err(error(ConstEvalErrorKind::IntegerOverflow { op: Op::Mul }, "2147483647 * 2"))
+        );
+        assert_eq!(evaluate("1 + a"), Err(error(ConstEvalErrorKind::NonConstant, "a")));
+        assert_eq!(
+            evaluate("COUNT(*) > 1"),
+            Err(error(ConstEvalErrorKind::NonConstant, "COUNT(*)"))
+        );
+    }
+
+    /// Builds a random integer expression, returning its source and the value native
+    /// `i64` math gives it, or `None` when an i32 step overflows or divides by zero.
+    fn random_integer_expression(rng: &mut Rng, depth: usize) -> (String, Option<i64>) {
+        if depth == 0 || rng.u8(0..4) == 0 {
+            let value = rng.i32(-1000..=1000);
+            return (format!("({value})"), Some(i64::from(value)));
+        }
+        let (left, lhs) = random_integer_expression(rng, depth - 1);
+        let (right, rhs) = random_integer_expression(rng, depth - 1);
+        let (op, value) = match rng.u8(0..4) {
+            0 => ("+", lhs.zip(rhs).map(|(lhs, rhs)| lhs + rhs)),
+            1 => ("-", lhs.zip(rhs).map(|(lhs, rhs)| lhs - rhs)),
+            2 => ("*", lhs.zip(rhs).map(|(lhs, rhs)| lhs * rhs)),
+            _ => ("/", lhs.zip(rhs).and_then(|(lhs, rhs)| lhs.checked_div(rhs))),
+        };
+        let value = value.filter(|value| i32::try_from(*value).is_ok());
+        (format!("({left} {op} {right})"), value)
+    }
+
+    #[test]
+    fn test_integer_folding_matches_native_math() {
+        let mut rng = Rng::with_seed(0x5eed_e7a1_2026_1015);
+        for _ in 0..2000 {
+            let (source, expected) = random_integer_expression(&mut rng, 4);
+            let got = evaluate(&source);
+            match expected {
+                Some(value) => assert_eq!(
+                    got,
+                    Ok(Literal::Number(NumberKind::Integer(value as i32))),
+                    "{source}"
+                ),
+                None => assert!(
+                    matches!(
+                        got,
+                        Err(ConstEvalError {
+                            kind: ConstEvalErrorKind::DivisionByZero
+                                | ConstEvalErrorKind::IntegerOverflow { .. },
+                            ..
+                        })
+                    ),
+                    "{source} evaluated to {got:?}"
+                ),
+            }
+        }
+    }
+}
@@ -1,16 +1,22 @@
-use std::fmt::Display;
+use std::{borrow::Cow, fmt::Display};
 
 use crate::sql_parser::lexer::token_kind::NumberKind;
 use crate::sql_parser::parser::Op;
+use crate::sql_parser::parser::ident::quote_ident;
+use crate::sql_parser::parser::stmt::{create_table::ColumnType, select::SelectQuery};
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Literal<'a> {
-    String(&'a str),
+    /// A string value, borrowed from the source unless it contained escaped quotes.
+    String(Cow<'a, str>),
     Number(NumberKind),
     Boolean(bool),
+    Null,
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum AggregateFunctionKind {
     Sum,
     Count,
@@ -21,12 +27,16 @@ pub enum AggregateFunctionKind {
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AggregateFunction<'a> {
     pub kind: AggregateFunctionKind,
+    /// True for `COUNT(DISTINCT x)`-style calls that aggregate only distinct values.
+    pub distinct: bool,
     pub expr: Box<Expression<'a>>,
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Expression<'a> {
     Literal(Literal<'a>),
     Identifier(&'a str),
@@ -34,6 +44,10 @@ pub enum Expression<'a> {
     BinaryOp((Box<Expression<'a>>, Op, Box<Expression<'a>>)),
     Wildcard,
     AggregateFunction(AggregateFunction<'a>),
+    /// A parenthesized `SELECT` used as a scalar value or as the right side of `IN`.
+    Subquery(Box<SelectQuery<'a>>),
+    /// A PostgreSQL-style `expr::TYPE` conversion.
+    Cast((Box<Expression<'a>>, ColumnType)),
 }
 
 impl From<i32> for Expression<'_> {
@@ -44,6 +58,12 @@ impl From<i32> for Expression<'_> {
 
 impl From<f32> for Expression<'_> {
     fn from(value: f32) -> Self {
+        Expression::from(f64::from(value))
+    }
+}
+
+impl From<f64> for Expression<'_> {
+    fn from(value: f64) -> Self {
         Expression::Literal(Literal::Number(NumberKind::Float(value)))
     }
 }
@@ -67,6 +87,74 @@ enum ChildSide {
 }
 
 impl Expression<'_> {
+    /// Returns true if this expression is shaped like a boolean predicate.
+    ///
+    /// Comparisons, logical operators, `NOT`, `IN`, and boolean literals qualify;
+    /// bare identifiers, other literals, and arithmetic do not.
+    pub fn is_predicate(&self) -> bool {
+        match self {
+            Expression::Literal(Literal::Boolean(_)) => true,
+            Expression::UnaryOp((op, _)) => *op == Op::Not,
+            Expression::BinaryOp((_, op, _)) => {
+                !matches!(op, Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Pow)
+            }
+            Expression::Literal(_)
+            | Expression::Identifier(_)
+            | Expression::Wildcard
+            | Expression::AggregateFunction(_)
+            | Expression::Subquery(_)
+            | Expression::Cast(_) => false,
+        }
+    }
+
+    /// Returns true if an aggregate function appears anywhere in this expression.
+    ///
+    /// Aggregates inside a subquery belong to that subquery and are not counted.
+    pub fn contains_aggregate(&self) -> bool {
+        match self {
+            Expression::AggregateFunction(_) => true,
+            Expression::UnaryOp((_, expr)) | Expression::Cast((expr, _)) => {
+                expr.contains_aggregate()
+            }
+            Expression::BinaryOp((left, _, right)) => {
+                left.contains_aggregate() || right.contains_aggregate()
+            }
+            Expression::Literal(_)
+            | Expression::Identifier(_)
+            | Expression::Wildcard
+            | Expression::Subquery(_) => false,
+        }
+    }
+
+    /// Returns true if a subquery appears anywhere in this expression.
+    pub fn contains_subquery(&self) -> bool {
+        match self {
+            Expression::Subquery(_) => true,
+            Expression::UnaryOp((_, expr)) | Expression::Cast((expr, _)) => {
+                expr.contains_subquery()
+            }
+            Expression::BinaryOp((left, _, right)) => {
+                left.contains_subquery() || right.contains_subquery()
+            }
+            Expression::Literal(_)
+            | Expression::Identifier(_)
+            | Expression::Wildcard
+            | Expression::AggregateFunction(_) => false,
+        }
+    }
+
+    /// Returns the number of AST nodes in this expression, including itself.
+    pub fn node_count(&self) -> usize {
+        match self {
+            Expression::Literal(_) | Expression::Identifier(_) | Expression::Wildcard => 1,
+            Expression::UnaryOp((_, expr)) => 1 + expr.node_count(),
+            Expression::BinaryOp((left, _, right)) => 1 + left.node_count() + right.node_count(),
+            Expression::AggregateFunction(function) => 1 + function.expr.node_count(),
+            Expression::Subquery(query) => 1 + query.node_count(),
+            Expression::Cast((expr, _)) => 1 + expr.node_count(),
+        }
+    }
+
     fn fmt_with_parent_op(
         &self,
         f: &mut std::fmt::Formatter<'_>,
@@ -76,9 +164,21 @@ impl Expression<'_> {
         let needs_parens = match (self, parent_op) {
             (Expression::BinaryOp((_, child_op, _)), Some(parent_op)) => {
                 let child_bp = child_op.infix_binding_power().map(|(l_bp, _)| l_bp).unwrap_or(0);
-                let parent_bp = parent_op.infix_binding_power().map(|(l_bp, _)| l_bp).unwrap_or(0);
+                let (parent_bp, parent_r_bp) = parent_op.infix_binding_power().unwrap_or((0, 0));
+                // An equal-precedence child keeps its parentheses on the side the operator
+                // does not associate towards.
+                let right_associative = parent_r_bp < parent_bp;
+                let outer_side = match side {
+                    ChildSide::Left => right_associative,
+                    ChildSide::Right => !right_associative,
+                };
 
-                child_bp < parent_bp || matches!(side, ChildSide::Right) && child_bp == parent_bp
+                child_bp < parent_bp || outer_side && child_bp == parent_bp
+            }
+            // `-a ^ b` parses as `-(a ^ b)`, so a negated left operand of `^` needs parentheses.
+            (Expression::UnaryOp(_), Some(Op::Pow)) => matches!(side, ChildSide::Left),
+            (Expression::Literal(Literal::Number(number)), Some(Op::Pow)) => {
+                matches!(side, ChildSide::Left) && number.to_string().starts_with('-')
             }
             _ => false,
         };
@@ -89,7 +189,7 @@ impl Expression<'_> {
 
         match self {
             Expression::Literal(literal) => write!(f, "{}", literal),
-            Expression::Identifier(ident) => write!(f, "{}", ident),
+            Expression::Identifier(ident) => write!(f, "{}", quote_ident(ident)),
             Expression::UnaryOp((op, expr)) => {
                 write!(f, "{}", op)?;
                 if matches!(**expr, Expression::BinaryOp(_)) {
@@ -105,6 +205,26 @@ impl Expression<'_> {
             }
             Expression::Wildcard => write!(f, "*"),
             Expression::AggregateFunction(agg) => write!(f, "{}", agg),
+            Expression::Subquery(query) => {
+                write!(f, "(")?;
+                query.fmt_body(f)?;
+                write!(f, ")")
+            }
+            Expression::Cast((expr, data_type)) => {
+                // `::` binds tighter than a prefix operator, so `-1::INT` is `-(1::INT)`.
+                let needs_parens = match &**expr {
+                    Expression::UnaryOp(_) | Expression::BinaryOp(_) => true,
+                    Expression::Literal(Literal::Number(number)) => {
+                        number.to_string().starts_with('-')
+                    }
+                    _ => false,
+                };
+                if needs_parens {
+                    write!(f, "({expr})::{data_type}")
+                } else {
+                    write!(f, "{expr}::{data_type}")
+                }
+            }
         }?;
 
         if needs_parens {
@@ -130,16 +250,18 @@ impl Display for AggregateFunctionKind {
 
 impl Display for AggregateFunction<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}({})", self.kind, self.expr)
+        let distinct = if self.distinct { "DISTINCT " } else { "" };
+        write!(f, "{}({distinct}{})", self.kind, self.expr)
     }
 }
 
 impl Display for Literal<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
-            Literal::String(s) => write!(f, "\"{}\"", s),
+            Literal::String(s) => write!(f, "'{}'", s.replace('\'', "''")),
             Literal::Number(n) => write!(f, "{}", n),
-            Literal::Boolean(b) => write!(f, "{}", b),
+            Literal::Boolean(b) => f.write_str(if *b { "TRUE" } else { "FALSE" }),
+            Literal::Null => write!(f, "NULL"),
         }
     }
 }
@@ -154,6 +276,25 @@ mod tests {
         select::SelectQuery,
     };
 
+    #[test]
+    fn test_tree_string_nests_tighter_operators() {
+        let expr = Parser::new("a + b * c").expr().unwrap();
+        assert_eq!(expr.tree_string(0), "+\n  a\n  *\n    b\n    c\n");
+
+        let expr = Parser::new("(a + b) * c").expr().unwrap();
+        assert_eq!(expr.tree_string(0), "*\n  +\n    a\n    b\n  c\n");
+    }
+
+    #[test]
+    fn test_tree_string_renders_unary_and_aggregate_nodes() {
+        let expr = Parser::new("NOT SUM(x) > -1").expr().unwrap();
+        let expected = "  >\n    NOT\n      SUM\n        x\n    -\n      1\n";
+        assert_eq!(expr.tree_string(1), expected);
+
+        let expr = Parser::new("COUNT(DISTINCT x)::TEXT").expr().unwrap();
+        assert_eq!(expr.tree_string(0), "::TEXT\n  COUNT DISTINCT\n    x\n");
+    }
+
     #[test]
     fn test_all_aggregate_functions() {
         let sql = "SELECT COUNT(*), SUM(price), AVG(price), STDDEV(price), MAX(price), MIN(price) FROM products;";
@@ -162,33 +303,42 @@ mod tests {
 
         let expected_query = Statement::Select(SelectQuery {
             table: Some("products"),
+            distinct: false,
             columns: ExpressionList(vec![
                 Expression::AggregateFunction(AggregateFunction {
                     kind: AggregateFunctionKind::Count,
+                    distinct: false,
                     expr: Box::new(Expression::Wildcard),
                 }),
                 Expression::AggregateFunction(AggregateFunction {
                     kind: AggregateFunctionKind::Sum,
+                    distinct: false,
                     expr: Box::new(Expression::Identifier("price")),
                 }),
                 Expression::AggregateFunction(AggregateFunction {
                     kind: AggregateFunctionKind::Avg,
+                    distinct: false,
                     expr: Box::new(Expression::Identifier("price")),
                 }),
                 Expression::AggregateFunction(AggregateFunction {
                     kind: AggregateFunctionKind::StdDev,
+                    distinct: false,
                     expr: Box::new(Expression::Identifier("price")),
                 }),
                 Expression::AggregateFunction(AggregateFunction {
                     kind: AggregateFunctionKind::Max,
+                    distinct: false,
                     expr: Box::new(Expression::Identifier("price")),
                 }),
                 Expression::AggregateFunction(AggregateFunction {
                     kind: AggregateFunctionKind::Min,
+                    distinct: false,
                     expr: Box::new(Expression::Identifier("price")),
                 }),
             ]),
             where_clause: None,
+            group_by: None,
+            having: None,
             order_by: None,
             limit: None,
             offset: None,
@@ -198,11 +348,74 @@ mod tests {
         // Test that the struct format works correctly
         let test_agg = AggregateFunction {
             kind: AggregateFunctionKind::Sum,
+            distinct: false,
             expr: Box::new(Expression::Identifier("price")),
         };
         assert_eq!(format!("{}", test_agg), "SUM(price)");
     }
 
+    #[test]
+    fn select_null_literal() {
+        let mut parser = Parser::new("SELECT NULL;");
+
+        let expected = Statement::Select(SelectQuery {
+            distinct: false,
+            columns: ExpressionList(vec![Expression::Literal(Literal::Null)]),
+            table: None,
+            where_clause: None,
+            group_by: None,
+            having: None,
+            order_by: None,
+            limit: None,
+            offset: None,
+        });
+        assert_eq!(parser.stmt(), Ok(expected));
+    }
+
+    #[test]
+    fn null_literal_is_case_insensitive_and_displays_uppercase() {
+        let expr = Parser::new("null").expr().unwrap();
+
+        assert_eq!(expr, Expression::Literal(Literal::Null));
+        assert_eq!(expr.to_string(), "NULL");
+    }
+
+    #[test]
+    fn boolean_literals_display_uppercase() {
+        assert_eq!(format!("{}", Literal::Boolean(true)), "TRUE");
+        assert_eq!(format!("{}", Literal::Boolean(false)), "FALSE");
+
+        let statement = Parser::new("select true, False;").stmt().unwrap();
+        assert_eq!(statement.to_string(), "SELECT TRUE, FALSE;");
+    }
+
+    #[test]
+    fn null_literal_compares_in_where_and_round_trips() {
+        let s = "SELECT a FROM t WHERE x == NULL OR NULL != y;";
+        let statement = Parser::new(s).stmt().unwrap();
+        let Statement::Select(SelectQuery { where_clause: Some(where_clause), .. }) = &statement
+        else {
+            panic!("expected SELECT with WHERE, got {statement:?}");
+        };
+        let null = || Box::new(Expression::Literal(Literal::Null));
+        let expected = Expression::BinaryOp((
+            Box::new(Expression::BinaryOp((
+                Box::new(Expression::Identifier("x")),
+                Op::EqualsEquals,
+                null(),
+            ))),
+            Op::Or,
+            Box::new(Expression::BinaryOp((
+                null(),
+                Op::NotEquals,
+                Box::new(Expression::Identifier("y")),
+            ))),
+        ));
+        assert_eq!(where_clause, &expected);
+        assert_eq!(statement.to_string(), s);
+        assert_eq!(Parser::new(&statement.to_string()).stmt(), Ok(statement));
+    }
+
     #[test]
     fn aggregate_functions_display_with_their_argument() {
         let cases = [
@@ -214,7 +427,7 @@ mod tests {
         ];
 
         for (kind, expr, expected) in cases {
-            let aggregate = AggregateFunction { kind, expr: Box::new(expr) };
+            let aggregate = AggregateFunction { kind, distinct: false, expr: Box::new(expr) };
 
             assert_eq!(aggregate.to_string(), expected);
         }
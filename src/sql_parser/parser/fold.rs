@@ -0,0 +1,193 @@
+use crate::sql_parser::{
+    lexer::token_kind::NumberKind,
+    parser::{
+        eval::{evaluate_arithmetic, negate},
+        expr::{AggregateFunction, Expression, Literal},
+        op::Op,
+    },
+};
+
+/// Evaluates arithmetic on numeric literals ahead of execution.
+///
+/// `2 + 3 * 4` becomes `14`, and an integer combined with a float is promoted
+/// to a float. Identifiers are left in place, so `a + 2 * 3` folds to `a + 6`.
+/// Operations that would overflow, divide by zero or produce a non-finite float
+/// are kept unfolded so the executor reports them as it would without folding.
+pub fn fold_constants(expr: Expression<'_>) -> Expression<'_> {
+    match expr {
+        Expression::UnaryOp((op, operand)) => {
+            let operand = fold_constants(*operand);
+            match (op, &operand) {
+                (Op::Add, Expression::Literal(Literal::Number(_))) => return operand,
+                (Op::Sub, Expression::Literal(Literal::Number(number))) => {
+                    if let Ok(negated) = negate(*number) {
+                        return number_expr(negated);
+                    }
+                }
+                _ => {}
+            }
+            Expression::UnaryOp((op, Box::new(operand)))
+        }
+        Expression::BinaryOp((left, op, right)) => {
+            let left = fold_constants(*left);
+            let right = fold_constants(*right);
+            if let (
+                Expression::Literal(Literal::Number(lhs)),
+                Expression::Literal(Literal::Number(rhs)),
+            ) = (&left, &right)
+                && let Some(folded) = fold_arithmetic(*lhs, op, *rhs)
+            {
+                return number_expr(folded);
+            }
+            Expression::BinaryOp((Box::new(left), op, Box::new(right)))
+        }
+        Expression::AggregateFunction(AggregateFunction { kind, distinct, expr }) => {
+            Expression::AggregateFunction(AggregateFunction {
+                kind,
+                distinct,
+                expr: Box::new(fold_constants(*expr)),
+            })
+        }
+        Expression::Cast((expr, data_type)) => {
+            Expression::Cast((Box::new(fold_constants(*expr)), data_type))
+        }
+        other => other,
+    }
+}
+
+/// Removes boolean operators whose result is fixed by a literal operand.
+///
+/// `x AND true` and `x OR false` reduce to `x`, `x AND false` to `false`,
+/// `x OR true` to `true`, and `NOT NOT x` to `x`. Operands are simplified
+/// first, so `(a AND true) OR false` reduces all the way to `a`.
+pub fn simplify_boolean(expr: Expression<'_>) -> Expression<'_> {
+    match expr {
+        Expression::UnaryOp((Op::Not, operand)) => match simplify_boolean(*operand) {
+            Expression::UnaryOp((Op::Not, inner)) => *inner,
+            operand => Expression::UnaryOp((Op::Not, Box::new(operand))),
+        },
+        Expression::BinaryOp((left, op @ (Op::And | Op::Or), right)) => {
+            let left = simplify_boolean(*left);
+            let right = simplify_boolean(*right);
+            // `AND` is absorbed by `false` and `OR` by `true`; the other literal is an identity.
+            let absorbing = op == Op::Or;
+            match (boolean_literal(&left), boolean_literal(&right)) {
+                (Some(value), _) | (_, Some(value)) if value == absorbing => {
+                    Expression::Literal(Literal::Boolean(absorbing))
+                }
+                (Some(_), _) => right,
+                (_, Some(_)) => left,
+                _ => Expression::BinaryOp((Box::new(left), op, Box::new(right))),
+            }
+        }
+        other => other,
+    }
+}
+
+fn boolean_literal(expr: &Expression<'_>) -> Option<bool> {
+    match expr {
+        Expression::Literal(Literal::Boolean(value)) => Some(*value),
+        _ => None,
+    }
+}
+
+fn number_expr<'a>(number: NumberKind) -> Expression<'a> {
+    Expression::Literal(Literal::Number(number))
+}
+
+fn fold_arithmetic(lhs: NumberKind, op: Op, rhs: NumberKind) -> Option<NumberKind> {
+    match evaluate_arithmetic(lhs, op, rhs).ok()? {
+        NumberKind::Float(value) if !value.is_finite() => None,
+        number => Some(number),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_parser::parser::Parser;
+
+    fn fold(source: &str) -> Expression<'_> {
+        fold_constants(Parser::new(source).expr().unwrap())
+    }
+
+    fn simplify(source: &str) -> Expression<'_> {
+        simplify_boolean(Parser::new(source).expr().unwrap())
+    }
+
+    #[test]
+    fn test_simplify_identity_operands() {
+        assert_eq!(simplify("x AND true"), Expression::Identifier("x"));
+        assert_eq!(simplify("true AND x"), Expression::Identifier("x"));
+        assert_eq!(simplify("x OR false"), Expression::Identifier("x"));
+        assert_eq!(simplify("false OR x"), Expression::Identifier("x"));
+    }
+
+    #[test]
+    fn test_simplify_absorbing_operands() {
+        assert_eq!(simplify("x AND false"), Expression::from(false));
+        assert_eq!(simplify("false AND x"), Expression::from(false));
+        assert_eq!(simplify("x OR true"), Expression::from(true));
+        assert_eq!(simplify("true OR x"), Expression::from(true));
+    }
+
+    #[test]
+    fn test_simplify_double_negation() {
+        assert_eq!(simplify("NOT NOT x"), Expression::Identifier("x"));
+        assert_eq!(simplify("NOT NOT NOT x"), simplify("NOT x"));
+    }
+
+    #[test]
+    fn test_simplify_nested_expression() {
+        assert_eq!(simplify("(a AND true) OR false"), Expression::Identifier("a"));
+        assert_eq!(simplify("NOT NOT (b OR false) AND (c OR true)"), Expression::Identifier("b"));
+        assert_eq!(simplify("a AND b"), Parser::new("a AND b").expr().unwrap());
+    }
+
+    #[test]
+    fn test_fold_integer_arithmetic() {
+        assert_eq!(fold("2 + 3 * 4"), Expression::from(14));
+        assert_eq!(fold("(2 + 3) * 4 - -1"), Expression::from(21));
+        assert_eq!(fold("+(1 + 2) - +-3"), Expression::from(6));
+    }
+
+    #[test]
+    fn test_fold_promotes_mixed_operands_to_float() {
+        assert_eq!(fold("1 + 0.5"), Expression::from(1.5));
+        assert_eq!(fold("2.5 * 2"), Expression::from(5.0));
+    }
+
+    #[test]
+    fn test_fold_division() {
+        assert_eq!(fold("7 / 2"), Expression::from(3));
+        assert_eq!(fold("7.0 / 2"), Expression::from(3.5));
+    }
+
+    #[test]
+    fn test_fold_keeps_overflow_and_division_by_zero() {
+        assert_eq!(fold("2147483647 + 1"), Parser::new("2147483647 + 1").expr().unwrap());
+        assert_eq!(fold("1 / 0"), Parser::new("1 / 0").expr().unwrap());
+        assert_eq!(fold("1.0 / 0"), Parser::new("1.0 / 0").expr().unwrap());
+    }
+
+    #[test]
+    fn test_fold_leaves_identifiers_in_place() {
+        let expected = Expression::BinaryOp((
+            Box::new(Expression::Identifier("a")),
+            Op::Add,
+            Box::new(Expression::from(6)),
+        ));
+        assert_eq!(fold("a + 2 * 3"), expected);
+
+        let expected = Expression::BinaryOp((
+            Box::new(Expression::BinaryOp((
+                Box::new(Expression::from(3)),
+                Op::Mul,
+                Box::new(Expression::Identifier("b")),
+            ))),
+            Op::LessThan,
+            Box::new(Expression::from(10)),
+        ));
+        assert_eq!(fold("(1 + 2) * b < 5 * 2"), expected);
+    }
+}
@@ -0,0 +1,80 @@
+use std::borrow::Cow;
+
+use crate::sql_parser::lexer::token_kind::TokenKind;
+
+/// How two identifiers are compared when resolving names.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum IdentCmp {
+    /// Identifiers match only if they are spelled identically, as for quoted identifiers.
+    CaseSensitive,
+    /// Identifiers match regardless of letter case, as for bare identifiers.
+    #[default]
+    CaseInsensitive,
+}
+
+/// Returns whether identifiers `a` and `b` name the same thing under `mode`.
+///
+/// Case-insensitive comparison folds Unicode letters as well as ASCII ones, so
+/// `Straße` and `STRASSE` do not match but `Ärger` and `ärger` do.
+pub fn ident_eq(a: &str, b: &str, mode: IdentCmp) -> bool {
+    match mode {
+        IdentCmp::CaseSensitive => a == b,
+        IdentCmp::CaseInsensitive => {
+            a.chars().flat_map(char::to_lowercase).eq(b.chars().flat_map(char::to_lowercase))
+        }
+    }
+}
+
+/// Returns `name` as it must be written in SQL to lex back as the same identifier.
+///
+/// Bare identifiers are returned as they are. Keywords and names a bare
+/// identifier cannot spell, such as `first name`, are wrapped in double quotes,
+/// or in backticks if the name itself contains a double quote. The lexer has
+/// no escape for quotes inside a quoted identifier, so a name containing both
+/// quote characters cannot be spelled in SQL at all.
+pub fn quote_ident(name: &str) -> Cow<'_, str> {
+    let mut chars = name.chars();
+    let is_bare = chars.next().is_some_and(|c| c.is_alphabetic() || c == '_')
+        && chars.all(|c| c.is_alphanumeric() || c == '_')
+        && matches!(TokenKind::from(name), TokenKind::Identifier(_));
+    if is_bare {
+        return Cow::Borrowed(name);
+    }
+    let quote = if name.contains('"') { '`' } else { '"' };
+    Cow::Owned(format!("{quote}{name}{quote}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_parser::parser::{Parser, expr::Expression};
+
+    #[test]
+    fn test_ident_eq_respects_mode() {
+        assert!(ident_eq("Foo", "foo", IdentCmp::CaseInsensitive));
+        assert!(!ident_eq("Foo", "foo", IdentCmp::CaseSensitive));
+        assert!(ident_eq("foo", "foo", IdentCmp::CaseSensitive));
+        assert!(ident_eq("Ärger", "äRGER", IdentCmp::CaseInsensitive));
+        assert!(!ident_eq("foo", "foo_", IdentCmp::CaseInsensitive));
+    }
+
+    #[test]
+    fn test_quoted_identifiers_can_compare_case_sensitively() {
+        let Expression::Identifier(quoted) = Parser::new("`Users`").expr().unwrap() else {
+            panic!("expected an identifier");
+        };
+        assert!(ident_eq(quoted, "users", IdentCmp::CaseInsensitive));
+        assert!(!ident_eq(quoted, "users", IdentCmp::CaseSensitive));
+        assert!(ident_eq(quoted, "Users", IdentCmp::CaseSensitive));
+    }
+
+    #[test]
+    fn test_quote_ident_only_quotes_when_needed() {
+        assert_eq!(quote_ident("users"), "users");
+        assert_eq!(quote_ident("_Straße2"), "_Straße2");
+        assert_eq!(quote_ident("select"), r#""select""#);
+        assert_eq!(quote_ident("first name"), r#""first name""#);
+        assert_eq!(quote_ident("2fa"), r#""2fa""#);
+        assert_eq!(quote_ident(r#"a"b"#), r#"`a"b`"#);
+    }
+}
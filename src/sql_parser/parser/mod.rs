@@ -1,8 +1,17 @@
+pub mod build;
+pub mod eval;
 pub mod expr;
+pub mod fold;
+pub mod ident;
 pub mod op;
+pub mod pretty;
 pub mod stmt;
+pub mod typecheck;
+pub mod validate;
+pub mod visit;
 
 use std::fmt::Display;
+use std::ops::Range;
 
 use expr::{AggregateFunction, AggregateFunctionKind, Expression, Literal};
 use op::Op;
@@ -10,16 +19,45 @@ use stmt::Statement;
 use stmt::lists::{ExpressionList, IdentifierList};
 
 use crate::sql_parser::error::{SQLError, SQLErrorKind};
-use crate::sql_parser::lexer::Lexer;
 use crate::sql_parser::lexer::token::Token;
 use crate::sql_parser::lexer::token_kind::{Aggregate, Keyword, NumberKind, TokenKind};
+use crate::sql_parser::lexer::{Lexer, unescape_string};
+
+/// Default limit on how deeply expressions may nest.
+pub const DEFAULT_MAX_DEPTH: usize = 128;
+
+/// `::` binds tighter than any prefix or infix operator, so `-a::INT` is `-(a::INT)`.
+const CAST_BINDING_POWER: u8 = 9;
+
+/// Limits and options for a [`Parser`], set in one place.
+///
+/// The [`Default`] configuration is what [`Parser::new`] uses.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ParserConfig {
+    /// Deepest expression nesting accepted before `ExpressionTooDeep` is reported.
+    pub max_depth: usize,
+    /// Largest [`Statement::node_count`] accepted, if any.
+    pub max_nodes: Option<usize>,
+}
+
+impl Default for ParserConfig {
+    fn default() -> Self {
+        Self { max_depth: DEFAULT_MAX_DEPTH, max_nodes: None }
+    }
+}
 
 #[derive(Debug)]
 pub struct Parser<'a> {
+    source: &'a str,
     lexer: Lexer<'a>,
+    depth: usize,
+    config: ParserConfig,
+    /// Whether end of input may stand in for a statement's closing semicolon.
+    eof_terminates: bool,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Command {
     Begin,
     Commit,
@@ -37,6 +75,7 @@ impl Display for Command {
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum SqlItem<'a> {
     Statement(Statement<'a>),
     Command(Command),
@@ -51,6 +90,57 @@ impl Display for SqlItem<'_> {
     }
 }
 
+/// The kind of statement at the start of the remaining input.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatementKind {
+    Explain,
+    With,
+    Select,
+    Insert,
+    Values,
+    Update,
+    Delete,
+    Create,
+}
+
+/// A parsed node together with the byte range of source text it came from.
+#[derive(Debug, PartialEq)]
+pub struct Spanned<T> {
+    pub node: T,
+    /// Byte range from the first token through the terminating semicolon.
+    pub span: Range<usize>,
+}
+
+/// Parses `source` as a standalone boolean predicate, as found after `WHERE`.
+///
+/// Trailing tokens are rejected, as are expressions that are not
+/// predicate-shaped, such as a lone identifier or arithmetic.
+pub fn parse_predicate(source: &str) -> Result<Expression<'_>, SQLError<'_>> {
+    let mut parser = Parser::new(source);
+    let start = match parser.lexer.peek() {
+        Some(Ok(token)) => token.offset,
+        _ => parser.lexer.position,
+    };
+    let predicate = parser.expr_bp(0)?;
+    parser.expect_end()?;
+
+    if !predicate.is_predicate() {
+        return Err(SQLError::new(SQLErrorKind::ExpectedPredicate, start));
+    }
+    Ok(predicate)
+}
+
+/// Parses all of `source` as one expression.
+///
+/// Unlike [`Parser::expr`], which stops at the first token that cannot continue
+/// the expression, any token left over is reported as an error at its offset.
+pub fn parse_expression_complete(source: &str) -> Result<Expression<'_>, SQLError<'_>> {
+    let mut parser = Parser::new(source);
+    let expr = parser.expr_bp(0)?;
+    parser.expect_end()?;
+    Ok(expr)
+}
+
 impl<'a> Iterator for Parser<'a> {
     type Item = Result<SqlItem<'a>, SQLError<'a>>;
 
@@ -64,16 +154,37 @@ impl<'a> Iterator for Parser<'a> {
 
 impl<'a> Parser<'a> {
     pub fn new(source: &'a str) -> Self {
-        Self { lexer: Lexer::new(source) }
+        Self::with_config(source, ParserConfig::default())
+    }
+
+    pub fn with_config(source: &'a str, config: ParserConfig) -> Self {
+        Self { source, lexer: Lexer::new(source), depth: 0, config, eof_terminates: false }
+    }
+
+    /// Creates a parser that rejects expressions nested deeper than `max_depth`.
+    pub fn with_max_depth(source: &'a str, max_depth: usize) -> Self {
+        Self::with_config(source, ParserConfig { max_depth, ..ParserConfig::default() })
+    }
+
+    /// Rejects statements whose [`Statement::node_count`] exceeds `max_nodes`.
+    pub fn with_max_nodes(mut self, max_nodes: usize) -> Self {
+        self.config.max_nodes = Some(max_nodes);
+        self
     }
 
     fn parse_non_negative_integer(&mut self) -> Result<Option<u32>, SQLError<'a>> {
-        let tok = self
-            .lexer
-            .next()
-            .ok_or(SQLError { kind: SQLErrorKind::UnexpectedEnd, pos: self.lexer.position })??;
+        let tok = self.lexer.next().ok_or_else(|| self.lexer.unexpected_end())??;
         match tok.kind {
             TokenKind::Number(NumberKind::Integer(num)) => Ok(num.try_into().ok()),
+            TokenKind::Plus => {
+                let tok = self.lexer.next().ok_or_else(|| self.lexer.unexpected_end())??;
+                match tok.kind {
+                    TokenKind::Number(NumberKind::Integer(num)) => Ok(num.try_into().ok()),
+                    other => {
+                        Err(SQLError::new(SQLErrorKind::ExpectedInteger { got: other }, tok.offset))
+                    }
+                }
+            }
             TokenKind::Minus => {
                 if let Some(Ok(Token {
                     kind: TokenKind::Number(NumberKind::Integer(num)), ..
@@ -130,25 +241,25 @@ impl<'a> Parser<'a> {
     }
 
     fn parse_identifier(&mut self) -> Result<&'a str, SQLError<'a>> {
-        self.lexer
-            .next()
-            .ok_or(SQLError { kind: SQLErrorKind::UnexpectedEnd, pos: self.lexer.position })
-            .and_then(|tok| {
-                tok.map(|tok| match tok.kind {
-                    TokenKind::Identifier(id) => Ok(id),
-                    other => Err(SQLError::new(
-                        SQLErrorKind::ExpectedIdentifier { got: other },
-                        self.lexer.position,
-                    )),
-                })
-            })?
+        self.lexer.next().ok_or_else(|| self.lexer.unexpected_end()).and_then(|tok| {
+            tok.map(|tok| match tok.kind {
+                TokenKind::Identifier(id) => Ok(id),
+                TokenKind::Keyword(_) => Err(SQLError::spanned(
+                    SQLErrorKind::ReservedKeywordAsIdentifier { keyword: tok.lexeme },
+                    tok.offset,
+                    self.lexer.token_end,
+                )),
+                other => Err(SQLError::spanned(
+                    SQLErrorKind::ExpectedIdentifier { got: other },
+                    tok.offset,
+                    self.lexer.token_end,
+                )),
+            })
+        })?
     }
 
     pub fn item(&mut self) -> Result<SqlItem<'a>, SQLError<'a>> {
-        let token = self
-            .lexer
-            .next()
-            .ok_or(SQLError { kind: SQLErrorKind::UnexpectedEnd, pos: self.lexer.position })??;
+        let token = self.lexer.next().ok_or_else(|| self.lexer.unexpected_end())??;
         match token.kind {
             TokenKind::Keyword(Keyword::Begin) => {
                 Ok(SqlItem::Command(self.parse_command(Command::Begin)?))
@@ -163,20 +274,113 @@ impl<'a> Parser<'a> {
         }
     }
 
+    /// Parses every item in a script, recovering from errors.
+    ///
+    /// When an item fails to parse, its error is recorded and the remaining
+    /// tokens up to and including the next `;` are skipped before parsing resumes.
+    pub fn parse_script(mut self) -> (Vec<SqlItem<'a>>, Vec<SQLError<'a>>) {
+        let mut items = Vec::new();
+        let mut errors = Vec::new();
+        while self.lexer.peek().is_some() {
+            match self.item() {
+                Ok(item) => items.push(item),
+                Err(err) => {
+                    errors.push(err);
+                    self.skip_past_semicolon();
+                }
+            }
+        }
+        (items, errors)
+    }
+
+    fn skip_past_semicolon(&mut self) {
+        let consumed_semicolon =
+            self.lexer.peeked.is_empty() && self.source[..self.lexer.position].ends_with(';');
+        if consumed_semicolon {
+            return;
+        }
+        for token in self.lexer.by_ref() {
+            if let Ok(Token { kind: TokenKind::Semicolon, .. }) = token {
+                return;
+            }
+        }
+    }
+
+    /// Parses exactly one statement, whose closing semicolon may be left out.
+    ///
+    /// Meant for interactive input such as `SELECT 1`. Unlike [`Parser::stmt`]
+    /// and the [`Iterator`] impl, end of input is accepted in place of `;`, but
+    /// anything after the statement is rejected.
+    pub fn parse_single_statement_allow_eof(mut self) -> Result<Statement<'a>, SQLError<'a>> {
+        self.eof_terminates = true;
+        let statement = self.stmt()?;
+        self.expect_end()?;
+        Ok(statement)
+    }
+
     pub fn stmt(&mut self) -> Result<Statement<'a>, SQLError<'a>> {
-        let token = self
-            .lexer
-            .next()
-            .ok_or(SQLError { kind: SQLErrorKind::UnexpectedEnd, pos: self.lexer.position })??;
+        let token = self.lexer.next().ok_or_else(|| self.lexer.unexpected_end())??;
         self.parse_statement_from_token(token)
     }
 
+    /// Returns the kind of the next statement without consuming any input.
+    pub fn peek_statement_kind(&mut self) -> Result<StatementKind, SQLError<'a>> {
+        let token = match self.lexer.peek() {
+            Some(Ok(token)) => *token,
+            Some(Err(err)) => return Err(*err),
+            None => return Err(self.lexer.unexpected_end()),
+        };
+        let kind = match token.kind {
+            TokenKind::Keyword(Keyword::Explain) => StatementKind::Explain,
+            TokenKind::Keyword(Keyword::With) => StatementKind::With,
+            TokenKind::Keyword(Keyword::Select) => StatementKind::Select,
+            TokenKind::Keyword(Keyword::Insert) => StatementKind::Insert,
+            TokenKind::Keyword(Keyword::Values) => StatementKind::Values,
+            TokenKind::Keyword(Keyword::Update) => StatementKind::Update,
+            TokenKind::Keyword(Keyword::Delete) => StatementKind::Delete,
+            TokenKind::Keyword(Keyword::Create) => StatementKind::Create,
+            other => return Err(SQLError::new(SQLErrorKind::Other(other), token.offset)),
+        };
+        Ok(kind)
+    }
+
+    /// Parses a statement and records the source span it was parsed from.
+    pub fn spanned_stmt(&mut self) -> Result<Spanned<Statement<'a>>, SQLError<'a>> {
+        let token = self.lexer.next().ok_or_else(|| self.lexer.unexpected_end())??;
+        let node = self.parse_statement_from_token(token)?;
+        Ok(Spanned { node, span: token.offset..self.lexer.token_end })
+    }
+
     fn parse_statement_from_token(
         &mut self,
         token: Token<'a>,
     ) -> Result<Statement<'a>, SQLError<'a>> {
+        let statement = self.parse_statement_kind(token)?;
+        if let Some(limit) = self.config.max_nodes
+            && statement.node_count() > limit
+        {
+            return Err(SQLError::spanned(
+                SQLErrorKind::StatementTooLarge { limit },
+                token.offset,
+                self.lexer.token_end,
+            ));
+        }
+        Ok(statement)
+    }
+
+    fn parse_statement_kind(&mut self, token: Token<'a>) -> Result<Statement<'a>, SQLError<'a>> {
         match token.kind {
-            TokenKind::Keyword(Keyword::Explain) => Ok(Statement::Explain(Box::new(self.stmt()?))),
+            TokenKind::Keyword(Keyword::Explain) => {
+                // `EXPLAIN;` ends before there is a statement to explain. The `;` is
+                // left unconsumed, so a script resumes with whatever follows it.
+                if let Some(Ok(Token { kind: TokenKind::Semicolon, offset, .. })) =
+                    self.lexer.peek()
+                {
+                    return Err(SQLError::new(SQLErrorKind::UnexpectedEnd, *offset));
+                }
+                Ok(Statement::Explain(Box::new(self.stmt()?)))
+            }
+            TokenKind::Keyword(Keyword::With) => Ok(Statement::With(self.parse_with_query()?)),
             TokenKind::Keyword(Keyword::Select) => {
                 Ok(Statement::Select(self.parse_select_query()?))
             }
@@ -189,21 +393,38 @@ impl<'a> Parser<'a> {
             TokenKind::Keyword(Keyword::Insert) => {
                 Ok(Statement::Insert(self.parse_insert_query()?))
             }
+            TokenKind::Keyword(Keyword::Values) => {
+                Ok(Statement::Values(self.parse_values_statement()?))
+            }
             TokenKind::Keyword(Keyword::Create) => self.parse_create_query(),
             other => Err(SQLError::new(SQLErrorKind::Other(other), token.offset)),
         }
     }
 
+    /// Checks that the input is exhausted, reporting the first leftover token at its offset.
+    fn expect_end(&mut self) -> Result<(), SQLError<'a>> {
+        match self.lexer.next() {
+            None => Ok(()),
+            Some(Ok(token)) => Err(SQLError::new(SQLErrorKind::Other(token.kind), token.offset)),
+            Some(Err(err)) => Err(err),
+        }
+    }
+
+    /// Consumes the `;` that ends a statement, or accepts end of input when allowed.
+    fn expect_statement_end(&mut self) -> Result<(), SQLError<'a>> {
+        if self.eof_terminates && self.lexer.peek().is_none() {
+            return Ok(());
+        }
+        self.lexer.expect_token(TokenKind::Semicolon)
+    }
+
     fn parse_command(&mut self, command: Command) -> Result<Command, SQLError<'a>> {
         self.lexer.expect_token(TokenKind::Semicolon)?;
         Ok(command)
     }
 
     fn parse_create_query(&mut self) -> Result<Statement<'a>, SQLError<'a>> {
-        let token = self
-            .lexer
-            .next()
-            .ok_or(SQLError { kind: SQLErrorKind::UnexpectedEnd, pos: self.lexer.position })??;
+        let token = self.lexer.next().ok_or_else(|| self.lexer.unexpected_end())??;
         match token.kind {
             TokenKind::Keyword(Keyword::Table) => {
                 Ok(Statement::CreateTable(self.parse_create_table_query()?))
@@ -230,25 +451,64 @@ impl<'a> Parser<'a> {
     }
 
     fn expr_bp(&mut self, min_bp: u8) -> Result<Expression<'a>, SQLError<'a>> {
-        let token = self
-            .lexer
-            .next()
-            .ok_or(SQLError { kind: SQLErrorKind::UnexpectedEnd, pos: self.lexer.position })??;
+        let depth = self.depth;
+        self.enter_nested_expression()?;
+        let expr = self.expr_bp_nested(min_bp);
+        // Postfix casts also raise the depth, so restore it rather than decrementing.
+        self.depth = depth;
+        expr
+    }
+
+    /// Counts one more level of expression nesting against [`ParserConfig::max_depth`].
+    fn enter_nested_expression(&mut self) -> Result<(), SQLError<'a>> {
+        if self.depth >= self.config.max_depth {
+            let pos = match self.lexer.peek() {
+                Some(Ok(token)) => token.offset,
+                _ => self.lexer.token_end,
+            };
+            return Err(SQLError::new(
+                SQLErrorKind::ExpressionTooDeep { limit: self.config.max_depth },
+                pos,
+            ));
+        }
+        self.depth += 1;
+        Ok(())
+    }
+
+    fn expr_bp_nested(&mut self, min_bp: u8) -> Result<Expression<'a>, SQLError<'a>> {
+        let token = self.lexer.next().ok_or_else(|| self.lexer.unexpected_end())??;
         let mut lhs = match token.kind {
-            TokenKind::String(lit) => Expression::Literal(Literal::String(lit)),
+            TokenKind::String(lit) => Expression::Literal(Literal::String(unescape_string(lit))),
             TokenKind::Number(num) => Expression::Literal(Literal::Number(num)),
             TokenKind::Keyword(Keyword::True) => Expression::Literal(Literal::Boolean(true)),
             TokenKind::Keyword(Keyword::False) => Expression::Literal(Literal::Boolean(false)),
+            TokenKind::Keyword(Keyword::Null) => Expression::Literal(Literal::Null),
             TokenKind::Identifier(id) => Expression::Identifier(id),
             TokenKind::Asterisk => Expression::Wildcard,
-            TokenKind::LeftParen => {
-                let lhs = self
-                    .expr_bp(0)
+            TokenKind::LeftParen
+                if matches!(
+                    self.lexer.peek(),
+                    Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Select), .. }))
+                ) =>
+            {
+                self.lexer.next();
+                let query = self.parse_select_body()?;
+                self.lexer
+                    .expect_token(TokenKind::RightParen)
                     .map_err(|_| SQLError::new(SQLErrorKind::UnclosedParenthesis, token.offset))?;
+                Expression::Subquery(Box::new(query))
+            }
+            TokenKind::LeftParen => {
+                let lhs = self.expr_bp(0).map_err(|err| match err.kind {
+                    SQLErrorKind::ExpressionTooDeep { .. } => err,
+                    _ => SQLError::new(SQLErrorKind::UnclosedParenthesis, token.offset),
+                })?;
                 self.lexer.expect_token(TokenKind::RightParen)?;
                 lhs
             }
-            TokenKind::Minus | TokenKind::Keyword(Keyword::Not) => self.parse_unary_op(token)?,
+            TokenKind::Plus | TokenKind::Minus | TokenKind::Keyword(Keyword::Not) => {
+                self.parse_unary_op(token)?
+            }
             TokenKind::Keyword(Keyword::Aggregate(agg)) => self.parse_aggregate_function(agg)?,
             other => {
                 return Err(SQLError::new(SQLErrorKind::Other(other), token.offset));
@@ -265,16 +525,32 @@ impl<'a> Parser<'a> {
                         | TokenKind::Keyword(
                             Keyword::From
                                 | Keyword::Where
+                                | Keyword::Group
+                                | Keyword::Having
                                 | Keyword::Order
                                 | Keyword::Desc
                                 | Keyword::Asc
                                 | Keyword::Limit
-                                | Keyword::Offset,
+                                | Keyword::Offset
+                                | Keyword::Primary
+                                | Keyword::Nullable
+                                | Keyword::Unique
+                                | Keyword::Not,
                         ),
                 )
             } {
                 break;
             }
+            if token.kind == TokenKind::DoubleColon {
+                if CAST_BINDING_POWER < min_bp {
+                    break;
+                }
+                self.enter_nested_expression()?;
+                self.lexer.next();
+                let data_type = self.parse_column_type()?;
+                lhs = Expression::Cast((Box::new(lhs), data_type));
+                continue;
+            }
             let op = Op::try_from(*token)?;
             let (l_bp, r_bp) = op.infix_binding_power().ok_or(SQLError::new(
                 SQLErrorKind::InvalidOperator { op: token.kind },
@@ -283,8 +559,15 @@ impl<'a> Parser<'a> {
             if l_bp < min_bp {
                 break;
             }
+            let op_offset = token.offset;
             self.lexer.next();
             let rhs = self.expr_bp(r_bp)?;
+            if op == Op::In && !matches!(rhs, Expression::Subquery(_)) {
+                return Err(SQLError::new(
+                    SQLErrorKind::ExpectedOther { expected: TokenKind::LeftParen },
+                    op_offset,
+                ));
+            }
             lhs = Expression::BinaryOp((Box::new(lhs), op, Box::new(rhs)));
         }
         Ok(lhs)
@@ -292,6 +575,12 @@ impl<'a> Parser<'a> {
 
     fn parse_aggregate_function(&mut self, agg: Aggregate) -> Result<Expression<'a>, SQLError<'a>> {
         self.lexer.expect_token(TokenKind::LeftParen)?;
+        let distinct = self.lexer.next_if_keyword(Keyword::Distinct);
+        if distinct
+            && let Some(Ok(Token { kind: TokenKind::Asterisk, offset, .. })) = self.lexer.peek()
+        {
+            return Err(SQLError::new(SQLErrorKind::DistinctWildcard, *offset));
+        }
         let expr = self.expr_bp(0)?;
         self.lexer.expect_token(TokenKind::RightParen)?;
         let kind = match agg {
@@ -302,7 +591,11 @@ impl<'a> Parser<'a> {
             Aggregate::Min => AggregateFunctionKind::Min,
             Aggregate::Max => AggregateFunctionKind::Max,
         };
-        Ok(Expression::AggregateFunction(AggregateFunction { kind, expr: Box::new(expr) }))
+        Ok(Expression::AggregateFunction(AggregateFunction {
+            kind,
+            distinct,
+            expr: Box::new(expr),
+        }))
     }
 }
 
@@ -312,8 +605,226 @@ mod parser_tests {
     use crate::sql_parser::{
         error::{SQLError, SQLErrorKind},
         lexer::token_kind::TokenKind,
+        parser::stmt::create_table::ColumnType,
     };
 
+    #[test]
+    fn test_peek_statement_kind_does_not_consume_input() {
+        let s = "  INSERT INTO t (a) VALUES (1); UPDATE t SET a = 2;";
+        let mut parser = Parser::new(s);
+
+        assert_eq!(parser.peek_statement_kind(), Ok(StatementKind::Insert));
+        assert_eq!(parser.peek_statement_kind(), Ok(StatementKind::Insert));
+        assert!(matches!(parser.stmt(), Ok(Statement::Insert(_))));
+
+        assert_eq!(parser.peek_statement_kind(), Ok(StatementKind::Update));
+        assert!(matches!(parser.stmt(), Ok(Statement::Update(_))));
+
+        assert_eq!(
+            parser.peek_statement_kind(),
+            Err(SQLError::new(SQLErrorKind::UnexpectedEnd, s.len()))
+        );
+    }
+
+    #[test]
+    fn test_peek_statement_kind_rejects_non_statement_token() {
+        let s = "foo;";
+        let mut parser = Parser::new(s);
+
+        let expected = SQLError::new(SQLErrorKind::Other(TokenKind::Identifier("foo")), 0);
+        assert_eq!(parser.peek_statement_kind(), Err(expected));
+        assert_eq!(parser.stmt(), Err(expected));
+    }
+
+    #[test]
+    fn test_deeply_nested_parens_are_rejected() {
+        let depth = 100_000;
+        let s = format!("SELECT {}1{};", "(".repeat(depth), ")".repeat(depth));
+        let mut parser = Parser::new(&s);
+
+        let err = parser.stmt().unwrap_err();
+        assert_eq!(err.kind, SQLErrorKind::ExpressionTooDeep { limit: DEFAULT_MAX_DEPTH });
+
+        let s = format!("SELECT a{};", "::INT".repeat(depth));
+        let err = Parser::new(&s).stmt().unwrap_err();
+        assert_eq!(err.kind, SQLErrorKind::ExpressionTooDeep { limit: DEFAULT_MAX_DEPTH });
+    }
+
+    #[test]
+    fn test_max_depth_is_configurable() {
+        let s = "SELECT ((1));";
+        assert!(Parser::with_max_depth(s, 3).stmt().is_ok());
+
+        let err = Parser::with_max_depth(s, 2).stmt().unwrap_err();
+        assert_eq!(err, SQLError::new(SQLErrorKind::ExpressionTooDeep { limit: 2 }, 9));
+
+        let err = Parser::with_max_depth("SELECT NOT NOT true;", 2).stmt().unwrap_err();
+        assert_eq!(err.kind, SQLErrorKind::ExpressionTooDeep { limit: 2 });
+
+        assert!(Parser::with_max_depth("SELECT a::INT;", 2).stmt().is_ok());
+        let err = Parser::with_max_depth("SELECT a::INT::TEXT;", 2).stmt().unwrap_err();
+        assert_eq!(err, SQLError::new(SQLErrorKind::ExpressionTooDeep { limit: 2 }, 13));
+    }
+
+    #[test]
+    fn test_node_count_of_simple_query() {
+        let stmt = Parser::new("SELECT id FROM users WHERE id == 1;").stmt().unwrap();
+        assert_eq!(stmt.node_count(), 7);
+
+        let stmt = Parser::new("INSERT INTO users (id, name) VALUES (1, 'a'), (2, 'b');").stmt();
+        assert_eq!(stmt.unwrap().node_count(), 8);
+    }
+
+    #[test]
+    fn test_node_count_of_nested_query() {
+        let stmt = Parser::new("SELECT (SELECT (SELECT NOT NOT true));").stmt().unwrap();
+        assert_eq!(stmt.node_count(), 9);
+
+        let stmt = Parser::new("EXPLAIN SELECT ((((1))));").stmt().unwrap();
+        assert_eq!(stmt.node_count(), 4);
+    }
+
+    #[test]
+    fn test_statement_exceeding_node_budget_is_rejected() {
+        let s = "SELECT a + b + c FROM t;";
+        assert!(Parser::new(s).with_max_nodes(8).stmt().is_ok());
+
+        let err = Parser::new(s).with_max_nodes(7).stmt().unwrap_err();
+        assert_eq!(
+            err,
+            SQLError::spanned(SQLErrorKind::StatementTooLarge { limit: 7 }, 0, s.len())
+        );
+    }
+
+    #[test]
+    fn test_parser_config_applies_every_option() {
+        let config = ParserConfig { max_depth: 2, max_nodes: Some(7) };
+        assert!(Parser::with_config("SELECT (a) FROM t;", config).stmt().is_ok());
+
+        let err = Parser::with_config("SELECT ((a)) FROM t;", config).stmt().unwrap_err();
+        assert_eq!(err.kind, SQLErrorKind::ExpressionTooDeep { limit: 2 });
+
+        let err = Parser::with_config("SELECT a + b + c FROM t;", config).stmt().unwrap_err();
+        assert_eq!(err.kind, SQLErrorKind::StatementTooLarge { limit: 7 });
+
+        // Spans end at the last consumed token, not after trailing input looked at by a peek.
+        let s = "SELECT a + b + c FROM t  -- done";
+        let err = Parser::with_config(s, config).parse_single_statement_allow_eof().unwrap_err();
+        let end = "SELECT a + b + c FROM t".len();
+        assert_eq!(err, SQLError::spanned(SQLErrorKind::StatementTooLarge { limit: 7 }, 0, end));
+
+        let err = Parser::with_max_depth("SELECT NOT   ", 1).stmt().unwrap_err();
+        let expected =
+            SQLError::new(SQLErrorKind::ExpressionTooDeep { limit: 1 }, "SELECT NOT".len());
+        assert_eq!(err, expected);
+
+        let default = ParserConfig::default();
+        assert_eq!(default, ParserConfig { max_depth: DEFAULT_MAX_DEPTH, max_nodes: None });
+        assert!(Parser::with_config("SELECT ((((a + b + c)))) FROM t;", default).stmt().is_ok());
+    }
+
+    #[test]
+    fn test_parse_predicate() {
+        let expected = Expression::BinaryOp((
+            Box::new(Expression::BinaryOp((
+                Box::new(Expression::Identifier("a")),
+                Op::GreaterThan,
+                Box::new(Expression::from(1)),
+            ))),
+            Op::And,
+            Box::new(Expression::BinaryOp((
+                Box::new(Expression::Identifier("b")),
+                Op::LessThan,
+                Box::new(Expression::from(2)),
+            ))),
+        ));
+        assert_eq!(parse_predicate("a > 1 AND b < 2"), Ok(expected));
+        assert!(parse_predicate("NOT active").is_ok());
+    }
+
+    #[test]
+    fn test_parse_predicate_rejects_trailing_tokens() {
+        let s = "a > 1 b";
+        let expected =
+            SQLError::new(SQLErrorKind::InvalidOperator { op: TokenKind::Identifier("b") }, 6);
+        assert_eq!(parse_predicate(s), Err(expected));
+
+        let s = "a > 1;";
+        let expected = SQLError::new(SQLErrorKind::Other(TokenKind::Semicolon), 5);
+        assert_eq!(parse_predicate(s), Err(expected));
+    }
+
+    #[test]
+    fn test_parse_expression_complete() {
+        assert_eq!(parse_expression_complete(" (a + 1) * 2 "), Parser::new("(a + 1) * 2").expr());
+
+        let s = "1 + 2 )";
+        assert!(Parser::new(s).expr().is_ok());
+        let expected = SQLError::new(SQLErrorKind::Other(TokenKind::RightParen), 6);
+        assert_eq!(parse_expression_complete(s), Err(expected));
+
+        let expected = SQLError::new(SQLErrorKind::Other(TokenKind::Comma), 1);
+        assert_eq!(parse_expression_complete("a, b"), Err(expected));
+    }
+
+    #[test]
+    fn test_parse_predicate_rejects_non_predicates() {
+        let s = " active ";
+        let expected = SQLError::new(SQLErrorKind::ExpectedPredicate, 1);
+        assert_eq!(parse_predicate(s), Err(expected));
+
+        assert_eq!(
+            parse_predicate("a + 1").map_err(|err| err.kind),
+            Err(SQLErrorKind::ExpectedPredicate)
+        );
+    }
+
+    #[test]
+    fn test_spanned_stmt_slices_back_to_source() {
+        let s = "SELECT a FROM t;\n  -- comment\n  DELETE FROM u WHERE id == 1;  ";
+        let mut parser = Parser::new(s);
+
+        let first = parser.spanned_stmt().unwrap();
+        assert_eq!(&s[first.span.clone()], "SELECT a FROM t;");
+        assert!(matches!(first.node, Statement::Select(_)));
+
+        let second = parser.spanned_stmt().unwrap();
+        assert_eq!(&s[second.span.clone()], "DELETE FROM u WHERE id == 1;");
+        assert!(matches!(second.node, Statement::Delete(_)));
+    }
+
+    #[test]
+    fn test_parse_script_recovers_after_malformed_statement() {
+        let s = "SELECT a FROM t; SELECT FROM t; SELECT b FROM u;";
+        let (items, errors) = Parser::new(s).parse_script();
+
+        let displayed = items.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(displayed, ["SELECT a FROM t;", "SELECT b FROM u;"]);
+        assert_eq!(
+            errors,
+            [SQLError::new(SQLErrorKind::Other(TokenKind::Keyword(Keyword::From)), 24)]
+        );
+    }
+
+    #[test]
+    fn test_parse_script_does_not_skip_past_consumed_semicolon() {
+        let s = "SELECT a FROM t; DELETE FROM; BEGIN; SELECT b FROM u;";
+        let (items, errors) = Parser::new(s).parse_script();
+
+        let displayed = items.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(displayed, ["SELECT a FROM t;", "BEGIN;", "SELECT b FROM u;"]);
+        assert_eq!(errors.len(), 1);
+    }
+
+    #[test]
+    fn test_parse_script_reports_unterminated_final_statement() {
+        let s = "SELECT a FROM t; SELECT b FROM u";
+        let (items, errors) = Parser::new(s).parse_script();
+
+        assert_eq!(items.len(), 1);
+        assert_eq!(errors, [SQLError::new(SQLErrorKind::ExpectedCommaOrSemicolon, s.len())]);
+    }
+
     #[test]
     fn test_parse_plus_exp() {
         let s = "12 + 34";
@@ -340,6 +851,55 @@ mod parser_tests {
         assert_eq!(Ok(expected), parser.expr())
     }
 
+    #[test]
+    fn test_string_literals_unescape_doubled_quotes() {
+        let string = |value: &str| Expression::Literal(Literal::String(value.to_owned().into()));
+        assert_eq!(Parser::new("'it''s ok'").expr(), Ok(string("it's ok")));
+        assert_eq!(Parser::new("''").expr(), Ok(string("")));
+
+        let expr = Parser::new(r#"'say "hi"' == 'it''s'"#).expr().unwrap();
+        assert_eq!(expr.to_string(), r#"'say "hi"' == 'it''s'"#);
+        assert_eq!(Parser::new(&expr.to_string()).expr(), Ok(expr));
+    }
+
+    #[test]
+    fn test_angle_bracket_not_equals() {
+        let expected = Parser::new("a != b AND c != 1").expr().unwrap();
+        assert_eq!(Parser::new("a <> b AND c<>1").expr(), Ok(expected));
+    }
+
+    #[test]
+    fn test_pow_is_right_associative() {
+        let pow = |a, b| Expression::BinaryOp((Box::new(a), Op::Pow, Box::new(b)));
+        let expected = pow(Expression::from(2), pow(Expression::from(3), Expression::from(2)));
+        assert_eq!(expected.to_string(), "2 ^ 3 ^ 2");
+        assert_eq!(Parser::new("2 ^ 3 ^ 2").expr(), Ok(expected));
+
+        let grouped = pow(pow(Expression::from(2), Expression::from(3)), Expression::from(2));
+        assert_eq!(grouped.to_string(), "(2 ^ 3) ^ 2");
+        assert_eq!(Parser::new("(2 ^ 3) ^ 2").expr(), Ok(grouped));
+    }
+
+    #[test]
+    fn test_pow_binds_tighter_than_mul_and_looser_than_prefix_minus() {
+        let pow = |a, b| Expression::BinaryOp((Box::new(a), Op::Pow, Box::new(b)));
+        let expected = Expression::BinaryOp((
+            Box::new(Expression::from(2)),
+            Op::Mul,
+            Box::new(pow(Expression::Identifier("x"), Expression::from(3))),
+        ));
+        assert_eq!(Parser::new("2 * x ^ 3").expr(), Ok(expected));
+
+        let expected =
+            Expression::UnaryOp((Op::Sub, Box::new(pow(Expression::from(2), Expression::from(2)))));
+        assert_eq!(Parser::new("-2 ^ 2").expr(), Ok(expected));
+
+        for s in ["(-2) ^ 2", "-(2 ^ 2)", "a ^ b * c", "a ^ (b * c)"] {
+            let expr = Parser::new(s).expr().unwrap();
+            assert_eq!(Parser::new(&expr.to_string()).expr(), Ok(expr));
+        }
+    }
+
     #[test]
     fn test_parse_mul_and_plus_exp_with_parens() {
         let s = "12 + (34 * 56)";
@@ -395,6 +955,186 @@ mod parser_tests {
         assert_eq!(Ok(expected), parser.expr());
     }
 
+    #[test]
+    fn test_explain_wraps_inner_statement() {
+        let s = "EXPLAIN SELECT 1;";
+        let got = Parser::new(s).stmt().unwrap();
+        let Statement::Explain(inner) = &got else {
+            panic!("expected EXPLAIN, got {got:?}");
+        };
+        assert!(matches!(**inner, Statement::Select(_)));
+        assert_eq!(got.to_string(), s);
+
+        let s = "EXPLAIN EXPLAIN DELETE FROM t;";
+        let got = Parser::new(s).stmt().unwrap();
+        let Statement::Explain(inner) = &got else {
+            panic!("expected EXPLAIN, got {got:?}");
+        };
+        assert!(
+            matches!(&**inner, Statement::Explain(inner) if matches!(**inner, Statement::Delete(_)))
+        );
+        assert_eq!(got.to_string(), s);
+    }
+
+    #[test]
+    fn test_explain_requires_a_statement() {
+        let expected = Err(SQLError::new(SQLErrorKind::UnexpectedEnd, "EXPLAIN".len()));
+        assert_eq!(Parser::new("EXPLAIN;").stmt(), expected);
+        assert_eq!(Parser::new("EXPLAIN").stmt(), expected);
+        assert_eq!(
+            Parser::new("EXPLAIN EXPLAIN ;").stmt(),
+            Err(SQLError::new(SQLErrorKind::UnexpectedEnd, 16))
+        );
+
+        let s = "EXPLAIN; SELECT 1;";
+        let (items, errors) = Parser::new(s).parse_script();
+        let displayed = items.iter().map(ToString::to_string).collect::<Vec<_>>();
+        assert_eq!(displayed, ["SELECT 1;"]);
+        assert_eq!(errors, [SQLError::new(SQLErrorKind::UnexpectedEnd, "EXPLAIN".len())]);
+        assert!(errors[0].is_recoverable(s));
+    }
+
+    #[test]
+    fn test_single_statement_may_omit_semicolon() {
+        let expected = Parser::new("SELECT 1;").stmt();
+        assert_eq!(Parser::new("SELECT 1").parse_single_statement_allow_eof(), expected);
+        assert_eq!(Parser::new("SELECT 1;").parse_single_statement_allow_eof(), expected);
+
+        let s = "EXPLAIN WITH a AS (SELECT x FROM t) DELETE FROM a WHERE x > 1  ";
+        let got = Parser::new(s).parse_single_statement_allow_eof().unwrap();
+        assert_eq!(got.to_string(), format!("{};", s.trim_end()));
+
+        let s = "SELECT 1; SELECT 2;";
+        let expected = SQLError::new(SQLErrorKind::Other(TokenKind::Keyword(Keyword::Select)), 10);
+        assert_eq!(Parser::new(s).parse_single_statement_allow_eof(), Err(expected));
+    }
+
+    #[test]
+    fn test_batch_parsing_still_requires_semicolons() {
+        let s = "SELECT 1";
+        let expected = SQLError::new(SQLErrorKind::ExpectedCommaOrSemicolon, s.len());
+        assert_eq!(Parser::new(s).stmt(), Err(expected));
+        assert_eq!(Parser::new(s).collect::<Vec<_>>(), [Err(expected)]);
+
+        let s = "DELETE FROM t DELETE FROM u;";
+        let expected = SQLError::new(
+            SQLErrorKind::UnexpectedTokenKind {
+                expected: TokenKind::Semicolon,
+                got: TokenKind::Keyword(Keyword::Delete),
+            },
+            14,
+        );
+        assert_eq!(Parser::new(s).next(), Some(Err(expected)));
+    }
+
+    #[test]
+    fn test_parse_cast() {
+        let expected = Expression::Cast((Box::new(Expression::Identifier("a")), ColumnType::Float));
+        assert_eq!(Parser::new("a::FLOAT").expr(), Ok(expected));
+
+        let expr = Parser::new("-a::INT + b::VARCHAR(8) * 2").expr().unwrap();
+        let expected = Expression::BinaryOp((
+            Box::new(Expression::UnaryOp((
+                Op::Sub,
+                Box::new(Expression::Cast((
+                    Box::new(Expression::Identifier("a")),
+                    ColumnType::Int,
+                ))),
+            ))),
+            Op::Add,
+            Box::new(Expression::BinaryOp((
+                Box::new(Expression::Cast((
+                    Box::new(Expression::Identifier("b")),
+                    ColumnType::Varchar(Some(8)),
+                ))),
+                Op::Mul,
+                Box::new(Expression::from(2)),
+            ))),
+        ));
+        assert_eq!(expr, expected);
+        assert_eq!(expr.to_string(), "-a::INT + b::VARCHAR(8) * 2");
+
+        let expr = Parser::new("(a + 1)::TEXT").expr().unwrap();
+        assert_eq!(expr.to_string(), "(a + 1)::TEXT");
+        assert_eq!(Parser::new(&expr.to_string()).expr(), Ok(expr));
+
+        let expr = Expression::Cast((Box::new(Expression::from(-1)), ColumnType::Int));
+        let displayed = expr.to_string();
+        assert_eq!(displayed, "(-1)::INT");
+        let reparsed = Parser::new(&displayed).expr().unwrap();
+        assert!(matches!(reparsed, Expression::Cast(_)));
+        assert_eq!(reparsed.to_string(), displayed);
+
+        let s = "a::b";
+        let expected =
+            SQLError::new(SQLErrorKind::InvalidDataType { got: TokenKind::Identifier("b") }, 3);
+        assert_eq!(Parser::new(s).expr(), Err(expected));
+    }
+
+    #[test]
+    fn test_unexpected_end_points_after_last_token() {
+        for s in
+            ["SELECT a,", "SELECT a,  \n\t", "SELECT a, -- trailing comment", "SELECT a, /* x */ "]
+        {
+            assert_eq!(
+                Parser::new(s).stmt(),
+                Err(SQLError::new(SQLErrorKind::ExpectedExpression, "SELECT a,".len())),
+                "wrong position for {s:?}"
+            );
+        }
+
+        let s = "CREATE TABLE t (a INT,\n  b  ";
+        assert_eq!(Parser::new(s).stmt(), Err(SQLError::new(SQLErrorKind::UnexpectedEnd, 26)));
+        assert_eq!(Parser::new("  ").stmt(), Err(SQLError::new(SQLErrorKind::UnexpectedEnd, 0)));
+        assert_eq!(Parser::new("1 +  ").expr(), Err(SQLError::new(SQLErrorKind::UnexpectedEnd, 3)));
+    }
+
+    #[test]
+    fn test_identifier_errors_span_the_offending_token() {
+        let s = "SELECT a FROM\n  42;";
+        let expected = SQLError::spanned(
+            SQLErrorKind::ExpectedIdentifier { got: TokenKind::Number(NumberKind::Integer(42)) },
+            16,
+            18,
+        );
+        assert_eq!(Parser::new(s).stmt(), Err(expected));
+
+        let s = "UPDATE  Select SET a = 1;";
+        let expected = SQLError::spanned(
+            SQLErrorKind::ReservedKeywordAsIdentifier { keyword: "Select" },
+            8,
+            14,
+        );
+        assert_eq!(Parser::new(s).stmt(), Err(expected));
+    }
+
+    #[test]
+    fn test_unary_plus() {
+        let plus = |expr| Expression::UnaryOp((Op::Add, Box::new(expr)));
+        let minus = |expr| Expression::UnaryOp((Op::Sub, Box::new(expr)));
+
+        let cases = [
+            ("+5", plus(Expression::from(5))),
+            ("+x", plus(Expression::Identifier("x"))),
+            ("+-5", plus(minus(Expression::from(5)))),
+            ("-+5", minus(plus(Expression::from(5)))),
+        ];
+        for (s, expected) in cases {
+            let expr = Parser::new(s).expr().unwrap();
+            assert_eq!(expr, expected);
+            assert_eq!(expr.to_string(), s);
+        }
+
+        let expr = Parser::new("+a * b").expr().unwrap();
+        let expected = Expression::BinaryOp((
+            Box::new(plus(Expression::Identifier("a"))),
+            Op::Mul,
+            Box::new(Expression::Identifier("b")),
+        ));
+        assert_eq!(expr, expected);
+        assert_eq!(Parser::new("1 + +2").expr().unwrap().to_string(), "1 + +2");
+    }
+
     #[test]
     fn test_invalid_operator() {
         let s = "operand invalid_operator";
@@ -453,16 +1193,16 @@ mod parser_tests {
         let mut parser = Parser::new("-123");
         assert_eq!(
             parser.parse_non_negative_integer(),
-            Err(SQLError { kind: SQLErrorKind::ExpectedNonNegativeInteger { got: -123 }, pos: 0 })
+            Err(SQLError::new(SQLErrorKind::ExpectedNonNegativeInteger { got: -123 }, 0))
         );
 
         let mut parser = Parser::new("abc");
         assert_eq!(
             parser.parse_non_negative_integer(),
-            Err(SQLError {
-                kind: SQLErrorKind::ExpectedInteger { got: TokenKind::Identifier("abc") },
-                pos: 0
-            })
+            Err(SQLError::new(
+                SQLErrorKind::ExpectedInteger { got: TokenKind::Identifier("abc") },
+                0
+            ))
         );
     }
 }
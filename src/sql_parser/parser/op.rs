@@ -12,10 +12,12 @@ impl<'a> TryFrom<Token<'a>> for Op {
             TokenKind::Keyword(Keyword::And) => Op::And,
             TokenKind::Keyword(Keyword::Or) => Op::Or,
             TokenKind::Keyword(Keyword::Not) => Op::Not,
+            TokenKind::Keyword(Keyword::In) => Op::In,
             TokenKind::Plus => Op::Add,
             TokenKind::Minus => Op::Sub,
             TokenKind::Asterisk => Op::Mul,
             TokenKind::Slash => Op::Div,
+            TokenKind::Caret => Op::Pow,
             TokenKind::EqualsEquals => Op::EqualsEquals,
             TokenKind::NotEquals => Op::NotEquals,
             TokenKind::LessThan => Op::LessThan,
@@ -34,6 +36,7 @@ impl<'a> TryFrom<Token<'a>> for Op {
 }
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Op {
     And,
     Or,
@@ -43,11 +46,13 @@ pub enum Op {
     GreaterThan,
     LessThanOrEqual,
     GreaterThanOrEqual,
+    In,
     Not,
     Add,
     Sub,
     Mul,
     Div,
+    Pow,
 }
 
 impl Display for Op {
@@ -60,12 +65,14 @@ impl Display for Op {
             Op::Sub => write!(f, "-"),
             Op::Mul => write!(f, "*"),
             Op::Div => write!(f, "/"),
+            Op::Pow => write!(f, "^"),
             Op::NotEquals => write!(f, "!="),
             Op::EqualsEquals => write!(f, "=="),
             Op::LessThan => write!(f, "<"),
             Op::GreaterThan => write!(f, ">"),
             Op::LessThanOrEqual => write!(f, "<="),
             Op::GreaterThanOrEqual => write!(f, ">="),
+            Op::In => write!(f, "IN"),
         }
     }
 }
@@ -73,7 +80,7 @@ impl Display for Op {
 impl Op {
     pub fn prefix_binding_power(&self) -> Option<((), u8)> {
         let res = match self {
-            Op::Not | Op::Sub => ((), 7),
+            Op::Not | Op::Add | Op::Sub => ((), 7),
             _ => return None,
         };
         Some(res)
@@ -87,9 +94,12 @@ impl Op {
             | Op::LessThan
             | Op::GreaterThan
             | Op::LessThanOrEqual
-            | Op::GreaterThanOrEqual => (3, 4),
+            | Op::GreaterThanOrEqual
+            | Op::In => (3, 4),
             Op::Add | Op::Sub => (5, 6),
             Op::Mul | Op::Div => (6, 7),
+            // The right power is lower than the left, so `2 ^ 3 ^ 2` is `2 ^ (3 ^ 2)`.
+            Op::Pow => (8, 7),
             _ => return None,
         };
         Some(res)
@@ -0,0 +1,296 @@
+use std::fmt::{Display, Write};
+
+use crate::sql_parser::parser::{
+    expr::{AggregateFunction, Expression},
+    ident::quote_ident,
+    stmt::{
+        Statement,
+        insert::{InsertSource, Values},
+        lists::ExpressionList,
+        select::SelectQuery,
+    },
+};
+
+/// How [`Dump`] labels expression nodes.
+#[derive(Clone, Copy, PartialEq)]
+enum Labels {
+    /// The AST variant name, as in `BinaryOp Add`, with subqueries expanded.
+    Variant,
+    /// The node's SQL spelling, as in `+`, with subqueries kept on one line.
+    Sql,
+}
+
+/// Accumulates one labelled node per line, two spaces deeper per level.
+struct Dump {
+    out: String,
+    labels: Labels,
+}
+
+impl Dump {
+    fn line(&mut self, depth: usize, label: impl Display) {
+        let _ = writeln!(self.out, "{:width$}{label}", "", width = depth * 2);
+    }
+
+    fn expr(&mut self, depth: usize, expr: &Expression<'_>) {
+        self.line(depth, self.expr_label(expr));
+        match expr {
+            Expression::UnaryOp((_, operand)) | Expression::Cast((operand, _)) => {
+                self.expr(depth + 1, operand);
+            }
+            Expression::BinaryOp((left, _, right)) => {
+                self.expr(depth + 1, left);
+                self.expr(depth + 1, right);
+            }
+            Expression::AggregateFunction(function) => self.expr(depth + 1, &function.expr),
+            Expression::Subquery(query) if self.labels == Labels::Variant => {
+                self.select(depth + 1, query);
+            }
+            Expression::Literal(_)
+            | Expression::Identifier(_)
+            | Expression::Wildcard
+            | Expression::Subquery(_) => {}
+        }
+    }
+
+    fn expr_label(&self, expr: &Expression<'_>) -> String {
+        let distinct = |function: &AggregateFunction<'_>| {
+            if function.distinct { " DISTINCT" } else { "" }
+        };
+        match (self.labels, expr) {
+            (Labels::Variant, Expression::Literal(literal)) => format!("Literal {literal}"),
+            (Labels::Variant, Expression::Identifier(name)) => {
+                format!("Identifier {}", quote_ident(name))
+            }
+            (Labels::Variant, Expression::UnaryOp((op, _))) => format!("UnaryOp {op:?}"),
+            (Labels::Variant, Expression::BinaryOp((_, op, _))) => format!("BinaryOp {op:?}"),
+            (Labels::Variant, Expression::Wildcard) => "Wildcard".to_owned(),
+            (Labels::Variant, Expression::AggregateFunction(function)) => {
+                format!("AggregateFunction {:?}{}", function.kind, distinct(function))
+            }
+            (Labels::Variant, Expression::Subquery(_)) => "Subquery".to_owned(),
+            (Labels::Variant, Expression::Cast((_, data_type))) => format!("Cast {data_type}"),
+            (Labels::Sql, Expression::UnaryOp((op, _))) => op.to_string().trim_end().to_owned(),
+            (Labels::Sql, Expression::BinaryOp((_, op, _))) => op.to_string(),
+            (Labels::Sql, Expression::AggregateFunction(function)) => {
+                format!("{}{}", function.kind, distinct(function))
+            }
+            (Labels::Sql, Expression::Cast((_, data_type))) => format!("::{data_type}"),
+            (Labels::Sql, leaf) => leaf.to_string(),
+        }
+    }
+
+    fn exprs(&mut self, depth: usize, label: &str, list: &ExpressionList<'_>) {
+        self.line(depth, label);
+        for expr in &list.0 {
+            self.expr(depth + 1, expr);
+        }
+    }
+
+    fn clause(&mut self, depth: usize, label: &str, expr: &Option<Expression<'_>>) {
+        if let Some(expr) = expr {
+            self.line(depth, label);
+            self.expr(depth + 1, expr);
+        }
+    }
+
+    fn values(&mut self, depth: usize, values: &Values<'_>) {
+        self.line(depth, "Values");
+        for row in &values.0 {
+            self.exprs(depth + 1, "Row", row);
+        }
+    }
+
+    fn select(&mut self, depth: usize, query: &SelectQuery<'_>) {
+        self.line(depth, if query.distinct { "Select DISTINCT" } else { "Select" });
+        let depth = depth + 1;
+        self.exprs(depth, "Columns", &query.columns);
+        if let Some(table) = query.table {
+            self.line(depth, format_args!("From {}", quote_ident(table)));
+        }
+        self.clause(depth, "Where", &query.where_clause);
+        if let Some(group_by) = &query.group_by {
+            self.exprs(depth, "GroupBy", group_by);
+        }
+        self.clause(depth, "Having", &query.having);
+        if let Some(order_by) = &query.order_by {
+            self.line(depth, "OrderBy");
+            for term in &order_by.terms {
+                self.line(depth + 1, term);
+            }
+        }
+        if let Some(limit) = query.limit {
+            self.line(depth, format_args!("Limit {limit}"));
+        }
+        if let Some(offset) = query.offset {
+            self.line(depth, format_args!("Offset {offset}"));
+        }
+    }
+
+    fn statement(&mut self, depth: usize, statement: &Statement<'_>) {
+        match statement {
+            Statement::Explain(statement) => {
+                self.line(depth, "Explain");
+                self.statement(depth + 1, statement);
+            }
+            Statement::With(query) => {
+                self.line(depth, "With");
+                for cte in &query.ctes {
+                    self.line(depth + 1, format_args!("Cte {}", quote_ident(cte.name)));
+                    self.select(depth + 2, &cte.query);
+                }
+                self.statement(depth + 1, &query.statement);
+            }
+            Statement::Select(query) => self.select(depth, query),
+            Statement::Update(query) => {
+                self.line(depth, format_args!("Update {}", quote_ident(query.table)));
+                for assignment in &query.assignments.0 {
+                    self.line(depth + 1, format_args!("Set {}", quote_ident(assignment.column)));
+                    self.expr(depth + 2, &assignment.expression);
+                }
+                self.clause(depth + 1, "Where", &query.where_clause);
+            }
+            Statement::Delete(query) => {
+                self.line(depth, format_args!("Delete {}", quote_ident(query.table)));
+                self.clause(depth + 1, "Where", &query.where_clause);
+            }
+            Statement::Insert(query) => {
+                self.line(depth, format_args!("Insert {}", quote_ident(query.table)));
+                self.line(depth + 1, format_args!("Columns {}", query.columns));
+                match &query.source {
+                    InsertSource::Values(values) => self.values(depth + 1, values),
+                    InsertSource::Select(query) => self.select(depth + 1, query),
+                }
+            }
+            Statement::Values(values) => self.values(depth, values),
+            Statement::CreateTable(query) => {
+                self.line(depth, format_args!("CreateTable {}", quote_ident(query.table_name)));
+                for column in &query.columns {
+                    self.line(depth + 1, format_args!("Column {column}"));
+                }
+            }
+            Statement::CreateIndex(query) => {
+                self.line(depth, format_args!("CreateIndex {}", quote_ident(query.index_name)));
+                self.line(
+                    depth + 1,
+                    format_args!("On {} ({})", quote_ident(query.table_name), query.columns),
+                );
+            }
+        }
+    }
+}
+
+impl Expression<'_> {
+    /// Dumps the AST structure, one node per line labelled with its variant.
+    ///
+    /// Children sit two spaces deeper than their parent, starting `indent`
+    /// levels in. [`Expression::tree_string`] prints each node as SQL instead.
+    pub fn pretty(&self, indent: usize) -> String {
+        let mut dump = Dump { out: String::new(), labels: Labels::Variant };
+        dump.expr(indent, self);
+        dump.out
+    }
+
+    /// Renders the expression as an indented tree, one node per line.
+    ///
+    /// Operators and aggregates are printed above their operands, two spaces
+    /// deeper per level, starting `indent` levels in. Unlike [`Display`], the
+    /// output makes grouping explicit instead of relying on precedence.
+    pub fn tree_string(&self, indent: usize) -> String {
+        let mut dump = Dump { out: String::new(), labels: Labels::Sql };
+        dump.expr(indent, self);
+        dump.out
+    }
+}
+
+impl Statement<'_> {
+    /// Dumps the AST structure, one clause or node per line, as [`Expression::pretty`] does.
+    pub fn pretty(&self, indent: usize) -> String {
+        let mut dump = Dump { out: String::new(), labels: Labels::Variant };
+        dump.statement(indent, self);
+        dump.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::sql_parser::parser::Parser;
+
+    #[test]
+    fn test_pretty_expression() {
+        let expr = Parser::new("1 + 2 * 3").expr().unwrap();
+        let expected = "\
+BinaryOp Add
+  Literal 1
+  BinaryOp Mul
+    Literal 2
+    Literal 3
+";
+        assert_eq!(expr.pretty(0), expected);
+
+        let expr = Parser::new("-COUNT(DISTINCT x)::TEXT").expr().unwrap();
+        let expected = "  UnaryOp Sub\n    Cast TEXT\n      AggregateFunction Count DISTINCT\n        Identifier x\n";
+        assert_eq!(expr.pretty(1), expected);
+    }
+
+    #[test]
+    fn test_pretty_statement_with_nested_where() {
+        let s = "SELECT DISTINCT a, * FROM t \
+                 WHERE (a > 1 OR NOT b) AND c IN (SELECT d FROM u WHERE e == 'x') \
+                 ORDER BY a DESC LIMIT 5;";
+        let expected = "\
+Select DISTINCT
+  Columns
+    Identifier a
+    Wildcard
+  From t
+  Where
+    BinaryOp And
+      BinaryOp Or
+        BinaryOp GreaterThan
+          Identifier a
+          Literal 1
+        UnaryOp Not
+          Identifier b
+      BinaryOp In
+        Identifier c
+        Subquery
+          Select
+            Columns
+              Identifier d
+            From u
+            Where
+              BinaryOp EqualsEquals
+                Identifier e
+                Literal 'x'
+  OrderBy
+    a DESC
+  Limit 5
+";
+        assert_eq!(Parser::new(s).stmt().unwrap().pretty(0), expected);
+
+        let s = "EXPLAIN UPDATE t SET a = a + 1 WHERE b;";
+        let expected = "\
+Explain
+  Update t
+    Set a
+      BinaryOp Add
+        Identifier a
+        Literal 1
+    Where
+      Identifier b
+";
+        assert_eq!(Parser::new(s).stmt().unwrap().pretty(0), expected);
+    }
+
+    #[test]
+    fn test_pretty_quotes_identifiers() {
+        let s = r#"DELETE FROM "my table" WHERE "order" > 1;"#;
+        let expected = r#"Delete "my table"
+  Where
+    BinaryOp GreaterThan
+      Identifier "order"
+      Literal 1
+"#;
+        assert_eq!(Parser::new(s).stmt().unwrap().pretty(0), expected);
+    }
+}
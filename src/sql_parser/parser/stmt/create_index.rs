@@ -3,10 +3,11 @@ use std::fmt::Display;
 use crate::sql_parser::{
     error::SQLError,
     lexer::token_kind::{Keyword, TokenKind},
-    parser::{Parser, stmt::lists::IdentifierList},
+    parser::{Parser, ident::quote_ident, stmt::lists::IdentifierList},
 };
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CreateIndexQuery<'a> {
     pub index_name: &'a str,
     pub table_name: &'a str,
@@ -15,7 +16,13 @@ pub struct CreateIndexQuery<'a> {
 
 impl Display for CreateIndexQuery<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "CREATE INDEX {} ON {} ({});", self.index_name, self.table_name, self.columns)
+        write!(
+            f,
+            "CREATE INDEX {} ON {} ({});",
+            quote_ident(self.index_name),
+            quote_ident(self.table_name),
+            self.columns
+        )
     }
 }
 
@@ -28,7 +35,7 @@ impl<'a> Parser<'a> {
         self.lexer.expect_token(TokenKind::LeftParen)?;
         let columns = self.parse_identifier_list()?;
         self.lexer.expect_token(TokenKind::RightParen)?;
-        self.lexer.expect_token(TokenKind::Semicolon)?;
+        self.expect_statement_end()?;
 
         Ok(CreateIndexQuery { index_name, table_name, columns })
     }
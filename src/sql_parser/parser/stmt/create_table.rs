@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::fmt::Display;
 
 use crate::sql_parser::{
@@ -6,30 +7,46 @@ use crate::sql_parser::{
         token::Token,
         token_kind::{Keyword, TokenKind},
     },
-    parser::Parser,
+    parser::{Parser, expr::Expression, ident::quote_ident},
 };
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ColumnType {
     Int,
+    BigInt,
     Float,
-    Text,
+    /// `TEXT` with an optional declared length, which is not enforced.
+    Text(Option<u32>),
+    /// `VARCHAR` with an optional declared length, stored the same way as `TEXT`.
+    Varchar(Option<u32>),
+    Boolean,
+    Blob,
 }
 
 impl Display for ColumnType {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ColumnType::Int => write!(f, "INT"),
+            ColumnType::BigInt => write!(f, "BIGINT"),
             ColumnType::Float => write!(f, "FLOAT"),
-            ColumnType::Text => write!(f, "TEXT"),
+            ColumnType::Text(None) => write!(f, "TEXT"),
+            ColumnType::Text(Some(size)) => write!(f, "TEXT({size})"),
+            ColumnType::Varchar(None) => write!(f, "VARCHAR"),
+            ColumnType::Varchar(Some(size)) => write!(f, "VARCHAR({size})"),
+            ColumnType::Boolean => write!(f, "BOOLEAN"),
+            ColumnType::Blob => write!(f, "BLOB"),
         }
     }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum ColumnConstraint {
     PrimaryKey,
     Nullable,
+    Unique,
+    NotNull,
 }
 
 impl Display for ColumnConstraint {
@@ -37,20 +54,28 @@ impl Display for ColumnConstraint {
         match self {
             ColumnConstraint::PrimaryKey => write!(f, "PRIMARY KEY"),
             ColumnConstraint::Nullable => write!(f, "NULLABLE"),
+            ColumnConstraint::Unique => write!(f, "UNIQUE"),
+            ColumnConstraint::NotNull => write!(f, "NOT NULL"),
         }
     }
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Column<'a> {
     pub name: &'a str,
     pub column_type: ColumnType,
+    /// The value used when an `INSERT` leaves the column out.
+    pub default: Option<Expression<'a>>,
     pub constraints: Vec<ColumnConstraint>,
 }
 
 impl Display for Column<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} {}", self.name, self.column_type)?;
+        write!(f, "{} {}", quote_ident(self.name), self.column_type)?;
+        if let Some(default) = &self.default {
+            write!(f, " DEFAULT {default}")?;
+        }
         if let Some(constraint) = self.constraints.first() {
             write!(f, " {}", constraint)?;
             for constraint in self.constraints.iter().skip(1) {
@@ -62,6 +87,7 @@ impl Display for Column<'_> {
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct CreateTableQuery<'a> {
     pub table_name: &'a str,
     pub columns: Vec<Column<'a>>,
@@ -69,7 +95,7 @@ pub struct CreateTableQuery<'a> {
 
 impl Display for CreateTableQuery<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "CREATE TABLE {} (", self.table_name)?;
+        write!(f, "CREATE TABLE {} (", quote_ident(self.table_name))?;
 
         let mut column_iter = self.columns.iter();
         if let Some(first_col) = column_iter.next() {
@@ -83,70 +109,203 @@ impl Display for CreateTableQuery<'_> {
     }
 }
 
+/// Parses `source` as exactly one `CREATE TABLE` statement.
+///
+/// Any other statement kind, or any token after the terminating semicolon, is
+/// rejected.
+pub fn parse_create_table(source: &str) -> Result<CreateTableQuery<'_>, SQLError<'_>> {
+    let mut parser = Parser::new(source);
+    parser.lexer.expect_token(TokenKind::Keyword(Keyword::Create))?;
+    parser.lexer.expect_token(TokenKind::Keyword(Keyword::Table))?;
+    let query = parser.parse_create_table_query()?;
+    parser.expect_end()?;
+    Ok(query)
+}
+
+/// What may follow a column's type, in order, and the tokens that end the column.
+const COLUMN_SUFFIXES_AND_END: &[&str] =
+    &["DEFAULT", "PRIMARY KEY", "NOT NULL", "NULLABLE", "UNIQUE", "','", "')'"];
+
 impl<'a> Parser<'a> {
     pub fn parse_create_table_query(&mut self) -> Result<CreateTableQuery<'a>, SQLError<'a>> {
         let table_name = self.parse_identifier()?;
 
         self.lexer.expect_token(TokenKind::LeftParen)?;
 
-        let columns = self.parse_comma_separated_list(|p| p.parse_column_definition())?;
-        validate_primary_key(&columns, self.lexer.position)?;
+        let mut names = HashSet::new();
+        let mut offsets = Vec::new();
+        let columns = self.parse_comma_separated_list(|p| {
+            let offset = match p.lexer.peek() {
+                Some(Ok(token)) => token.offset,
+                _ => p.lexer.token_end,
+            };
+            offsets.push(offset);
+            let column = p.parse_column_definition()?;
+            if !names.insert(column.name) {
+                return Err(SQLError::spanned(
+                    SQLErrorKind::DuplicateColumn { name: column.name },
+                    offset,
+                    offset + column.name.len(),
+                ));
+            }
+            Ok(column)
+        })?;
+        let end = match self.lexer.peek() {
+            Some(Ok(token)) => token.offset,
+            _ => self.lexer.token_end,
+        };
+        validate_primary_key(&columns, &offsets, end)?;
 
         self.lexer.expect_token(TokenKind::RightParen)?;
-        self.lexer.expect_token(TokenKind::Semicolon)?;
+        self.expect_statement_end()?;
 
         Ok(CreateTableQuery { table_name, columns })
     }
 
     fn parse_column_definition(&mut self) -> Result<Column<'a>, SQLError<'a>> {
         let name = self.parse_identifier()?;
+        let column_type = self.parse_column_type()?;
 
-        let column_type = match self.lexer.next() {
-            Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Int), .. })) => ColumnType::Int,
-            Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Float), .. })) => ColumnType::Float,
-            Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Text), .. })) => ColumnType::Text,
-            Some(Ok(Token { kind, offset })) => {
-                return Err(SQLError::new(SQLErrorKind::InvalidDataType { got: kind }, offset));
-            }
-            Some(Err(e)) => return Err(e),
-            None => {
-                return Err(SQLError::new(SQLErrorKind::UnexpectedEnd, self.lexer.position));
-            }
+        let default = if self.lexer.next_if_keyword(Keyword::Default) {
+            Some(self.expr_bp(0)?)
+        } else {
+            None
         };
 
         let mut constraints = Vec::new();
         while let Some(Ok(token)) = self.lexer.peek() {
-            match &token.kind {
+            let offset = token.offset;
+            let constraint = match &token.kind {
                 TokenKind::Keyword(Keyword::Primary) => {
                     self.lexer.next();
                     self.lexer.expect_token(TokenKind::Keyword(Keyword::Key))?;
-                    constraints.push(ColumnConstraint::PrimaryKey);
+                    ColumnConstraint::PrimaryKey
                 }
                 TokenKind::Keyword(Keyword::Nullable) => {
                     self.lexer.next();
-                    constraints.push(ColumnConstraint::Nullable);
+                    ColumnConstraint::Nullable
+                }
+                TokenKind::Keyword(Keyword::Unique) => {
+                    self.lexer.next();
+                    ColumnConstraint::Unique
+                }
+                TokenKind::Keyword(Keyword::Not) => {
+                    self.lexer.next();
+                    self.lexer.expect_token(TokenKind::Keyword(Keyword::Null))?;
+                    ColumnConstraint::NotNull
                 }
                 _ => break,
+            };
+            if constraints.contains(&constraint) {
+                return Err(SQLError::spanned(
+                    SQLErrorKind::DuplicateConstraint { column: name, constraint },
+                    offset,
+                    self.lexer.token_end,
+                ));
+            }
+            let conflicts_with = match constraint {
+                ColumnConstraint::Nullable => Some(ColumnConstraint::NotNull),
+                ColumnConstraint::NotNull => Some(ColumnConstraint::Nullable),
+                ColumnConstraint::PrimaryKey | ColumnConstraint::Unique => None,
+            };
+            if let Some(conflicts_with) = conflicts_with.filter(|c| constraints.contains(c)) {
+                return Err(SQLError::spanned(
+                    SQLErrorKind::ConflictingConstraint {
+                        column: name,
+                        constraint,
+                        conflicts_with,
+                    },
+                    offset,
+                    self.lexer.token_end,
+                ));
             }
+            constraints.push(constraint);
+        }
+
+        if let Some(Ok(token)) = self.lexer.peek()
+            && !matches!(token.kind, TokenKind::Comma | TokenKind::RightParen)
+        {
+            // `DEFAULT` is only accepted before the first constraint.
+            let next = usize::from(default.is_some() || !constraints.is_empty());
+            return Err(SQLError::new(
+                SQLErrorKind::ExpectedOneOf {
+                    expected: &COLUMN_SUFFIXES_AND_END[next..],
+                    got: token.kind,
+                },
+                token.offset,
+            ));
         }
 
-        Ok(Column { name, column_type, constraints })
+        Ok(Column { name, column_type, default, constraints })
+    }
+
+    /// Parses a column type such as `INT` or `VARCHAR(32)`.
+    pub(crate) fn parse_column_type(&mut self) -> Result<ColumnType, SQLError<'a>> {
+        let column_type = match self.lexer.next() {
+            Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Int), .. })) => ColumnType::Int,
+            Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Float), .. })) => ColumnType::Float,
+            Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Text), .. })) => {
+                ColumnType::Text(self.parse_type_size()?)
+            }
+            Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Varchar), .. })) => {
+                ColumnType::Varchar(self.parse_type_size()?)
+            }
+            Some(Ok(Token { kind: TokenKind::Keyword(Keyword::BigInt), .. })) => ColumnType::BigInt,
+            Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Boolean), .. })) => {
+                ColumnType::Boolean
+            }
+            Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Blob), .. })) => ColumnType::Blob,
+            Some(Ok(Token { kind, offset, .. })) => {
+                return Err(SQLError::new(SQLErrorKind::InvalidDataType { got: kind }, offset));
+            }
+            Some(Err(e)) => return Err(e),
+            None => {
+                return Err(self.lexer.unexpected_end());
+            }
+        };
+        Ok(column_type)
+    }
+
+    /// Parses the optional `(n)` length that may follow a text type.
+    fn parse_type_size(&mut self) -> Result<Option<u32>, SQLError<'a>> {
+        if !matches!(self.lexer.peek(), Some(Ok(Token { kind: TokenKind::LeftParen, .. }))) {
+            return Ok(None);
+        }
+        self.lexer.next();
+        let size = self.parse_non_negative_integer()?;
+        self.lexer.expect_token(TokenKind::RightParen)?;
+        Ok(size)
     }
 }
 
-fn validate_primary_key<'a>(columns: &[Column<'a>], pos: usize) -> Result<(), SQLError<'a>> {
+/// Checks the table has a single `INT` primary key as its first column.
+///
+/// Errors about a particular column are placed at its entry in `offsets`;
+/// a missing primary key is reported at `end`, the end of the column list.
+fn validate_primary_key<'a>(
+    columns: &[Column<'a>],
+    offsets: &[usize],
+    end: usize,
+) -> Result<(), SQLError<'a>> {
     let primary_keys: Vec<_> = columns
         .iter()
         .enumerate()
         .filter(|(_, column)| column.constraints.contains(&ColumnConstraint::PrimaryKey))
         .collect();
 
-    if primary_keys.len() != 1 {
+    if let [_, (second, _), ..] = primary_keys[..] {
+        return Err(SQLError::new(
+            SQLErrorKind::MultiplePrimaryKeys { count: primary_keys.len() },
+            offsets[second],
+        ));
+    }
+
+    if primary_keys.is_empty() {
         return Err(SQLError::new(
             SQLErrorKind::InvalidPrimaryKey {
                 reason: "tables must declare exactly one primary key",
             },
-            pos,
+            end,
         ));
     }
 
@@ -154,21 +313,21 @@ fn validate_primary_key<'a>(columns: &[Column<'a>], pos: usize) -> Result<(), SQ
     if ordinal != 0 {
         return Err(SQLError::new(
             SQLErrorKind::InvalidPrimaryKey { reason: "primary key must be the first column" },
-            pos,
+            offsets[ordinal],
         ));
     }
 
     if column.column_type != ColumnType::Int {
         return Err(SQLError::new(
             SQLErrorKind::InvalidPrimaryKey { reason: "primary key must use INT type" },
-            pos,
+            offsets[ordinal],
         ));
     }
 
     if column.constraints.contains(&ColumnConstraint::Nullable) {
         return Err(SQLError::new(
             SQLErrorKind::InvalidPrimaryKey { reason: "primary key cannot be nullable" },
-            pos,
+            offsets[ordinal],
         ));
     }
 
@@ -195,10 +354,21 @@ mod tests {
                 Column {
                     name: "id",
                     column_type: ColumnType::Int,
+                    default: None,
                     constraints: Vec::from([ColumnConstraint::PrimaryKey]),
                 },
-                Column { name: "name", column_type: ColumnType::Text, constraints: Vec::new() },
-                Column { name: "age", column_type: ColumnType::Int, constraints: Vec::new() },
+                Column {
+                    name: "name",
+                    column_type: ColumnType::Text(None),
+                    default: None,
+                    constraints: Vec::new(),
+                },
+                Column {
+                    name: "age",
+                    column_type: ColumnType::Int,
+                    default: None,
+                    constraints: Vec::new(),
+                },
             ],
         };
 
@@ -217,10 +387,21 @@ mod tests {
                 Column {
                     name: "id",
                     column_type: ColumnType::Int,
+                    default: None,
                     constraints: Vec::from([ColumnConstraint::PrimaryKey]),
                 },
-                Column { name: "name", column_type: ColumnType::Text, constraints: Vec::new() },
-                Column { name: "price", column_type: ColumnType::Float, constraints: Vec::new() },
+                Column {
+                    name: "name",
+                    column_type: ColumnType::Text(None),
+                    default: None,
+                    constraints: Vec::new(),
+                },
+                Column {
+                    name: "price",
+                    column_type: ColumnType::Float,
+                    default: None,
+                    constraints: Vec::new(),
+                },
             ],
         };
 
@@ -238,6 +419,7 @@ mod tests {
             columns: vec![Column {
                 name: "id",
                 column_type: ColumnType::Int,
+                default: None,
                 constraints: Vec::from([ColumnConstraint::PrimaryKey]),
             }],
         };
@@ -246,17 +428,116 @@ mod tests {
         assert_eq!(Ok(expected), parser.stmt());
     }
 
+    #[test]
+    fn test_create_table_with_quoted_names() {
+        let s = r#"CREATE TABLE "my table" ("order" INT PRIMARY KEY, "first name" TEXT);"#;
+        let mut parser = Parser::new(s);
+
+        let expected_query = CreateTableQuery {
+            table_name: "my table",
+            columns: vec![
+                Column {
+                    name: "order",
+                    column_type: ColumnType::Int,
+                    default: None,
+                    constraints: Vec::from([ColumnConstraint::PrimaryKey]),
+                },
+                Column {
+                    name: "first name",
+                    column_type: ColumnType::Text(None),
+                    default: None,
+                    constraints: Vec::new(),
+                },
+            ],
+        };
+
+        let expected = CreateTable(expected_query);
+        assert_eq!(Ok(expected), parser.stmt());
+    }
+
+    #[test]
+    fn test_create_table_with_every_column_type() {
+        let s = "CREATE TABLE t (a INT PRIMARY KEY, b BIGINT, c FLOAT, d TEXT, e BOOLEAN, f BLOB);";
+        let types: Vec<_> = parse_columns(s).into_iter().map(|column| column.column_type).collect();
+        let expected = vec![
+            ColumnType::Int,
+            ColumnType::BigInt,
+            ColumnType::Float,
+            ColumnType::Text(None),
+            ColumnType::Boolean,
+            ColumnType::Blob,
+        ];
+        assert_eq!(types, expected);
+        assert_eq!(Parser::new(s).stmt().unwrap().to_string(), s);
+
+        let s = "CREATE TABLE t (a INT PRIMARY KEY, b bigint, c Boolean, d blob);";
+        assert_eq!(parse_columns(s)[3].column_type, ColumnType::Blob);
+    }
+
+    #[test]
+    fn test_create_table_with_type_aliases() {
+        let s = "CREATE TABLE t (id INTEGER PRIMARY KEY, n INT, a BOOL, b boolean);";
+        let types: Vec<_> = parse_columns(s).into_iter().map(|column| column.column_type).collect();
+        let expected =
+            vec![ColumnType::Int, ColumnType::Int, ColumnType::Boolean, ColumnType::Boolean];
+        assert_eq!(types, expected);
+        assert_eq!(
+            Parser::new(s).stmt().unwrap().to_string(),
+            "CREATE TABLE t (id INT PRIMARY KEY, n INT, a BOOLEAN, b BOOLEAN);"
+        );
+    }
+
+    #[test]
+    fn test_create_table_with_sized_text_types() {
+        let s =
+            "CREATE TABLE t (id INT PRIMARY KEY, a VARCHAR(255), b TEXT, c TEXT(16), d VARCHAR);";
+        let types: Vec<_> = parse_columns(s).into_iter().map(|column| column.column_type).collect();
+        let expected = vec![
+            ColumnType::Int,
+            ColumnType::Varchar(Some(255)),
+            ColumnType::Text(None),
+            ColumnType::Text(Some(16)),
+            ColumnType::Varchar(None),
+        ];
+        assert_eq!(types, expected);
+        assert_eq!(Parser::new(s).stmt().unwrap().to_string(), s);
+    }
+
+    #[test]
+    fn test_create_table_rejects_empty_type_size() {
+        let s = "CREATE TABLE t (id INT PRIMARY KEY, name VARCHAR());";
+        let err = SQLError::new(
+            SQLErrorKind::ExpectedInteger { got: TokenKind::RightParen },
+            s.find("()").unwrap() + 1,
+        );
+        assert_eq!(Parser::new(s).stmt(), Err(err));
+
+        let s = "CREATE TABLE t (id INT PRIMARY KEY, name VARCHAR(-1));";
+        let err = SQLError::new(
+            SQLErrorKind::ExpectedNonNegativeInteger { got: -1 },
+            s.find('-').unwrap(),
+        );
+        assert_eq!(Parser::new(s).stmt(), Err(err));
+    }
+
     #[test]
     fn test_parse_create_table_invalid_column_type() {
         let s = "CREATE TABLE invalid (id INVALID_TYPE);";
         let mut parser = Parser::new(s);
 
-        let err = SQLError {
-            kind: SQLErrorKind::InvalidDataType { got: TokenKind::Identifier("INVALID_TYPE") },
-            pos: 25,
-        };
+        let err = SQLError::new(
+            SQLErrorKind::InvalidDataType { got: TokenKind::Identifier("INVALID_TYPE") },
+            25,
+        );
 
         assert_eq!(Err(err), parser.stmt());
+
+        let s = "CREATE TABLE invalid (id INT PRIMARY KEY, flag BIT);";
+        let err = SQLError::new(
+            SQLErrorKind::InvalidDataType { got: TokenKind::Identifier("BIT") },
+            s.find("BIT").unwrap(),
+        );
+        assert_eq!(Err(err), Parser::new(s).stmt());
     }
 
     #[test]
@@ -264,10 +545,11 @@ mod tests {
         let s = "CREATE TABLE (id INT PRIMARY KEY);";
         let mut parser = Parser::new(s);
 
-        let err = SQLError {
-            kind: SQLErrorKind::ExpectedIdentifier { got: TokenKind::LeftParen },
-            pos: 14,
-        };
+        let err = SQLError::spanned(
+            SQLErrorKind::ExpectedIdentifier { got: TokenKind::LeftParen },
+            13,
+            14,
+        );
 
         assert_eq!(Err(err), parser.stmt());
     }
@@ -283,9 +565,15 @@ mod tests {
                 Column {
                     name: "id",
                     column_type: ColumnType::Int,
+                    default: None,
                     constraints: Vec::from([ColumnConstraint::PrimaryKey]),
                 },
-                Column { name: "name", column_type: ColumnType::Text, constraints: Vec::new() },
+                Column {
+                    name: "name",
+                    column_type: ColumnType::Text(None),
+                    default: None,
+                    constraints: Vec::new(),
+                },
             ],
         };
 
@@ -304,11 +592,13 @@ mod tests {
                 Column {
                     name: "id",
                     column_type: ColumnType::Int,
+                    default: None,
                     constraints: Vec::from([ColumnConstraint::PrimaryKey]),
                 },
                 Column {
                     name: "name",
-                    column_type: ColumnType::Text,
+                    column_type: ColumnType::Text(None),
+                    default: None,
                     constraints: Vec::from_iter(vec![ColumnConstraint::Nullable]),
                 },
             ],
@@ -328,6 +618,7 @@ mod tests {
             columns: vec![Column {
                 name: "a",
                 column_type: ColumnType::Int,
+                default: None,
                 constraints: Vec::from([ColumnConstraint::PrimaryKey]),
             }],
         };
@@ -336,6 +627,70 @@ mod tests {
         assert_eq!(Ok(expected), parser.stmt());
     }
 
+    #[test]
+    fn column_display_keeps_declared_constraint_order() {
+        let column = Column {
+            name: "id",
+            column_type: ColumnType::Int,
+            default: None,
+            constraints: vec![ColumnConstraint::Nullable, ColumnConstraint::PrimaryKey],
+        };
+        let first = column.to_string();
+        assert_eq!(first, "id INT NULLABLE PRIMARY KEY");
+        for _ in 0..100 {
+            assert_eq!(column.to_string(), first);
+        }
+
+        let s = "CREATE TABLE users (id INT PRIMARY KEY, name TEXT NULLABLE, age INT NULLABLE);";
+        let first = parse_create_table(s).unwrap().to_string();
+        assert_eq!(first, s);
+        for _ in 0..100 {
+            assert_eq!(parse_create_table(s).unwrap().to_string(), first);
+        }
+    }
+
+    #[test]
+    fn create_table_rejects_duplicate_constraint() {
+        let s = "CREATE TABLE users (id INT PRIMARY KEY, name TEXT NULLABLE NULLABLE);";
+        let start = s.rfind("NULLABLE").unwrap();
+        let expected = SQLError::spanned(
+            SQLErrorKind::DuplicateConstraint {
+                column: "name",
+                constraint: ColumnConstraint::Nullable,
+            },
+            start,
+            start + "NULLABLE".len(),
+        );
+        assert_eq!(Parser::new(s).stmt(), Err(expected));
+
+        let s = "CREATE TABLE users (id INT PRIMARY KEY PRIMARY KEY);";
+        assert!(matches!(
+            Parser::new(s).stmt(),
+            Err(SQLError { kind: SQLErrorKind::DuplicateConstraint { column: "id", .. }, .. })
+        ));
+    }
+
+    #[test]
+    fn create_table_rejects_duplicate_column_names() {
+        let s = "CREATE TABLE t (a INT PRIMARY KEY, b TEXT, a TEXT);";
+        let start = s.rfind("a TEXT").unwrap();
+        let expected =
+            SQLError::spanned(SQLErrorKind::DuplicateColumn { name: "a" }, start, start + 1);
+        assert_eq!(Parser::new(s).stmt(), Err(expected));
+    }
+
+    #[test]
+    fn create_table_accepts_distinct_column_names() {
+        let s = "CREATE TABLE t (a INT PRIMARY KEY, ab TEXT, b TEXT);";
+        let Ok(CreateTable(query)) = Parser::new(s).stmt() else {
+            panic!("expected CREATE TABLE to parse");
+        };
+        assert_eq!(
+            query.columns.iter().map(|column| column.name).collect::<Vec<_>>(),
+            ["a", "ab", "b"]
+        );
+    }
+
     #[test]
     fn create_table_requires_exactly_one_primary_key() {
         let mut parser = Parser::new("CREATE TABLE users (id INT, name TEXT);");
@@ -348,13 +703,31 @@ mod tests {
 
     #[test]
     fn create_table_rejects_multiple_primary_keys() {
-        let mut parser =
-            Parser::new("CREATE TABLE users (id INT PRIMARY KEY, other INT PRIMARY KEY);");
+        let s = "CREATE TABLE users (id INT PRIMARY KEY, other INT PRIMARY KEY);";
+        let expected =
+            SQLError::new(SQLErrorKind::MultiplePrimaryKeys { count: 2 }, s.find("other").unwrap());
+        assert_eq!(Parser::new(s).stmt(), Err(expected));
 
-        assert!(matches!(
-            parser.stmt(),
-            Err(SQLError { kind: SQLErrorKind::InvalidPrimaryKey { .. }, .. })
-        ));
+        let s = "CREATE TABLE users (id INT PRIMARY KEY, other INT);";
+        assert!(Parser::new(s).stmt().is_ok());
+    }
+
+    #[test]
+    fn test_primary_key_errors_point_at_the_offending_column() {
+        let cases = [
+            ("CREATE TABLE t (a INT, b INT PRIMARY KEY);", "b INT", "first column"),
+            ("CREATE TABLE t (a TEXT PRIMARY KEY);", "a TEXT", "INT type"),
+            ("CREATE TABLE t (a INT PRIMARY KEY NULLABLE);", "a INT", "nullable"),
+            ("CREATE TABLE t (a INT, b INT  );", ");", "exactly one"),
+        ];
+        for (s, at, reason) in cases {
+            let err = Parser::new(s).stmt().unwrap_err();
+            assert!(
+                matches!(err.kind, SQLErrorKind::InvalidPrimaryKey { reason: r } if r.contains(reason)),
+                "unexpected error {err:?} for {s}"
+            );
+            assert_eq!(err.pos, s.find(at).unwrap(), "wrong position for {s}");
+        }
     }
 
     #[test]
@@ -377,6 +750,61 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn parse_create_table_returns_query() {
+        let expected = CreateTableQuery {
+            table_name: "users",
+            columns: vec![
+                Column {
+                    name: "id",
+                    column_type: ColumnType::Int,
+                    default: None,
+                    constraints: Vec::from([ColumnConstraint::PrimaryKey]),
+                },
+                Column {
+                    name: "name",
+                    column_type: ColumnType::Text(None),
+                    default: None,
+                    constraints: Vec::new(),
+                },
+            ],
+        };
+
+        assert_eq!(
+            Ok(expected),
+            parse_create_table("CREATE TABLE users (id INT PRIMARY KEY, name TEXT);  ")
+        );
+    }
+
+    #[test]
+    fn parse_create_table_rejects_other_statement_kinds() {
+        let err = SQLError::new(
+            SQLErrorKind::UnexpectedTokenKind {
+                expected: TokenKind::Keyword(Keyword::Create),
+                got: TokenKind::Keyword(Keyword::Select),
+            },
+            0,
+        );
+        assert_eq!(Err(err), parse_create_table("SELECT * FROM users;"));
+
+        let err = SQLError::new(
+            SQLErrorKind::UnexpectedTokenKind {
+                expected: TokenKind::Keyword(Keyword::Table),
+                got: TokenKind::Keyword(Keyword::Index),
+            },
+            7,
+        );
+        assert_eq!(Err(err), parse_create_table("CREATE INDEX idx ON users (id);"));
+    }
+
+    #[test]
+    fn parse_create_table_rejects_trailing_tokens() {
+        let s = "CREATE TABLE users (id INT PRIMARY KEY); DROP";
+
+        let err = SQLError::new(SQLErrorKind::Other(TokenKind::Identifier("DROP")), 41);
+        assert_eq!(Err(err), parse_create_table(s));
+    }
+
     #[test]
     fn create_table_rejects_nullable_primary_key() {
         let mut parser = Parser::new("CREATE TABLE users (id INT PRIMARY KEY NULLABLE);");
@@ -386,4 +814,141 @@ mod tests {
             Err(SQLError { kind: SQLErrorKind::InvalidPrimaryKey { .. }, .. })
         ));
     }
+
+    fn parse_columns(s: &str) -> Vec<Column<'_>> {
+        match Parser::new(s).stmt() {
+            Ok(CreateTable(query)) => query.columns,
+            other => panic!("expected CREATE TABLE, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_create_table_with_literal_default() {
+        let s = "CREATE TABLE users (id INT PRIMARY KEY, age INT DEFAULT 0);";
+        let columns = parse_columns(s);
+        assert_eq!(columns[1].default, Some(Expression::from(0)));
+        assert_eq!(columns[1].to_string(), "age INT DEFAULT 0");
+    }
+
+    #[test]
+    fn test_create_table_with_expression_default() {
+        let s = "CREATE TABLE users (id INT PRIMARY KEY, age INT DEFAULT 1 + 1, name TEXT);";
+        let columns = parse_columns(s);
+        assert_eq!(columns[1].default, Some(Parser::new("1 + 1").expr().unwrap()));
+        assert_eq!(columns[2].default, None);
+        assert_eq!(columns[1].to_string(), "age INT DEFAULT 1 + 1");
+    }
+
+    #[test]
+    fn test_create_table_default_before_constraint() {
+        let s = "CREATE TABLE users (id INT DEFAULT -1 PRIMARY KEY, age INT DEFAULT 0 NULLABLE);";
+        let columns = parse_columns(s);
+        assert_eq!(columns[0].constraints, vec![ColumnConstraint::PrimaryKey]);
+        assert_eq!(columns[1].default, Some(Expression::from(0)));
+        assert_eq!(columns[1].constraints, vec![ColumnConstraint::Nullable]);
+        assert_eq!(Parser::new(s).stmt().unwrap().to_string(), s);
+
+        let s = "CREATE TABLE users (id INT PRIMARY KEY, age INT NULLABLE DEFAULT 0);";
+        let kind = SQLErrorKind::ExpectedOneOf {
+            expected: &["PRIMARY KEY", "NOT NULL", "NULLABLE", "UNIQUE", "','", "')'"],
+            got: TokenKind::Keyword(Keyword::Default),
+        };
+        let err = SQLError::new(kind, s.find("DEFAULT").unwrap());
+        assert_eq!(Parser::new(s).stmt(), Err(err));
+    }
+
+    #[test]
+    fn test_misspelled_constraint_lists_what_may_follow_the_type() {
+        let s = "CREATE TABLE users (id INT PRIMRY KEY);";
+        let kind = SQLErrorKind::ExpectedOneOf {
+            expected: &["DEFAULT", "PRIMARY KEY", "NOT NULL", "NULLABLE", "UNIQUE", "','", "')'"],
+            got: TokenKind::Identifier("PRIMRY"),
+        };
+        let err = SQLError::new(kind, s.find("PRIMRY").unwrap());
+        assert_eq!(Parser::new(s).stmt(), Err(err));
+        assert_eq!(
+            err.kind.to_string(),
+            "Expected one of DEFAULT, PRIMARY KEY, NOT NULL, NULLABLE, UNIQUE, ',', ')' \
+             but found IDENT ('PRIMRY')"
+        );
+    }
+
+    #[test]
+    fn test_create_table_with_unique_constraint() {
+        let s = "CREATE TABLE users (id INT UNIQUE PRIMARY KEY, email TEXT UNIQUE);";
+        let columns = parse_columns(s);
+        let expected = vec![ColumnConstraint::Unique, ColumnConstraint::PrimaryKey];
+        assert_eq!(columns[0].constraints, expected);
+        assert_eq!(columns[1].constraints, vec![ColumnConstraint::Unique]);
+        assert_eq!(Parser::new(s).stmt().unwrap().to_string(), s);
+    }
+
+    #[test]
+    fn test_create_table_with_not_null_constraint() {
+        let s = "CREATE TABLE users (id INT PRIMARY KEY, name TEXT DEFAULT 'x' NOT NULL UNIQUE);";
+        let columns = parse_columns(s);
+        let expected = vec![ColumnConstraint::NotNull, ColumnConstraint::Unique];
+        assert_eq!(columns[1].constraints, expected);
+        assert_eq!(Parser::new(s).stmt().unwrap().to_string(), s);
+
+        let s = "CREATE TABLE users (id INT PRIMARY KEY, name TEXT NOT);";
+        let kind = SQLErrorKind::UnexpectedTokenKind {
+            expected: TokenKind::Keyword(Keyword::Null),
+            got: TokenKind::RightParen,
+        };
+        assert_eq!(Parser::new(s).stmt(), Err(SQLError::new(kind, s.len() - 2)));
+    }
+
+    #[test]
+    fn test_create_table_rejects_duplicate_unique_and_not_null() {
+        let s = "CREATE TABLE users (id INT PRIMARY KEY, email TEXT UNIQUE UNIQUE);";
+        let start = s.rfind("UNIQUE").unwrap();
+        let expected = SQLError::spanned(
+            SQLErrorKind::DuplicateConstraint {
+                column: "email",
+                constraint: ColumnConstraint::Unique,
+            },
+            start,
+            start + "UNIQUE".len(),
+        );
+        assert_eq!(Parser::new(s).stmt(), Err(expected));
+
+        let s = "CREATE TABLE users (id INT PRIMARY KEY, name TEXT NOT NULL NOT NULL);";
+        let start = s.rfind("NOT NULL").unwrap();
+        let expected = SQLError::spanned(
+            SQLErrorKind::DuplicateConstraint {
+                column: "name",
+                constraint: ColumnConstraint::NotNull,
+            },
+            start,
+            start + "NOT NULL".len(),
+        );
+        assert_eq!(Parser::new(s).stmt(), Err(expected));
+    }
+
+    #[test]
+    fn test_create_table_rejects_nullable_with_not_null() {
+        for (s, keyword, constraint, conflicts_with) in [
+            (
+                "CREATE TABLE t (id INT PRIMARY KEY, x INT NULLABLE NOT NULL);",
+                "NOT NULL",
+                ColumnConstraint::NotNull,
+                ColumnConstraint::Nullable,
+            ),
+            (
+                "CREATE TABLE t (id INT PRIMARY KEY, x INT NOT NULL UNIQUE NULLABLE);",
+                "NULLABLE",
+                ColumnConstraint::Nullable,
+                ColumnConstraint::NotNull,
+            ),
+        ] {
+            let start = s.rfind(keyword).unwrap();
+            let expected = SQLError::spanned(
+                SQLErrorKind::ConflictingConstraint { column: "x", constraint, conflicts_with },
+                start,
+                start + keyword.len(),
+            );
+            assert_eq!(Parser::new(s).stmt(), Err(expected), "{s}");
+        }
+    }
 }
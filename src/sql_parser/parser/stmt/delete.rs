@@ -6,10 +6,11 @@ use crate::sql_parser::{
         token::Token,
         token_kind::{Keyword, TokenKind},
     },
-    parser::{Parser, expr::Expression},
+    parser::{Parser, expr::Expression, ident::quote_ident},
 };
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct DeleteQuery<'a> {
     pub table: &'a str,
     pub where_clause: Option<Expression<'a>>,
@@ -17,7 +18,7 @@ pub struct DeleteQuery<'a> {
 
 impl Display for DeleteQuery<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "DELETE FROM {}", self.table)?;
+        write!(f, "DELETE FROM {}", quote_ident(self.table))?;
 
         if let Some(ref where_clause) = self.where_clause {
             write!(f, " WHERE {}", where_clause)?;
@@ -41,7 +42,7 @@ impl<'a> Parser<'a> {
                 None
             };
 
-        self.lexer.expect_token(TokenKind::Semicolon)?;
+        self.expect_statement_end()?;
         Ok(DeleteQuery { table, where_clause })
     }
 }
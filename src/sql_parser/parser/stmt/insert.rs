@@ -1,15 +1,20 @@
 use std::fmt::Display;
 
 use crate::sql_parser::{
-    error::SQLError,
+    error::{SQLError, SQLErrorKind},
     lexer::token_kind::{Keyword, TokenKind},
     parser::{
         Parser,
-        stmt::lists::{ExpressionList, IdentifierList},
+        ident::quote_ident,
+        stmt::{
+            lists::{ExpressionList, IdentifierList},
+            select::SelectQuery,
+        },
     },
 };
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Values<'a>(pub Vec<ExpressionList<'a>>);
 
 impl Display for Values<'_> {
@@ -23,18 +28,67 @@ impl<'a> Parser<'a> {
     fn parse_values(&mut self) -> Result<Values<'a>, SQLError<'a>> {
         Ok(Values(self.parse_comma_separated_list_in_parenthesis(|p| p.parse_expression_list())?))
     }
+
+    /// Parses a standalone `VALUES` row set, requiring every row to have the same arity.
+    pub fn parse_values_statement(&mut self) -> Result<Values<'a>, SQLError<'a>> {
+        let mut arity = None;
+        let rows = self.parse_comma_separated_list(|p| {
+            let start = match p.lexer.peek() {
+                Some(Ok(token)) => token.offset,
+                _ => p.lexer.token_end,
+            };
+            p.lexer.expect_token(TokenKind::LeftParen)?;
+            let row = p.parse_expression_list()?;
+            p.lexer.expect_token(TokenKind::RightParen)?;
+
+            let got = row.0.len();
+            match arity {
+                Some(expected) if expected != got => Err(SQLError::spanned(
+                    SQLErrorKind::ValuesArityMismatch { expected, got },
+                    start,
+                    p.lexer.token_end,
+                )),
+                _ => {
+                    arity = Some(got);
+                    Ok(row)
+                }
+            }
+        })?;
+        self.expect_statement_end()?;
+        Ok(Values(rows))
+    }
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct InsertQuery<'a> {
     pub table: &'a str,
     pub columns: IdentifierList<'a>,
-    pub values: Values<'a>,
+    pub source: InsertSource<'a>,
+}
+
+/// The rows written by an `INSERT`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum InsertSource<'a> {
+    /// Literal rows, as in `INSERT INTO t (a) VALUES (1), (2);`.
+    Values(Values<'a>),
+    /// The result of a query, as in `INSERT INTO t (a) SELECT x FROM s;`.
+    Select(Box<SelectQuery<'a>>),
+}
+
+impl Display for InsertSource<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InsertSource::Values(values) => write!(f, "VALUES {values}"),
+            InsertSource::Select(query) => query.fmt_body(f),
+        }
+    }
 }
 
 impl Display for InsertQuery<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "INSERT INTO {} ({}) VALUES {};", self.table, self.columns, self.values)
+        write!(f, "INSERT INTO {} ({}) {};", quote_ident(self.table), self.columns, self.source)
     }
 }
 
@@ -47,11 +101,27 @@ impl<'a> Parser<'a> {
         let columns = self.parse_identifier_list()?;
         self.lexer.expect_token(TokenKind::RightParen)?;
 
+        if self.lexer.next_if_keyword(Keyword::Select) {
+            let query = self.parse_select_query()?;
+            return Ok(InsertQuery {
+                table,
+                columns,
+                source: InsertSource::Select(Box::new(query)),
+            });
+        }
+        if let Some(Ok(token)) = self.lexer.peek()
+            && token.kind != TokenKind::Keyword(Keyword::Values)
+        {
+            return Err(SQLError::new(
+                SQLErrorKind::ExpectedOneOf { expected: &["VALUES", "SELECT"], got: token.kind },
+                token.offset,
+            ));
+        }
         self.lexer.expect_token(TokenKind::Keyword(Keyword::Values))?;
 
         let values = self.parse_values()?;
-        self.lexer.expect_token(TokenKind::Semicolon)?;
-        Ok(InsertQuery { table, columns, values })
+        self.expect_statement_end()?;
+        Ok(InsertQuery { table, columns, source: InsertSource::Values(values) })
     }
 }
 
@@ -60,11 +130,9 @@ mod tests {
     use super::*;
     use crate::sql_parser::parser::{
         Parser, SqlItem,
+        build::{InsertQueryBuilder, lit, text},
         expr::{Expression, Literal},
-        stmt::{
-            Statement,
-            lists::{ExpressionList, IdentifierList},
-        },
+        stmt::{Statement, lists::ExpressionList},
     };
 
     #[test]
@@ -72,22 +140,87 @@ mod tests {
         let s = "INSERT INTO products (id, name, price) VALUES (123, 'Cake', 45.67), (789, 'Waffles', 10.00);";
         let mut parser = Parser::new(s);
         let got = parser.next();
-        let expected = InsertQuery {
-            table: "products",
-            columns: IdentifierList(vec!["id", "name", "price"]),
-            values: Values(vec![
-                ExpressionList(vec![
-                    Expression::from(123),
-                    Expression::Literal(Literal::String("Cake")),
-                    Expression::from(45.67f32),
-                ]),
-                ExpressionList(vec![
-                    Expression::from(789),
-                    Expression::Literal(Literal::String("Waffles")),
-                    Expression::from(10.00f32),
-                ]),
-            ]),
-        };
+        let expected = InsertQueryBuilder::new("products")
+            .column("id")
+            .column("name")
+            .column("price")
+            .row([lit(123), text("Cake"), lit(45.67)])
+            .row([lit(789), text("Waffles"), lit(10.00)])
+            .build()
+            .unwrap();
         assert_eq!(Some(Ok(SqlItem::Statement(Statement::Insert(expected)))), got);
     }
+
+    #[test]
+    fn test_parse_insert_select_query() {
+        let s = "INSERT INTO t (a, b) SELECT x, y + 1 FROM s WHERE x > 0;";
+        let got = Parser::new(s).stmt().unwrap();
+        let Statement::Insert(InsertQuery { table, columns, source: InsertSource::Select(query) }) =
+            &got
+        else {
+            panic!("expected INSERT ... SELECT, got {got:?}");
+        };
+        assert_eq!(*table, "t");
+        assert_eq!(columns.0, ["a", "b"]);
+        assert_eq!(query.table, Some("s"));
+        assert_eq!(query.columns.0.len(), 2);
+
+        assert_eq!(got.to_string(), s);
+        assert_eq!(Parser::new(&got.to_string()).stmt(), Ok(got));
+    }
+
+    #[test]
+    fn test_insert_requires_values_or_select() {
+        let s = "INSERT INTO t (a) TABLE s;";
+        let offset = s.find("TABLE").unwrap();
+        let expected = SQLError::new(
+            SQLErrorKind::ExpectedOneOf {
+                expected: &["VALUES", "SELECT"],
+                got: TokenKind::Keyword(Keyword::Table),
+            },
+            offset,
+        );
+        assert_eq!(Parser::new(s).stmt(), Err(expected));
+
+        let err = Parser::new("INSERT INTO t (a)").stmt().unwrap_err();
+        assert_eq!(err.kind, SQLErrorKind::UnexpectedEnd);
+    }
+
+    #[test]
+    fn test_parse_single_row_values_statement() {
+        let s = "VALUES (1, 'a');";
+        let got = Parser::new(s).stmt();
+        let expected = Values(vec![ExpressionList(vec![
+            Expression::from(1),
+            Expression::Literal(Literal::String("a".into())),
+        ])]);
+        assert_eq!(Ok(Statement::Values(expected)), got);
+        assert_eq!(got.unwrap().to_string(), "VALUES (1, 'a');");
+    }
+
+    #[test]
+    fn test_parse_multi_row_values_statement() {
+        let s = "VALUES (1, 'a'), (2 + 3, 'b'), (NULL, 'c');";
+        let got = Parser::new(s).stmt().unwrap();
+        let Statement::Values(values) = &got else {
+            panic!("expected VALUES statement, got {got:?}");
+        };
+        assert_eq!(values.0.len(), 3);
+
+        let displayed = got.to_string();
+        assert_eq!(displayed, "VALUES (1, 'a'), (2 + 3, 'b'), (NULL, 'c');");
+        assert_eq!(Parser::new(&displayed).stmt(), Ok(got));
+    }
+
+    #[test]
+    fn test_values_statement_rejects_arity_mismatch() {
+        let s = "VALUES (1, 'a'), (2);";
+        let start = s.find("(2)").unwrap();
+        let expected = SQLError::spanned(
+            SQLErrorKind::ValuesArityMismatch { expected: 2, got: 1 },
+            start,
+            start + "(2)".len(),
+        );
+        assert_eq!(Parser::new(s).stmt(), Err(expected));
+    }
 }
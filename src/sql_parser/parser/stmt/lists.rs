@@ -1,8 +1,9 @@
 use std::fmt::Display;
 
-use crate::sql_parser::parser::expr::Expression;
+use crate::sql_parser::parser::{expr::Expression, ident::quote_ident};
 
 #[derive(Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ExpressionList<'a>(pub Vec<Expression<'a>>);
 impl Display for ExpressionList<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -13,10 +14,11 @@ impl Display for ExpressionList<'_> {
 }
 
 #[derive(Debug, PartialEq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IdentifierList<'a>(pub Vec<&'a str>);
 impl Display for IdentifierList<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let joined = self.0.join(", ");
+        let joined = self.0.iter().map(|ident| quote_ident(ident)).collect::<Vec<_>>().join(", ");
         write!(f, "{joined}")
     }
 }
@@ -6,23 +6,34 @@ pub mod delete;
 pub mod insert;
 pub mod select;
 pub mod update;
+pub mod with;
 
 pub mod lists;
 
 use create_index::CreateIndexQuery;
 use create_table::CreateTableQuery;
 use delete::DeleteQuery;
-use insert::InsertQuery;
+use insert::{InsertQuery, InsertSource, Values};
 use select::SelectQuery;
 use update::UpdateQuery;
+use with::WithQuery;
+
+use crate::sql_parser::{
+    error::SQLError,
+    parser::{Parser, expr::Expression},
+};
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Statement<'a> {
     Explain(Box<Statement<'a>>),
+    With(WithQuery<'a>),
     Select(SelectQuery<'a>),
     Update(UpdateQuery<'a>),
     Delete(DeleteQuery<'a>),
     Insert(InsertQuery<'a>),
+    /// A standalone `VALUES` row set used as a query.
+    Values(Values<'a>),
     CreateTable(CreateTableQuery<'a>),
     CreateIndex(CreateIndexQuery<'a>),
 }
@@ -31,12 +42,103 @@ impl Display for Statement<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Statement::Explain(statement) => write!(f, "EXPLAIN {statement}"),
+            Statement::With(query) => query.fmt(f),
             Statement::Select(query) => query.fmt(f),
             Statement::Update(query) => query.fmt(f),
             Statement::Delete(query) => query.fmt(f),
             Statement::Insert(query) => query.fmt(f),
+            Statement::Values(values) => write!(f, "VALUES {values};"),
             Statement::CreateTable(query) => query.fmt(f),
             Statement::CreateIndex(query) => query.fmt(f),
         }
     }
 }
+
+impl Statement<'_> {
+    /// Returns the number of AST nodes in the statement, including itself.
+    ///
+    /// Every expression node, table and column name counts as one node, which
+    /// gives a rough measure of how expensive the statement is to plan.
+    pub fn node_count(&self) -> usize {
+        1 + match self {
+            Statement::Explain(statement) => statement.node_count(),
+            Statement::With(query) => {
+                query.ctes.iter().map(|cte| 1 + cte.query.node_count()).sum::<usize>()
+                    + query.statement.node_count()
+            }
+            Statement::Select(query) => query.node_count(),
+            Statement::Update(query) => {
+                1 + query
+                    .assignments
+                    .0
+                    .iter()
+                    .map(|assignment| 1 + assignment.expression.node_count())
+                    .sum::<usize>()
+                    + query.where_clause.as_ref().map_or(0, Expression::node_count)
+            }
+            Statement::Delete(query) => {
+                1 + query.where_clause.as_ref().map_or(0, Expression::node_count)
+            }
+            Statement::Insert(query) => {
+                1 + query.columns.0.len()
+                    + match &query.source {
+                        InsertSource::Values(values) => values
+                            .0
+                            .iter()
+                            .flat_map(|row| &row.0)
+                            .map(Expression::node_count)
+                            .sum::<usize>(),
+                        InsertSource::Select(query) => query.node_count(),
+                    }
+            }
+            Statement::Values(values) => {
+                values.0.iter().flat_map(|row| &row.0).map(Expression::node_count).sum::<usize>()
+            }
+            Statement::CreateTable(query) => {
+                1 + query
+                    .columns
+                    .iter()
+                    .map(|column| 1 + column.default.as_ref().map_or(0, Expression::node_count))
+                    .sum::<usize>()
+            }
+            Statement::CreateIndex(query) => 2 + query.columns.0.len(),
+        }
+    }
+}
+
+/// A statement that owns its SQL text, so it can outlive the source it was parsed from.
+///
+/// The text is the statement's canonical [`Display`] form, which parses back to an equal AST.
+#[derive(Debug, PartialEq, Clone)]
+pub struct OwnedStatement {
+    sql: String,
+}
+
+impl OwnedStatement {
+    /// Parses `sql` and keeps its canonical form.
+    pub fn parse(sql: &str) -> Result<Self, SQLError<'_>> {
+        Parser::new(sql).stmt().map(|statement| Self::from(&statement))
+    }
+
+    /// Returns the canonical SQL text of the statement.
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// Parses the stored text back into a borrowed [`Statement`].
+    pub fn statement(&self) -> Result<Statement<'_>, SQLError<'_>> {
+        Parser::new(&self.sql).stmt()
+    }
+}
+
+impl From<&Statement<'_>> for OwnedStatement {
+    fn from(statement: &Statement<'_>) -> Self {
+        Self { sql: statement.to_string() }
+    }
+}
+
+impl Display for OwnedStatement {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.sql)
+    }
+}
@@ -6,9 +6,10 @@ use crate::sql_parser::{
         token::Token,
         token_kind::{Keyword, TokenKind},
     },
-    parser::{Parser, expr::Expression, stmt::lists::ExpressionList},
+    parser::{Parser, expr::Expression, ident::quote_ident, stmt::lists::ExpressionList},
 };
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Ordering {
     Ascending,
     Descending,
@@ -24,6 +25,7 @@ impl Display for Ordering {
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct OrderByTerm<'a> {
     pub column: &'a str,
     pub order: Option<Ordering>,
@@ -31,7 +33,7 @@ pub struct OrderByTerm<'a> {
 
 impl Display for OrderByTerm<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.column)?;
+        write!(f, "{}", quote_ident(self.column))?;
 
         if let Some(ref order) = self.order {
             write!(f, " {}", order)?;
@@ -41,6 +43,7 @@ impl Display for OrderByTerm<'_> {
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct OrderBy<'a> {
     pub terms: Vec<OrderByTerm<'a>>,
 }
@@ -83,10 +86,15 @@ impl Display for OrderBy<'_> {
     }
 }
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct SelectQuery<'a> {
+    /// True for `SELECT DISTINCT`, which removes duplicate result rows.
+    pub distinct: bool,
     pub columns: ExpressionList<'a>,
     pub table: Option<&'a str>,
     pub where_clause: Option<Expression<'a>>,
+    pub group_by: Option<ExpressionList<'a>>,
+    pub having: Option<Expression<'a>>,
     pub order_by: Option<OrderBy<'a>>,
     pub limit: Option<u32>,
     pub offset: Option<u32>,
@@ -94,15 +102,139 @@ pub struct SelectQuery<'a> {
 
 impl Display for SelectQuery<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "SELECT {}", self.columns)?;
+        self.fmt_body(f)?;
+        write!(f, ";")
+    }
+}
+
+/// The set of optional clauses present in a [`SelectQuery`].
+///
+/// Flags are combined with `|` and tested with [`ClauseMask::contains`]. Bits
+/// follow the order in which a `SELECT` accepts its clauses.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub struct ClauseMask(u8);
+
+impl ClauseMask {
+    pub const FROM: Self = Self(1 << 0);
+    pub const WHERE: Self = Self(1 << 1);
+    pub const GROUP_BY: Self = Self(1 << 2);
+    pub const HAVING: Self = Self(1 << 3);
+    pub const ORDER_BY: Self = Self(1 << 4);
+    pub const LIMIT: Self = Self(1 << 5);
+    pub const OFFSET: Self = Self(1 << 6);
+
+    pub const fn empty() -> Self {
+        Self(0)
+    }
+
+    pub const fn bits(self) -> u8 {
+        self.0
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// Returns true if every clause in `other` is also in `self`.
+    pub const fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+}
+
+impl std::ops::BitOr for ClauseMask {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Self(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitOrAssign for ClauseMask {
+    fn bitor_assign(&mut self, rhs: Self) {
+        self.0 |= rhs.0;
+    }
+}
+
+/// Clauses in the order a `SELECT` accepts them, followed by the statement end.
+const CLAUSES_AND_END: &[&str] =
+    &["FROM", "WHERE", "GROUP BY", "HAVING", "ORDER BY", "LIMIT", "OFFSET", "';'"];
+
+impl SelectQuery<'_> {
+    /// Returns which optional clauses the query has.
+    pub fn clauses_present(&self) -> ClauseMask {
+        let clauses = [
+            (self.table.is_some(), ClauseMask::FROM),
+            (self.where_clause.is_some(), ClauseMask::WHERE),
+            (self.group_by.is_some(), ClauseMask::GROUP_BY),
+            (self.having.is_some(), ClauseMask::HAVING),
+            (self.order_by.is_some(), ClauseMask::ORDER_BY),
+            (self.limit.is_some(), ClauseMask::LIMIT),
+            (self.offset.is_some(), ClauseMask::OFFSET),
+        ];
+        clauses
+            .into_iter()
+            .filter(|(present, _)| *present)
+            .fold(ClauseMask::empty(), |mask, (_, clause)| mask | clause)
+    }
+
+    /// Returns the clauses that may still follow the last clause present, and the statement end.
+    fn following_clauses(&self) -> &'static [&'static str] {
+        let next = u8::BITS - self.clauses_present().bits().leading_zeros();
+        &CLAUSES_AND_END[next as usize..]
+    }
+
+    /// Returns true if the query is a constant select that needs no table scan.
+    ///
+    /// A scalar query has no `FROM` table and projects a single column with no
+    /// aggregate or subquery, as in `SELECT 1 + 1;`.
+    pub fn is_scalar(&self) -> bool {
+        let [column] = self.columns.0.as_slice() else {
+            return false;
+        };
+        self.table.is_none() && !column.contains_aggregate() && !column.contains_subquery()
+    }
+
+    /// Returns the number of AST nodes in the query.
+    ///
+    /// The query itself, its table, every expression node, every `ORDER BY` term
+    /// and the `LIMIT` and `OFFSET` values each count as one node.
+    pub fn node_count(&self) -> usize {
+        1 + self.columns.0.iter().map(Expression::node_count).sum::<usize>()
+            + usize::from(self.table.is_some())
+            + self.where_clause.as_ref().map_or(0, Expression::node_count)
+            + self
+                .group_by
+                .as_ref()
+                .map_or(0, |group_by| group_by.0.iter().map(Expression::node_count).sum::<usize>())
+            + self.having.as_ref().map_or(0, Expression::node_count)
+            + self.order_by.as_ref().map_or(0, |order_by| order_by.terms.len())
+            + usize::from(self.limit.is_some())
+            + usize::from(self.offset.is_some())
+    }
+
+    /// Writes the query without its terminating semicolon, as used inside subqueries.
+    pub(crate) fn fmt_body(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SELECT ")?;
+        if self.distinct {
+            write!(f, "DISTINCT ")?;
+        }
+        write!(f, "{}", self.columns)?;
 
         if let Some(table) = self.table {
-            write!(f, " FROM {}", table)?;
+            write!(f, " FROM {}", quote_ident(table))?;
         }
         if let Some(ref where_clause) = self.where_clause {
             write!(f, " WHERE {}", where_clause)?;
         }
 
+        if let Some(ref group_by) = self.group_by {
+            write!(f, " GROUP BY {}", group_by)?;
+        }
+
+        if let Some(ref having) = self.having {
+            write!(f, " HAVING {}", having)?;
+        }
+
         if let Some(ref order_by_clause) = self.order_by {
             write!(f, " ORDER BY {}", order_by_clause)?;
         }
@@ -115,15 +247,41 @@ impl Display for SelectQuery<'_> {
             write!(f, " OFFSET {}", offset)?;
         }
 
-        write!(f, ";")
+        Ok(())
     }
 }
 
 impl<'a> Parser<'a> {
     pub fn parse_select_query(&mut self) -> Result<SelectQuery<'a>, SQLError<'a>> {
+        let query = self.parse_select_body()?;
+
+        if let Some(Ok(token)) = self.lexer.peek()
+            && token.kind != TokenKind::Semicolon
+        {
+            return Err(SQLError::new(
+                SQLErrorKind::ExpectedOneOf {
+                    expected: query.following_clauses(),
+                    got: token.kind,
+                },
+                token.offset,
+            ));
+        }
+        self.expect_statement_end().map_err(|err| match err {
+            SQLError { kind: SQLErrorKind::UnexpectedEnd, .. } => {
+                SQLError { kind: SQLErrorKind::ExpectedCommaOrSemicolon, ..err }
+            }
+            err => err,
+        })?;
+
+        Ok(query)
+    }
+
+    /// Parses a `SELECT` after its keyword, stopping before any terminating semicolon.
+    pub(crate) fn parse_select_body(&mut self) -> Result<SelectQuery<'a>, SQLError<'a>> {
+        let distinct = self.lexer.next_if_keyword(Keyword::Distinct);
         let columns = match self.parse_expression_list() {
-            Err(SQLError { kind: SQLErrorKind::UnexpectedEnd, pos }) => {
-                return Err(SQLError { kind: SQLErrorKind::ExpectedExpression, pos });
+            Err(err @ SQLError { kind: SQLErrorKind::UnexpectedEnd, .. }) => {
+                return Err(SQLError { kind: SQLErrorKind::ExpectedExpression, ..err });
             }
             Ok(cols) => cols,
             Err(err) => return Err(err),
@@ -148,34 +306,86 @@ impl<'a> Parser<'a> {
                 None
             };
 
-        let order_by = self.parse_order_by()?;
-
-        let limit = if let Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Limit), .. })) =
+        let group_by = if let Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Group), .. })) =
             self.lexer.peek()
         {
             self.lexer.next();
-            self.parse_non_negative_integer()?
+            self.lexer.expect_token(TokenKind::Keyword(Keyword::By))?;
+            Some(self.parse_expression_list()?)
         } else {
             None
         };
 
-        let offset = if let Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Offset), .. })) =
+        let having = if let Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Having), .. })) =
             self.lexer.peek()
         {
             self.lexer.next();
-            self.parse_non_negative_integer()?
+            Some(self.expr_bp(0)?)
         } else {
             None
         };
 
-        self.lexer.expect_token(TokenKind::Semicolon).map_err(|err| match err {
-            SQLError { kind: SQLErrorKind::UnexpectedEnd, pos } => {
-                SQLError { kind: SQLErrorKind::ExpectedCommaOrSemicolon, pos }
+        let order_by = self.parse_order_by()?;
+
+        let has_limit = self.peek_limit().is_some();
+        let (limit, limit_offset) = if has_limit {
+            self.lexer.next();
+            let first = self.parse_non_negative_integer()?;
+            // `LIMIT offset, count`, as accepted by MySQL.
+            if let Some(Ok(Token { kind: TokenKind::Comma, .. })) = self.lexer.peek() {
+                self.lexer.next();
+                (self.parse_non_negative_integer()?, Some(first))
+            } else {
+                (first, None)
             }
-            err => err,
-        })?;
+        } else {
+            (None, None)
+        };
+        if has_limit && let Some(at) = self.peek_limit() {
+            return Err(SQLError::new(SQLErrorKind::DuplicateLimit, at));
+        }
+
+        let offset = match self.lexer.peek() {
+            Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Offset), offset: at, .. })) => {
+                if limit_offset.is_some() {
+                    return Err(SQLError::new(SQLErrorKind::DuplicateOffset, *at));
+                }
+                self.lexer.next();
+                let offset = self.parse_non_negative_integer()?;
+                if let Some(at) = self.peek_limit() {
+                    let kind = if has_limit {
+                        SQLErrorKind::DuplicateLimit
+                    } else {
+                        SQLErrorKind::OffsetBeforeLimit
+                    };
+                    return Err(SQLError::new(kind, at));
+                }
+                offset
+            }
+            _ => limit_offset.flatten(),
+        };
+
+        Ok(SelectQuery {
+            distinct,
+            columns,
+            table,
+            where_clause,
+            group_by,
+            having,
+            order_by,
+            limit,
+            offset,
+        })
+    }
 
-        Ok(SelectQuery { columns, table, where_clause, order_by, limit, offset })
+    /// Returns the offset of the next token if it is `LIMIT`.
+    fn peek_limit(&mut self) -> Option<usize> {
+        match self.lexer.peek() {
+            Some(Ok(Token { kind: TokenKind::Keyword(Keyword::Limit), offset, .. })) => {
+                Some(*offset)
+            }
+            _ => None,
+        }
     }
 }
 
@@ -184,8 +394,14 @@ mod tests {
     use super::*;
     use crate::sql_parser::{
         error::{SQLError, SQLErrorKind},
-        lexer::token_kind::{Keyword, TokenKind},
-        parser::{Parser, op::Op, stmt::Statement::Select},
+        lexer::token_kind::TokenKind,
+        parser::{
+            Parser,
+            build::{SelectQueryBuilder, aggregate, col},
+            expr::{AggregateFunction, AggregateFunctionKind},
+            op::Op,
+            stmt::Statement::Select,
+        },
     };
 
     #[test]
@@ -193,6 +409,7 @@ mod tests {
         let s = "SELECT abc, def, ghi;";
         let mut parser = Parser::new(s);
         let expected_query = SelectQuery {
+            distinct: false,
             columns: ExpressionList(vec![
                 Expression::Identifier("abc"),
                 Expression::Identifier("def"),
@@ -200,6 +417,8 @@ mod tests {
             ]),
             table: None,
             where_clause: None,
+            group_by: None,
+            having: None,
             order_by: None,
             limit: None,
             offset: None,
@@ -213,6 +432,7 @@ mod tests {
         let s = "SELECT abc, def, ghi FROM big_table;";
         let mut parser = Parser::new(s);
         let expected_query = SelectQuery {
+            distinct: false,
             columns: ExpressionList(vec![
                 Expression::Identifier("abc"),
                 Expression::Identifier("def"),
@@ -220,6 +440,8 @@ mod tests {
             ]),
             table: Some("big_table"),
             where_clause: None,
+            group_by: None,
+            having: None,
             order_by: None,
             limit: None,
             offset: None,
@@ -232,34 +454,43 @@ mod tests {
     fn test_parse_select_query_with_from_table_and_where_clause() {
         let s = "SELECT abc, def, ghi FROM some_table WHERE abc < def;";
         let mut parser = Parser::new(s);
-        let expected_query = SelectQuery {
-            columns: ExpressionList(vec![
-                Expression::Identifier("abc"),
-                Expression::Identifier("def"),
-                Expression::Identifier("ghi"),
-            ]),
-            table: Some("some_table"),
-            where_clause: Some(Expression::BinaryOp((
-                Box::new(Expression::Identifier("abc")),
-                Op::LessThan,
-                Box::new(Expression::Identifier("def")),
-            ))),
-            order_by: None,
-            limit: None,
-            offset: None,
-        };
+        let expected_query = SelectQueryBuilder::new()
+            .column(col("abc"))
+            .column(col("def"))
+            .column(col("ghi"))
+            .from("some_table")
+            .filter(col("abc").lt(col("def")))
+            .build()
+            .unwrap();
         let expected = Select(expected_query);
         assert_eq!(Ok(expected), parser.stmt());
     }
 
+    #[test]
+    fn test_float_literals_display_with_fractional_digit() {
+        for (s, value) in [("SELECT 1.0;", 1.0), ("SELECT 1.5;", 1.5), ("SELECT 100.0;", 100.0)] {
+            let got = Parser::new(s).stmt().unwrap();
+            let Select(query) = &got else {
+                panic!("expected SELECT, got {got:?}");
+            };
+            assert_eq!(query.columns, ExpressionList(vec![Expression::from(value)]));
+            assert_eq!(got.to_string(), s);
+        }
+        assert_eq!(Expression::from(1).to_string(), "1");
+        assert_eq!(Expression::from(1.0).to_string(), "1.0");
+    }
+
     #[test]
     fn test_parse_select_query_without_from() {
         let s = "SELECT 3 WHERE 1;";
         let mut parser = Parser::new(s);
         let expected_query = SelectQuery {
+            distinct: false,
             columns: ExpressionList(vec![Expression::from(3)]),
             table: None,
             where_clause: Some(Expression::from(1)),
+            group_by: None,
+            having: None,
             order_by: None,
             limit: None,
             offset: None,
@@ -286,14 +517,98 @@ mod tests {
         assert_eq!(expected, parser.stmt());
     }
 
+    #[test]
+    fn test_identifiers_may_contain_digits_and_underscores() {
+        let s = "SELECT col_1, _tmp FROM a1b2 WHERE _tmp > col_1;";
+        let expected = SelectQueryBuilder::new()
+            .column(col("col_1"))
+            .column(col("_tmp"))
+            .from("a1b2")
+            .filter(col("_tmp").gt(col("col_1")))
+            .build()
+            .unwrap();
+        assert_eq!(Parser::new(s).stmt(), Ok(Select(expected)));
+    }
+
+    #[test]
+    fn test_quoted_identifiers_may_be_keywords() {
+        let s = "SELECT `from` FROM `my table` WHERE `select` > 1;";
+        let expected = SelectQueryBuilder::new()
+            .column(col("from"))
+            .from("my table")
+            .filter(col("select").gt(1))
+            .build()
+            .unwrap();
+        assert_eq!(Parser::new(s).stmt(), Ok(Select(expected)));
+
+        let s = r#"SELECT "order", "first name" FROM "my table";"#;
+        let expected = SelectQueryBuilder::new()
+            .column(col("order"))
+            .column(col("first name"))
+            .from("my table")
+            .build()
+            .unwrap();
+        assert_eq!(Parser::new(s).stmt(), Ok(Select(expected)));
+    }
+
+    #[test]
+    fn test_clauses_present() {
+        let Select(query) = Parser::new("SELECT 1;").stmt().unwrap() else { unreachable!() };
+        assert_eq!(query.clauses_present(), ClauseMask::empty());
+        assert!(query.clauses_present().is_empty());
+
+        let s = "SELECT a, COUNT(*) FROM t WHERE a > 1 GROUP BY a HAVING COUNT(*) > 1 \
+                 ORDER BY a LIMIT 10 OFFSET 5;";
+        let Select(query) = Parser::new(s).stmt().unwrap() else { unreachable!() };
+        let mask = query.clauses_present();
+        assert_eq!(mask.bits(), 0b111_1111);
+        assert!(mask.contains(ClauseMask::GROUP_BY | ClauseMask::HAVING));
+
+        let s = "SELECT a FROM t ORDER BY a;";
+        let Select(query) = Parser::new(s).stmt().unwrap() else { unreachable!() };
+        assert_eq!(query.clauses_present(), ClauseMask::FROM | ClauseMask::ORDER_BY);
+        assert!(!query.clauses_present().contains(ClauseMask::WHERE));
+    }
+
+    #[test]
+    fn test_clause_typos_list_the_clauses_that_may_follow() {
+        let s = "SELECT a FROM t LIMT 5;";
+        let kind = SQLErrorKind::ExpectedOneOf {
+            expected: &["WHERE", "GROUP BY", "HAVING", "ORDER BY", "LIMIT", "OFFSET", "';'"],
+            got: TokenKind::Identifier("LIMT"),
+        };
+        assert_eq!(Parser::new(s).stmt(), Err(SQLError::new(kind, 16)));
+
+        let s = "SELECT a FROM t ORDER BY a GROUP BY a;";
+        let kind = SQLErrorKind::ExpectedOneOf {
+            expected: &["LIMIT", "OFFSET", "';'"],
+            got: TokenKind::Keyword(Keyword::Group),
+        };
+        let err = SQLError::new(kind, s.find("GROUP").unwrap());
+        assert_eq!(Parser::new(s).stmt(), Err(err));
+        assert_eq!(err.kind.to_string(), "Expected one of LIMIT, OFFSET, ';' but found GROUP");
+
+        let s = "SELECT a DESC;";
+        let kind = SQLErrorKind::ExpectedOneOf {
+            expected: &[
+                "FROM", "WHERE", "GROUP BY", "HAVING", "ORDER BY", "LIMIT", "OFFSET", "';'",
+            ],
+            got: TokenKind::Keyword(Keyword::Desc),
+        };
+        assert_eq!(Parser::new(s).stmt(), Err(SQLError::new(kind, 9)));
+    }
+
     #[test]
     fn test_parse_select_query_with_order_by() {
         let s = "SELECT foo FROM bar WHERE baz ORDER BY qax, quux DESC;";
         let mut parser = Parser::new(s);
         let expected_query = SelectQuery {
+            distinct: false,
             columns: ExpressionList(vec![Expression::Identifier("foo")]),
             table: Some("bar"),
             where_clause: Some(Expression::Identifier("baz")),
+            group_by: None,
+            having: None,
             order_by: Some(OrderBy {
                 terms: vec![
                     OrderByTerm { column: "qax", order: None },
@@ -309,9 +624,12 @@ mod tests {
         let s = "SELECT foo FROM bar WHERE baz ORDER BY qax ASC;";
         let mut parser = Parser::new(s);
         let expected_query = SelectQuery {
+            distinct: false,
             columns: ExpressionList(vec![Expression::Identifier("foo")]),
             table: Some("bar"),
             where_clause: Some(Expression::Identifier("baz")),
+            group_by: None,
+            having: None,
             order_by: Some(OrderBy {
                 terms: vec![OrderByTerm { column: "qax", order: Some(Ordering::Ascending) }],
             }),
@@ -322,6 +640,88 @@ mod tests {
         assert_eq!(Ok(expected), parser.stmt());
     }
 
+    #[test]
+    fn test_order_by_directions_are_per_term() {
+        let terms = |s| match Parser::new(s).stmt() {
+            Ok(Select(SelectQuery { order_by: Some(order_by), .. })) => order_by.terms,
+            other => panic!("expected SELECT with ORDER BY, got {other:?}"),
+        };
+        let term = |column, order| OrderByTerm { column, order };
+
+        let s = "SELECT a FROM t ORDER BY a ASC, b DESC, c;";
+        let expected = vec![
+            term("a", Some(Ordering::Ascending)),
+            term("b", Some(Ordering::Descending)),
+            term("c", None),
+        ];
+        assert_eq!(terms(s), expected);
+        assert_eq!(Parser::new(s).stmt().unwrap().to_string(), s);
+
+        assert_eq!(terms("SELECT a FROM t ORDER BY a, b;"), vec![term("a", None), term("b", None)]);
+        assert_eq!(
+            terms("SELECT a FROM t ORDER BY b DESC;"),
+            vec![term("b", Some(Ordering::Descending))]
+        );
+    }
+
+    #[test]
+    fn test_parse_select_query_with_group_by_and_having() {
+        let s = "SELECT dept, COUNT(*) FROM staff WHERE active GROUP BY dept HAVING COUNT(*) > 2 \
+                 ORDER BY dept;";
+        let mut parser = Parser::new(s);
+        let count = || aggregate(AggregateFunctionKind::Count, Expression::Wildcard);
+        let expected_query = SelectQueryBuilder::new()
+            .column(col("dept"))
+            .column(count())
+            .from("staff")
+            .filter(col("active"))
+            .group_by(col("dept"))
+            .having(count().gt(2))
+            .order_by("dept", None)
+            .build()
+            .unwrap();
+        let got = parser.stmt();
+        assert_eq!(Ok(Select(expected_query)), got);
+        assert_eq!(got.unwrap().to_string(), s.split_whitespace().collect::<Vec<_>>().join(" "));
+    }
+
+    #[test]
+    fn test_parse_select_distinct_wildcard() {
+        let s = "SELECT DISTINCT * FROM users;";
+        let got = Parser::new(s).stmt();
+        let Ok(Select(query)) = &got else {
+            panic!("expected SELECT DISTINCT to parse, got {got:?}");
+        };
+        assert!(query.distinct);
+        assert_eq!(query.columns, ExpressionList(vec![Expression::Wildcard]));
+        assert_eq!(got.unwrap().to_string(), s);
+    }
+
+    #[test]
+    fn test_parse_distinct_aggregate() {
+        let s = "SELECT COUNT(DISTINCT name) FROM users;";
+        let got = Parser::new(s).stmt().unwrap();
+        let Select(query) = &got else {
+            panic!("expected SELECT, got {got:?}");
+        };
+        assert_eq!(
+            query.columns,
+            ExpressionList(vec![Expression::AggregateFunction(AggregateFunction {
+                kind: AggregateFunctionKind::Count,
+                distinct: true,
+                expr: Box::new(Expression::Identifier("name")),
+            })])
+        );
+        assert_eq!(got.to_string(), s);
+    }
+
+    #[test]
+    fn test_parse_count_distinct_wildcard_is_rejected() {
+        let s = "SELECT COUNT(DISTINCT *) FROM users;";
+        let expected = SQLError::new(SQLErrorKind::DistinctWildcard, s.find('*').unwrap());
+        assert_eq!(Err(expected), Parser::new(s).stmt());
+    }
+
     #[test]
     fn test_parse_select_query_rejects_order_by_expression() {
         let s = "SELECT foo FROM bar ORDER BY qax + 1 ASC;";
@@ -334,9 +734,12 @@ mod tests {
         let s = "SELECT foo FROM bar LIMIT 5;";
         let mut parser = Parser::new(s);
         let expected_query = SelectQuery {
+            distinct: false,
             columns: ExpressionList(vec![Expression::Identifier("foo")]),
             table: Some("bar"),
             where_clause: None,
+            group_by: None,
+            having: None,
             order_by: None,
             limit: Some(5),
             offset: None,
@@ -347,9 +750,12 @@ mod tests {
         let s = "SELECT foo FROM bar WHERE baz ORDER BY qux LIMIT 10;";
         let mut parser = Parser::new(s);
         let expected_query = SelectQuery {
+            distinct: false,
             columns: ExpressionList(vec![Expression::Identifier("foo")]),
             table: Some("bar"),
             where_clause: Some(Expression::Identifier("baz")),
+            group_by: None,
+            having: None,
             order_by: Some(OrderBy { terms: vec![OrderByTerm { column: "qux", order: None }] }),
             limit: Some(10),
             offset: None,
@@ -363,14 +769,73 @@ mod tests {
         assert_eq!(Err(expected), parser.stmt());
     }
 
+    #[test]
+    fn test_limit_with_comma_sets_offset_and_count() {
+        let select = |s| match Parser::new(s).stmt() {
+            Ok(Select(query)) => Ok((query.limit, query.offset)),
+            Ok(other) => panic!("expected SELECT, got {other:?}"),
+            Err(err) => Err(err),
+        };
+        assert_eq!(select("SELECT a FROM t LIMIT 10, 20;"), Ok((Some(20), Some(10))));
+        assert_eq!(select("SELECT a FROM t LIMIT 5;"), Ok((Some(5), None)));
+
+        let s = "SELECT a FROM t LIMIT 10, 20 OFFSET 5;";
+        let expected = SQLError::new(SQLErrorKind::DuplicateOffset, s.find("OFFSET").unwrap());
+        assert_eq!(select(s), Err(expected));
+    }
+
+    #[test]
+    fn test_offset_must_follow_limit() {
+        let select = |s| match Parser::new(s).stmt() {
+            Ok(Select(query)) => Ok((query.limit, query.offset)),
+            Ok(other) => panic!("expected SELECT, got {other:?}"),
+            Err(err) => Err(err),
+        };
+        assert_eq!(select("SELECT a FROM t LIMIT 10 OFFSET 5;"), Ok((Some(10), Some(5))));
+        assert_eq!(select("SELECT a FROM t OFFSET 5;"), Ok((None, Some(5))));
+
+        let s = "SELECT a FROM t OFFSET 5 LIMIT 10;";
+        let expected = SQLError::new(SQLErrorKind::OffsetBeforeLimit, s.find("LIMIT").unwrap());
+        assert_eq!(select(s), Err(expected));
+
+        for s in [
+            "SELECT a FROM t LIMIT 1 LIMIT 2;",
+            "SELECT a FROM t LIMIT 1 OFFSET 2 LIMIT 3;",
+            "SELECT a FROM t LIMIT 1, 2 LIMIT 3;",
+        ] {
+            let expected = SQLError::new(SQLErrorKind::DuplicateLimit, s.rfind("LIMIT").unwrap());
+            assert_eq!(select(s), Err(expected), "{s}");
+        }
+    }
+
+    #[test]
+    fn test_parse_select_query_with_explicitly_positive_limit() {
+        let s = "SELECT foo LIMIT +5 OFFSET +2;";
+        let Ok(Select(query)) = Parser::new(s).stmt() else {
+            panic!("expected SELECT to parse");
+        };
+        assert_eq!(query.limit, Some(5));
+        assert_eq!(query.offset, Some(2));
+
+        let s = "SELECT foo LIMIT +-5;";
+        let expected = SQLError::new(
+            SQLErrorKind::ExpectedInteger { got: TokenKind::Minus },
+            s.find('-').unwrap(),
+        );
+        assert_eq!(Err(expected), Parser::new(s).stmt());
+    }
+
     #[test]
     fn test_parse_select_query_with_offset() {
         let s = "SELECT foo FROM bar OFFSET 5;";
         let mut parser = Parser::new(s);
         let expected_query = SelectQuery {
+            distinct: false,
             columns: ExpressionList(vec![Expression::Identifier("foo")]),
             table: Some("bar"),
             where_clause: None,
+            group_by: None,
+            having: None,
             order_by: None,
             limit: None,
             offset: Some(5),
@@ -381,9 +846,12 @@ mod tests {
         let s = "SELECT foo FROM bar LIMIT 10 OFFSET 5;";
         let mut parser = Parser::new(s);
         let expected_query = SelectQuery {
+            distinct: false,
             columns: ExpressionList(vec![Expression::Identifier("foo")]),
             table: Some("bar"),
             where_clause: None,
+            group_by: None,
+            having: None,
             order_by: None,
             limit: Some(10),
             offset: Some(5),
@@ -397,10 +865,130 @@ mod tests {
         let s = "SELECT col FROM table;";
         let mut parser = Parser::new(s);
         let got = parser.stmt();
-        let expected = SQLError {
-            kind: SQLErrorKind::ExpectedIdentifier { got: TokenKind::Keyword(Keyword::Table) },
-            pos: 21,
-        };
+        let expected = SQLError::spanned(
+            SQLErrorKind::ReservedKeywordAsIdentifier { keyword: "table" },
+            16,
+            21,
+        );
         assert_eq!(Err(expected), got);
     }
+
+    #[test]
+    fn test_reserved_keyword_error_keeps_source_spelling() {
+        let s = "SELECT col FROM sElEcT;";
+        let err = Parser::new(s).stmt().unwrap_err();
+        assert_eq!(err.kind, SQLErrorKind::ReservedKeywordAsIdentifier { keyword: "sElEcT" });
+        assert_eq!(err.kind.to_string(), "Expected identifier, got reserved keyword 'sElEcT'");
+    }
+
+    #[test]
+    fn test_parse_select_with_in_subquery() {
+        let s = "SELECT name FROM users WHERE id IN (SELECT user_id FROM admins);";
+        let mut parser = Parser::new(s);
+        let subquery = SelectQuery {
+            distinct: false,
+            columns: ExpressionList(vec![Expression::Identifier("user_id")]),
+            table: Some("admins"),
+            where_clause: None,
+            group_by: None,
+            having: None,
+            order_by: None,
+            limit: None,
+            offset: None,
+        };
+        let expected_query = SelectQuery {
+            distinct: false,
+            columns: ExpressionList(vec![Expression::Identifier("name")]),
+            table: Some("users"),
+            where_clause: Some(Expression::BinaryOp((
+                Box::new(Expression::Identifier("id")),
+                Op::In,
+                Box::new(Expression::Subquery(Box::new(subquery))),
+            ))),
+            group_by: None,
+            having: None,
+            order_by: None,
+            limit: None,
+            offset: None,
+        };
+        let expected = Select(expected_query);
+        let got = parser.stmt();
+        assert_eq!(Ok(expected), got);
+        assert_eq!(got.unwrap().to_string(), s);
+    }
+
+    #[test]
+    fn test_parse_select_with_scalar_subquery() {
+        let s = "SELECT name FROM products WHERE price > (SELECT AVG(price) FROM products);";
+        let mut parser = Parser::new(s);
+        let subquery = SelectQuery {
+            distinct: false,
+            columns: ExpressionList(vec![Expression::AggregateFunction(AggregateFunction {
+                kind: AggregateFunctionKind::Avg,
+                distinct: false,
+                expr: Box::new(Expression::Identifier("price")),
+            })]),
+            table: Some("products"),
+            where_clause: None,
+            group_by: None,
+            having: None,
+            order_by: None,
+            limit: None,
+            offset: None,
+        };
+        let expected_query = SelectQuery {
+            distinct: false,
+            columns: ExpressionList(vec![Expression::Identifier("name")]),
+            table: Some("products"),
+            where_clause: Some(Expression::BinaryOp((
+                Box::new(Expression::Identifier("price")),
+                Op::GreaterThan,
+                Box::new(Expression::Subquery(Box::new(subquery))),
+            ))),
+            group_by: None,
+            having: None,
+            order_by: None,
+            limit: None,
+            offset: None,
+        };
+        let expected = Select(expected_query);
+        let got = parser.stmt();
+        assert_eq!(Ok(expected), got);
+        assert_eq!(got.unwrap().to_string(), s);
+    }
+
+    #[test]
+    fn test_parse_select_with_unterminated_subquery() {
+        let s = "SELECT name FROM users WHERE id IN (SELECT user_id FROM admins;";
+        let mut parser = Parser::new(s);
+        let expected = SQLError::new(SQLErrorKind::UnclosedParenthesis, s.find('(').unwrap());
+        assert_eq!(Err(expected), parser.stmt());
+    }
+
+    #[test]
+    fn test_parse_select_in_requires_subquery() {
+        let s = "SELECT name FROM users WHERE id IN 5;";
+        let mut parser = Parser::new(s);
+        let expected = SQLError::new(
+            SQLErrorKind::ExpectedOther { expected: TokenKind::LeftParen },
+            s.find("IN").unwrap(),
+        );
+        assert_eq!(Err(expected), parser.stmt());
+    }
+
+    #[test]
+    fn test_select_is_scalar() {
+        let scalar = |s| match Parser::new(s).stmt() {
+            Ok(Select(query)) => query.is_scalar(),
+            other => panic!("expected SELECT, got {other:?}"),
+        };
+
+        assert!(scalar("SELECT 1+1;"));
+        assert!(!scalar("SELECT a FROM t;"));
+        assert!(!scalar("SELECT COUNT(*);"));
+        assert!(!scalar("SELECT 1 + MAX(a);"));
+        assert!(!scalar("SELECT 1, 2;"));
+        assert!(!scalar("SELECT (SELECT a FROM t);"));
+        assert!(!scalar("SELECT -(SELECT 1);"));
+    }
 }
@@ -6,10 +6,11 @@ use crate::sql_parser::{
         token::Token,
         token_kind::{Keyword, TokenKind},
     },
-    parser::{Parser, expr::Expression},
+    parser::{Parser, expr::Expression, ident::quote_ident},
 };
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Assignment<'a> {
     pub column: &'a str,
     pub expression: Expression<'a>,
@@ -17,11 +18,12 @@ pub struct Assignment<'a> {
 
 impl Display for Assignment<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{} = {}", self.column, self.expression)
+        write!(f, "{} = {}", quote_ident(self.column), self.expression)
     }
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct AssignmentList<'a>(pub Vec<Assignment<'a>>);
 
 impl Display for AssignmentList<'_> {
@@ -32,6 +34,7 @@ impl Display for AssignmentList<'_> {
 }
 
 #[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct UpdateQuery<'a> {
     pub table: &'a str,
     pub assignments: AssignmentList<'a>,
@@ -40,7 +43,7 @@ pub struct UpdateQuery<'a> {
 
 impl Display for UpdateQuery<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "UPDATE {} SET {}", self.table, self.assignments)?;
+        write!(f, "UPDATE {} SET {}", quote_ident(self.table), self.assignments)?;
 
         if let Some(ref where_clause) = self.where_clause {
             write!(f, " WHERE {}", where_clause)?;
@@ -71,7 +74,7 @@ impl<'a> Parser<'a> {
                 None
             };
 
-        self.lexer.expect_token(TokenKind::Semicolon)?;
+        self.expect_statement_end()?;
         Ok(UpdateQuery { table, assignments, where_clause })
     }
 }
@@ -90,7 +93,7 @@ mod tests {
             assignments: AssignmentList(vec![Assignment {
                 column: "name",
                 expression: Expression::Literal(crate::sql_parser::parser::expr::Literal::String(
-                    "Ada",
+                    "Ada".into(),
                 )),
             }]),
             where_clause: None,
@@ -109,7 +112,7 @@ mod tests {
                 Assignment {
                     column: "name",
                     expression: Expression::Literal(
-                        crate::sql_parser::parser::expr::Literal::String("Ada"),
+                        crate::sql_parser::parser::expr::Literal::String("Ada".into()),
                     ),
                 },
                 Assignment { column: "active", expression: Expression::from(true) },
@@ -0,0 +1,184 @@
+use std::fmt::Display;
+
+use crate::sql_parser::{
+    error::{SQLError, SQLErrorKind},
+    lexer::token_kind::{Keyword, TokenKind},
+    parser::{
+        Parser,
+        ident::quote_ident,
+        stmt::{Statement, select::SelectQuery},
+    },
+};
+
+/// A named `SELECT` bound by a `WITH` clause.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CommonTableExpression<'a> {
+    pub name: &'a str,
+    pub query: SelectQuery<'a>,
+}
+
+impl Display for CommonTableExpression<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} AS (", quote_ident(self.name))?;
+        self.query.fmt_body(f)?;
+        write!(f, ")")
+    }
+}
+
+/// A statement prefixed by one or more common table expressions.
+///
+/// Recursive CTEs are not supported; each query is parsed as a plain `SELECT`.
+/// The prefixed statement is a `SELECT`, `INSERT`, `UPDATE` or `DELETE`.
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WithQuery<'a> {
+    pub ctes: Vec<CommonTableExpression<'a>>,
+    pub statement: Box<Statement<'a>>,
+}
+
+impl Display for WithQuery<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let ctes = self.ctes.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+        write!(f, "WITH {ctes} {}", self.statement)
+    }
+}
+
+impl<'a> Parser<'a> {
+    pub fn parse_with_query(&mut self) -> Result<WithQuery<'a>, SQLError<'a>> {
+        let ctes = self.parse_comma_separated_list(|p| p.parse_common_table_expression())?;
+        let token = self.lexer.next().ok_or_else(|| self.lexer.unexpected_end())??;
+        let statement = match token.kind {
+            TokenKind::Keyword(Keyword::Select) => Statement::Select(self.parse_select_query()?),
+            TokenKind::Keyword(Keyword::Insert) => Statement::Insert(self.parse_insert_query()?),
+            TokenKind::Keyword(Keyword::Update) => Statement::Update(self.parse_update_query()?),
+            TokenKind::Keyword(Keyword::Delete) => Statement::Delete(self.parse_delete_query()?),
+            got => {
+                let expected = &["SELECT", "INSERT", "UPDATE", "DELETE"];
+                return Err(SQLError::new(
+                    SQLErrorKind::ExpectedOneOf { expected, got },
+                    token.offset,
+                ));
+            }
+        };
+        Ok(WithQuery { ctes, statement: Box::new(statement) })
+    }
+
+    fn parse_common_table_expression(&mut self) -> Result<CommonTableExpression<'a>, SQLError<'a>> {
+        let name = self.parse_identifier()?;
+        self.lexer.expect_token(TokenKind::Keyword(Keyword::As))?;
+        self.lexer.expect_token(TokenKind::LeftParen)?;
+        self.lexer.expect_token(TokenKind::Keyword(Keyword::Select))?;
+        let query = self.parse_select_body()?;
+        self.lexer.expect_token(TokenKind::RightParen)?;
+        Ok(CommonTableExpression { name, query })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_parser::parser::{
+        expr::{Expression, Literal},
+        op::Op,
+        stmt::lists::ExpressionList,
+    };
+
+    #[test]
+    fn test_parse_with_two_ctes() {
+        let s = "WITH active AS (SELECT * FROM users WHERE active), \
+                 admins AS (SELECT id FROM active WHERE role == 'admin') \
+                 SELECT id FROM admins;";
+        let mut parser = Parser::new(s);
+        let expected = WithQuery {
+            ctes: vec![
+                CommonTableExpression {
+                    name: "active",
+                    query: SelectQuery {
+                        distinct: false,
+                        columns: ExpressionList(vec![Expression::Wildcard]),
+                        table: Some("users"),
+                        where_clause: Some(Expression::Identifier("active")),
+                        group_by: None,
+                        having: None,
+                        order_by: None,
+                        limit: None,
+                        offset: None,
+                    },
+                },
+                CommonTableExpression {
+                    name: "admins",
+                    query: SelectQuery {
+                        distinct: false,
+                        columns: ExpressionList(vec![Expression::Identifier("id")]),
+                        table: Some("active"),
+                        where_clause: Some(Expression::BinaryOp((
+                            Box::new(Expression::Identifier("role")),
+                            Op::EqualsEquals,
+                            Box::new(Expression::Literal(Literal::String("admin".into()))),
+                        ))),
+                        group_by: None,
+                        having: None,
+                        order_by: None,
+                        limit: None,
+                        offset: None,
+                    },
+                },
+            ],
+            statement: Box::new(Statement::Select(SelectQuery {
+                distinct: false,
+                columns: ExpressionList(vec![Expression::Identifier("id")]),
+                table: Some("admins"),
+                where_clause: None,
+                group_by: None,
+                having: None,
+                order_by: None,
+                limit: None,
+                offset: None,
+            })),
+        };
+
+        let got = parser.stmt();
+        assert_eq!(Ok(Statement::With(expected)), got);
+
+        let displayed = got.unwrap().to_string();
+        assert_eq!(
+            displayed,
+            "WITH active AS (SELECT * FROM users WHERE active), \
+             admins AS (SELECT id FROM active WHERE role == 'admin') \
+             SELECT id FROM admins;"
+        );
+        assert!(Parser::new(&displayed).stmt().is_ok());
+    }
+
+    #[test]
+    fn test_parse_with_missing_as() {
+        let s = "WITH active (SELECT * FROM users) SELECT name FROM active;";
+        let mut parser = Parser::new(s);
+        let expected = SQLError::new(
+            SQLErrorKind::UnexpectedTokenKind {
+                expected: TokenKind::Keyword(Keyword::As),
+                got: TokenKind::LeftParen,
+            },
+            s.find('(').unwrap(),
+        );
+        assert_eq!(Err(expected), parser.stmt());
+    }
+
+    #[test]
+    fn test_with_body_must_be_dml() {
+        let expected = &["SELECT", "INSERT", "UPDATE", "DELETE"];
+        for (s, got) in [
+            ("WITH a AS (SELECT 1) WITH b AS (SELECT 2) SELECT 3;", Keyword::With),
+            ("WITH a AS (SELECT 1) CREATE TABLE t (id INT PRIMARY KEY);", Keyword::Create),
+            ("WITH a AS (SELECT 1) EXPLAIN SELECT 1;", Keyword::Explain),
+            ("WITH a AS (SELECT 1) VALUES (1);", Keyword::Values),
+        ] {
+            let kind = SQLErrorKind::ExpectedOneOf { expected, got: TokenKind::Keyword(got) };
+            assert_eq!(Parser::new(s).stmt(), Err(SQLError::new(kind, 21)), "{s}");
+        }
+
+        let s = "WITH a AS (SELECT x FROM t) DELETE FROM a WHERE x > 1;";
+        assert!(matches!(Parser::new(s).stmt(), Ok(Statement::With(_))));
+    }
+}
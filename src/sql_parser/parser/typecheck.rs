@@ -0,0 +1,355 @@
+use std::fmt::Display;
+
+use crate::sql_parser::{
+    lexer::token_kind::NumberKind,
+    parser::{
+        expr::{AggregateFunction, AggregateFunctionKind, Expression, Literal},
+        ident::{IdentCmp, ident_eq},
+        op::Op,
+        stmt::create_table::{Column, ColumnType},
+    },
+};
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum TypeErrorKind<'a> {
+    /// No column in the schema has this name.
+    UnknownColumn { column: &'a str },
+    /// The operator is not defined for an operand of this type, as in `name * 2`.
+    InvalidOperand { op: Op, operand: ColumnType },
+    /// The operands cannot be compared with each other, as in `name == 1`.
+    OperandMismatch { op: Op, left: ColumnType, right: ColumnType },
+    /// The aggregate is not defined for an argument of this type, as in `SUM(name)`.
+    InvalidAggregateArgument { function: AggregateFunctionKind, argument: ColumnType },
+    /// The expression has no single column type: a bare `NULL`, `*` or a subquery.
+    Untyped,
+}
+
+impl Display for TypeErrorKind<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeErrorKind::UnknownColumn { column } => write!(f, "Unknown column {column}"),
+            TypeErrorKind::InvalidOperand { op, operand } => {
+                write!(f, "Operator {} does not apply to {operand}", op.to_string().trim())
+            }
+            TypeErrorKind::OperandMismatch { op, left, right } => {
+                write!(f, "Operator {op} cannot compare {left} with {right}")
+            }
+            TypeErrorKind::InvalidAggregateArgument { function, argument } => {
+                write!(f, "Aggregate function {function} does not apply to {argument}")
+            }
+            TypeErrorKind::Untyped => write!(f, "Expression has no column type"),
+        }
+    }
+}
+
+/// An error raised while inferring the type of an expression.
+///
+/// As with [`ConstEvalError`](crate::sql_parser::parser::eval::ConstEvalError), the error
+/// is positioned by the rendered sub-expression that failed, so an unknown
+/// column is reported as that identifier.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TypeError<'a> {
+    pub kind: TypeErrorKind<'a>,
+    pub expression: String,
+}
+
+impl<'a> TypeError<'a> {
+    fn new(kind: TypeErrorKind<'a>, expression: &Expression<'_>) -> Self {
+        Self { kind, expression: expression.to_string() }
+    }
+}
+
+impl Display for TypeError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} in `{}`", self.kind, self.expression)
+    }
+}
+
+impl std::error::Error for TypeError<'_> {}
+
+/// Infers the column type `expr` produces when evaluated against rows of `schema`.
+///
+/// Identifiers resolve case-insensitively to their declared types. Arithmetic
+/// takes numeric operands and promotes to the wider of `INT`, `BIGINT` and
+/// `FLOAT`; comparisons take two numbers, two strings or two values of the same
+/// other type and produce `BOOLEAN`. `NULL` adopts the type of the operand it
+/// is combined with. `COUNT` is `INT`, `AVG` and `STDDEV` are `FLOAT`, and
+/// `SUM`, `MIN` and `MAX` keep their argument's type.
+pub fn infer_type<'a>(
+    expr: &Expression<'a>,
+    schema: &[Column<'_>],
+) -> Result<ColumnType, TypeError<'a>> {
+    infer(expr, schema)?.ok_or_else(|| TypeError::new(TypeErrorKind::Untyped, expr))
+}
+
+/// Infers a type, using `None` for `NULL`, which fits wherever any type would.
+fn infer<'a>(
+    expr: &Expression<'a>,
+    schema: &[Column<'_>],
+) -> Result<Option<ColumnType>, TypeError<'a>> {
+    let column_type = match expr {
+        Expression::Literal(Literal::String(_)) => ColumnType::Text(None),
+        Expression::Literal(Literal::Number(NumberKind::Integer(_))) => ColumnType::Int,
+        Expression::Literal(Literal::Number(NumberKind::Float(_))) => ColumnType::Float,
+        Expression::Literal(Literal::Boolean(_)) => ColumnType::Boolean,
+        Expression::Literal(Literal::Null) => return Ok(None),
+        Expression::Identifier(name) => schema
+            .iter()
+            .find(|column| ident_eq(column.name, name, IdentCmp::CaseInsensitive))
+            .map(|column| column.column_type)
+            .ok_or_else(|| TypeError::new(TypeErrorKind::UnknownColumn { column: name }, expr))?,
+        Expression::UnaryOp((op, operand)) => {
+            let Some(operand) = infer(operand, schema)? else {
+                return Ok(None);
+            };
+            let valid = match op {
+                Op::Not => operand == ColumnType::Boolean,
+                _ => is_numeric(operand),
+            };
+            if !valid {
+                return Err(TypeError::new(
+                    TypeErrorKind::InvalidOperand { op: *op, operand },
+                    expr,
+                ));
+            }
+            operand
+        }
+        Expression::BinaryOp((left, Op::In, right)) => {
+            infer(left, schema)?;
+            // The subquery's columns come from its own table, which `schema` does not describe.
+            if !matches!(**right, Expression::Subquery(_)) {
+                infer(right, schema)?;
+            }
+            ColumnType::Boolean
+        }
+        Expression::BinaryOp((left, op, right)) => {
+            let left = infer(left, schema)?;
+            let right = infer(right, schema)?;
+            return binary_type(*op, left, right).map_err(|kind| TypeError::new(kind, expr));
+        }
+        Expression::AggregateFunction(AggregateFunction { kind, expr: argument, .. }) => {
+            let argument = match (kind, &**argument) {
+                (AggregateFunctionKind::Count, Expression::Wildcard) => None,
+                _ => infer(argument, schema)?,
+            };
+            let numeric = matches!(
+                kind,
+                AggregateFunctionKind::Sum
+                    | AggregateFunctionKind::Avg
+                    | AggregateFunctionKind::StdDev
+            );
+            if let Some(argument) = argument
+                && numeric
+                && !is_numeric(argument)
+            {
+                let kind = TypeErrorKind::InvalidAggregateArgument { function: *kind, argument };
+                return Err(TypeError::new(kind, expr));
+            }
+            match kind {
+                AggregateFunctionKind::Count => ColumnType::Int,
+                AggregateFunctionKind::Avg | AggregateFunctionKind::StdDev => ColumnType::Float,
+                AggregateFunctionKind::Sum
+                | AggregateFunctionKind::Min
+                | AggregateFunctionKind::Max => return Ok(argument),
+            }
+        }
+        Expression::Cast((operand, data_type)) => {
+            infer(operand, schema)?;
+            *data_type
+        }
+        Expression::Wildcard | Expression::Subquery(_) => {
+            return Err(TypeError::new(TypeErrorKind::Untyped, expr));
+        }
+    };
+    Ok(Some(column_type))
+}
+
+fn binary_type<'a>(
+    op: Op,
+    left: Option<ColumnType>,
+    right: Option<ColumnType>,
+) -> Result<Option<ColumnType>, TypeErrorKind<'a>> {
+    let operand_fits = |operand: ColumnType| match op {
+        Op::And | Op::Or => operand == ColumnType::Boolean,
+        Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Pow => is_numeric(operand),
+        _ => true,
+    };
+    if let Some(operand) = left.into_iter().chain(right).find(|operand| !operand_fits(*operand)) {
+        return Err(TypeErrorKind::InvalidOperand { op, operand });
+    }
+
+    match op {
+        Op::And | Op::Or => Ok(Some(ColumnType::Boolean)),
+        Op::Add | Op::Sub | Op::Mul | Op::Div | Op::Pow => Ok(match (left, right) {
+            (Some(left), Some(right)) => Some(wider_numeric(left, right)),
+            (operand, None) | (None, operand) => operand,
+        }),
+        _ => match (left, right) {
+            (Some(left), Some(right)) if !comparable(left, right) => {
+                Err(TypeErrorKind::OperandMismatch { op, left, right })
+            }
+            _ => Ok(Some(ColumnType::Boolean)),
+        },
+    }
+}
+
+fn is_numeric(column_type: ColumnType) -> bool {
+    matches!(column_type, ColumnType::Int | ColumnType::BigInt | ColumnType::Float)
+}
+
+fn is_string(column_type: ColumnType) -> bool {
+    matches!(column_type, ColumnType::Text(_) | ColumnType::Varchar(_))
+}
+
+fn comparable(left: ColumnType, right: ColumnType) -> bool {
+    (is_numeric(left) && is_numeric(right))
+        || (is_string(left) && is_string(right))
+        || left == right
+}
+
+fn wider_numeric(left: ColumnType, right: ColumnType) -> ColumnType {
+    match (left, right) {
+        (ColumnType::Float, _) | (_, ColumnType::Float) => ColumnType::Float,
+        (ColumnType::BigInt, _) | (_, ColumnType::BigInt) => ColumnType::BigInt,
+        _ => ColumnType::Int,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_parser::parser::{Parser, stmt::create_table::parse_create_table};
+
+    const SCHEMA: &str = "CREATE TABLE t (i INT PRIMARY KEY, b BIGINT, f FLOAT, \
+                          s TEXT, v VARCHAR(8), flag BOOLEAN, data BLOB);";
+
+    fn infer_str(source: &str) -> Result<ColumnType, TypeError<'_>> {
+        let schema = parse_create_table(SCHEMA).unwrap().columns;
+        infer_type(&Parser::new(source).expr().unwrap(), &schema)
+    }
+
+    #[test]
+    fn test_infer_literals_and_columns() {
+        assert_eq!(infer_str("1"), Ok(ColumnType::Int));
+        assert_eq!(infer_str("1.5"), Ok(ColumnType::Float));
+        assert_eq!(infer_str("'x'"), Ok(ColumnType::Text(None)));
+        assert_eq!(infer_str("TRUE"), Ok(ColumnType::Boolean));
+        assert_eq!(infer_str("V"), Ok(ColumnType::Varchar(Some(8))));
+        assert_eq!(infer_str("data"), Ok(ColumnType::Blob));
+        assert_eq!(infer_str("s::INT"), Ok(ColumnType::Int));
+    }
+
+    #[test]
+    fn test_arithmetic_matrix() {
+        let numeric = [
+            ("i", "i", ColumnType::Int),
+            ("i", "b", ColumnType::BigInt),
+            ("b", "i", ColumnType::BigInt),
+            ("i", "f", ColumnType::Float),
+            ("f", "b", ColumnType::Float),
+            ("i", "NULL", ColumnType::Int),
+            ("NULL", "f", ColumnType::Float),
+        ];
+        for op in ["+", "-", "*", "/", "^"] {
+            for (left, right, expected) in numeric {
+                let source = format!("{left} {op} {right}");
+                assert_eq!(infer_str(&source), Ok(expected), "{source}");
+            }
+            for (column, operand) in [
+                ("s", ColumnType::Text(None)),
+                ("v", ColumnType::Varchar(Some(8))),
+                ("flag", ColumnType::Boolean),
+                ("data", ColumnType::Blob),
+            ] {
+                let source = format!("i {op} {column}");
+                let err = infer_str(&source).unwrap_err();
+                assert!(
+                    matches!(err.kind, TypeErrorKind::InvalidOperand { operand: got, .. } if got == operand),
+                    "{source}: {err}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_comparison_matrix() {
+        let comparable = [
+            ("i", "f"),
+            ("b", "1"),
+            ("s", "v"),
+            ("s", "'x'"),
+            ("flag", "TRUE"),
+            ("data", "data"),
+            ("s", "NULL"),
+        ];
+        let mismatched = [("i", "s"), ("s", "flag"), ("flag", "1"), ("data", "v")];
+        for op in ["==", "!=", "<", ">", "<=", ">="] {
+            for (left, right) in comparable {
+                let source = format!("{left} {op} {right}");
+                assert_eq!(infer_str(&source), Ok(ColumnType::Boolean), "{source}");
+            }
+            for (left, right) in mismatched {
+                let source = format!("{left} {op} {right}");
+                let err = infer_str(&source).unwrap_err();
+                assert!(
+                    matches!(err.kind, TypeErrorKind::OperandMismatch { .. }),
+                    "{source}: {err}"
+                );
+                assert_eq!(err.expression, source);
+            }
+        }
+    }
+
+    #[test]
+    fn test_logical_operators_require_booleans() {
+        assert_eq!(infer_str("flag AND i > 1 OR NOT flag"), Ok(ColumnType::Boolean));
+        assert_eq!(infer_str("i IN (SELECT x FROM u)"), Ok(ColumnType::Boolean));
+
+        let err = infer_str("flag AND i").unwrap_err();
+        assert_eq!(
+            err.kind,
+            TypeErrorKind::InvalidOperand { op: Op::And, operand: ColumnType::Int }
+        );
+        let err = infer_str("NOT s").unwrap_err();
+        assert_eq!(
+            err.kind,
+            TypeErrorKind::InvalidOperand { op: Op::Not, operand: ColumnType::Text(None) }
+        );
+        assert_eq!(infer_str("-f"), Ok(ColumnType::Float));
+        assert!(infer_str("-flag").is_err());
+    }
+
+    #[test]
+    fn test_aggregate_types() {
+        assert_eq!(infer_str("COUNT(*)"), Ok(ColumnType::Int));
+        assert_eq!(infer_str("COUNT(s)"), Ok(ColumnType::Int));
+        assert_eq!(infer_str("SUM(b)"), Ok(ColumnType::BigInt));
+        assert_eq!(infer_str("AVG(i)"), Ok(ColumnType::Float));
+        assert_eq!(infer_str("STDDEV(f)"), Ok(ColumnType::Float));
+        assert_eq!(infer_str("MAX(s)"), Ok(ColumnType::Text(None)));
+        assert_eq!(infer_str("MIN(i) + 1.0"), Ok(ColumnType::Float));
+
+        let err = infer_str("SUM(s)").unwrap_err();
+        assert_eq!(
+            err.kind,
+            TypeErrorKind::InvalidAggregateArgument {
+                function: AggregateFunctionKind::Sum,
+                argument: ColumnType::Text(None),
+            }
+        );
+    }
+
+    #[test]
+    fn test_unknown_column_names_the_identifier() {
+        let err = infer_str("i + 2 * missing").unwrap_err();
+        assert_eq!(err.kind, TypeErrorKind::UnknownColumn { column: "missing" });
+        assert_eq!(err.to_string(), "Unknown column missing in `missing`");
+    }
+
+    #[test]
+    fn test_untyped_expressions() {
+        assert_eq!(infer_str("NULL").unwrap_err().kind, TypeErrorKind::Untyped);
+        assert_eq!(infer_str("(SELECT 1)").unwrap_err().kind, TypeErrorKind::Untyped);
+        assert_eq!(infer_str("NULL + NULL").unwrap_err().expression, "NULL + NULL");
+    }
+}
@@ -0,0 +1,228 @@
+use std::fmt::Display;
+
+use crate::sql_parser::parser::{
+    expr::{AggregateFunction, AggregateFunctionKind, Expression},
+    stmt::select::SelectQuery,
+    visit::{Visitor, walk_expression},
+};
+
+/// The part of a `SELECT` a [`SemanticError`] was found in.
+///
+/// The AST does not keep source offsets, so errors are positioned by clause and,
+/// for the projection, by the index of the offending select-list item.
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum SelectClause {
+    Projection(usize),
+    Where,
+    Having,
+}
+
+impl Display for SelectClause {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SelectClause::Projection(index) => write!(f, "select list item {}", index + 1),
+            SelectClause::Where => write!(f, "WHERE clause"),
+            SelectClause::Having => write!(f, "HAVING clause"),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub enum SemanticErrorKind<'a> {
+    /// A column is selected next to aggregates without appearing in `GROUP BY`.
+    UngroupedColumn { column: &'a str },
+    /// An aggregate is used to filter rows before grouping.
+    AggregateInWhere { function: AggregateFunctionKind },
+    /// `HAVING` is used without `GROUP BY` or any aggregate to group over.
+    HavingWithoutGrouping,
+}
+
+impl Display for SemanticErrorKind<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SemanticErrorKind::UngroupedColumn { column } => {
+                write!(f, "Column {column} must appear in GROUP BY or be used in an aggregate")
+            }
+            SemanticErrorKind::AggregateInWhere { function } => {
+                write!(f, "Aggregate function {function} is not allowed in WHERE")
+            }
+            SemanticErrorKind::HavingWithoutGrouping => {
+                write!(f, "HAVING requires GROUP BY or an aggregate in the select list")
+            }
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Copy, Clone)]
+pub struct SemanticError<'a> {
+    pub kind: SemanticErrorKind<'a>,
+    pub clause: SelectClause,
+}
+
+impl Display for SemanticError<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Semantic error in {}: {}", self.clause, self.kind)
+    }
+}
+
+impl std::error::Error for SemanticError<'_> {}
+
+/// Collects aggregate calls, without descending into subqueries.
+#[derive(Default)]
+struct AggregateCollector {
+    functions: Vec<AggregateFunctionKind>,
+}
+
+impl Visitor<'_> for AggregateCollector {
+    fn visit_aggregate(&mut self, function: &AggregateFunction<'_>) {
+        self.functions.push(function.kind);
+    }
+
+    fn visit_subquery(&mut self, _query: &SelectQuery<'_>) {}
+}
+
+/// Collects columns referenced outside aggregates and outside grouped expressions.
+struct UngroupedColumns<'q, 'a> {
+    group_by: &'q [Expression<'a>],
+    columns: Vec<&'a str>,
+}
+
+impl<'a> Visitor<'a> for UngroupedColumns<'_, 'a> {
+    fn visit_expr(&mut self, expr: &Expression<'a>) {
+        if !self.group_by.contains(expr) {
+            walk_expression(self, expr);
+        }
+    }
+
+    fn visit_identifier(&mut self, identifier: &'a str) {
+        self.columns.push(identifier);
+    }
+
+    fn visit_wildcard(&mut self) {
+        self.columns.push("*");
+    }
+
+    fn visit_aggregate(&mut self, _function: &AggregateFunction<'a>) {}
+
+    fn visit_subquery(&mut self, _query: &SelectQuery<'a>) {}
+}
+
+impl<'a> SelectQuery<'a> {
+    /// Checks aggregate and grouping rules that the grammar alone cannot enforce.
+    ///
+    /// Every violation found is returned, in clause order.
+    pub fn validate(&self) -> Result<(), Vec<SemanticError<'a>>> {
+        let mut errors = Vec::new();
+        let group_by = self.group_by.as_ref().map_or(&[][..], |list| &list.0[..]);
+        let projection_has_aggregate = self.columns.0.iter().any(Expression::contains_aggregate);
+        let grouped = self.group_by.is_some() || projection_has_aggregate;
+
+        if grouped {
+            for (index, column) in self.columns.0.iter().enumerate() {
+                let mut ungrouped = UngroupedColumns { group_by, columns: Vec::new() };
+                ungrouped.visit_expr(column);
+                errors.extend(ungrouped.columns.into_iter().map(|column| SemanticError {
+                    kind: SemanticErrorKind::UngroupedColumn { column },
+                    clause: SelectClause::Projection(index),
+                }));
+            }
+        }
+
+        if let Some(where_clause) = &self.where_clause {
+            let mut aggregates = AggregateCollector::default();
+            aggregates.visit_expr(where_clause);
+            errors.extend(aggregates.functions.into_iter().map(|function| SemanticError {
+                kind: SemanticErrorKind::AggregateInWhere { function },
+                clause: SelectClause::Where,
+            }));
+        }
+
+        if self.having.as_ref().is_some_and(|having| !grouped && !having.contains_aggregate()) {
+            errors.push(SemanticError {
+                kind: SemanticErrorKind::HavingWithoutGrouping,
+                clause: SelectClause::Having,
+            });
+        }
+
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_parser::parser::{Parser, stmt::Statement};
+
+    fn validate(source: &str) -> Result<(), Vec<SemanticError<'_>>> {
+        match Parser::new(source).stmt().unwrap() {
+            Statement::Select(query) => query.validate(),
+            other => panic!("expected SELECT, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_validate_accepts_grouped_query() {
+        let s = "SELECT dept, role, COUNT(*), AVG(salary * 2) FROM staff \
+                 WHERE salary > 0 GROUP BY dept, role HAVING COUNT(*) > 1 ORDER BY dept;";
+        assert_eq!(validate(s), Ok(()));
+        assert_eq!(validate("SELECT dept + 1 FROM staff GROUP BY dept + 1;"), Ok(()));
+        assert_eq!(validate("SELECT SUM(x), MAX(y) FROM t;"), Ok(()));
+        assert_eq!(validate("SELECT a, b FROM t WHERE a > 1;"), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_rejects_ungrouped_column_next_to_aggregate() {
+        let expected = SemanticError {
+            kind: SemanticErrorKind::UngroupedColumn { column: "name" },
+            clause: SelectClause::Projection(1),
+        };
+        assert_eq!(validate("SELECT COUNT(*), name FROM t;"), Err(vec![expected]));
+
+        let expected = vec![
+            SemanticError {
+                kind: SemanticErrorKind::UngroupedColumn { column: "b" },
+                clause: SelectClause::Projection(0),
+            },
+            SemanticError {
+                kind: SemanticErrorKind::UngroupedColumn { column: "*" },
+                clause: SelectClause::Projection(1),
+            },
+        ];
+        assert_eq!(validate("SELECT a + b, * FROM t GROUP BY a;"), Err(expected));
+    }
+
+    #[test]
+    fn test_validate_rejects_aggregate_in_where() {
+        let expected = SemanticError {
+            kind: SemanticErrorKind::AggregateInWhere { function: AggregateFunctionKind::Sum },
+            clause: SelectClause::Where,
+        };
+        assert_eq!(validate("SELECT a FROM t WHERE SUM(a) > 10;"), Err(vec![expected]));
+
+        let s = "SELECT a FROM t WHERE a IN (SELECT MAX(b) FROM u);";
+        assert_eq!(validate(s), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_rejects_having_without_grouping() {
+        let expected = SemanticError {
+            kind: SemanticErrorKind::HavingWithoutGrouping,
+            clause: SelectClause::Having,
+        };
+        assert_eq!(validate("SELECT a FROM t HAVING a > 1;"), Err(vec![expected]));
+        assert_eq!(validate("SELECT 1 FROM t HAVING COUNT(*) > 1;"), Ok(()));
+    }
+
+    #[test]
+    fn test_semantic_error_display() {
+        let err = SemanticError {
+            kind: SemanticErrorKind::UngroupedColumn { column: "name" },
+            clause: SelectClause::Projection(1),
+        };
+        assert_eq!(
+            err.to_string(),
+            "Semantic error in select list item 2: \
+             Column name must appear in GROUP BY or be used in an aggregate"
+        );
+    }
+}
@@ -0,0 +1,431 @@
+use std::collections::BTreeSet;
+
+use crate::sql_parser::parser::{
+    expr::{AggregateFunction, Expression, Literal},
+    op::Op,
+    stmt::{
+        Statement,
+        create_table::ColumnType,
+        insert::{InsertQuery, InsertSource},
+        lists::ExpressionList,
+        select::SelectQuery,
+        update::UpdateQuery,
+    },
+};
+
+/// Read-only traversal over statements and [`Expression`] trees.
+///
+/// Every method has a default implementation that keeps walking into child
+/// nodes, so a visitor only overrides the nodes it cares about. An overriding
+/// method that still wants the children visited calls back into
+/// [`Visitor::visit_expr`] or the matching `walk_*` function itself.
+pub trait Visitor<'a> {
+    fn visit_statement(&mut self, statement: &Statement<'a>) {
+        walk_statement(self, statement);
+    }
+
+    fn visit_select(&mut self, query: &SelectQuery<'a>) {
+        walk_select(self, query);
+    }
+
+    fn visit_expression_list(&mut self, list: &ExpressionList<'a>) {
+        for expr in &list.0 {
+            self.visit_expr(expr);
+        }
+    }
+
+    fn visit_expr(&mut self, expr: &Expression<'a>) {
+        walk_expression(self, expr);
+    }
+
+    fn visit_literal(&mut self, _literal: &Literal<'a>) {}
+
+    fn visit_identifier(&mut self, _identifier: &'a str) {}
+
+    fn visit_wildcard(&mut self) {}
+
+    fn visit_unary_op(&mut self, _op: Op, expr: &Expression<'a>) {
+        self.visit_expr(expr);
+    }
+
+    fn visit_binary_op(&mut self, left: &Expression<'a>, _op: Op, right: &Expression<'a>) {
+        self.visit_expr(left);
+        self.visit_expr(right);
+    }
+
+    fn visit_aggregate(&mut self, function: &AggregateFunction<'a>) {
+        self.visit_expr(&function.expr);
+    }
+
+    fn visit_subquery(&mut self, query: &SelectQuery<'a>) {
+        self.visit_select(query);
+    }
+
+    fn visit_cast(&mut self, expr: &Expression<'a>, _data_type: &ColumnType) {
+        self.visit_expr(expr);
+    }
+}
+
+/// Visits every query and expression directly contained in `statement`.
+///
+/// `CREATE INDEX` holds no expressions, so nothing is visited for it.
+pub fn walk_statement<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, statement: &Statement<'a>) {
+    match statement {
+        Statement::Explain(statement) => visitor.visit_statement(statement),
+        Statement::With(query) => {
+            for cte in &query.ctes {
+                visitor.visit_select(&cte.query);
+            }
+            visitor.visit_statement(&query.statement);
+        }
+        Statement::Select(query) => visitor.visit_select(query),
+        Statement::Update(query) => {
+            for assignment in &query.assignments.0 {
+                visitor.visit_expr(&assignment.expression);
+            }
+            if let Some(where_clause) = &query.where_clause {
+                visitor.visit_expr(where_clause);
+            }
+        }
+        Statement::Delete(query) => {
+            if let Some(where_clause) = &query.where_clause {
+                visitor.visit_expr(where_clause);
+            }
+        }
+        Statement::Insert(query) => match &query.source {
+            InsertSource::Values(values) => {
+                for row in &values.0 {
+                    visitor.visit_expression_list(row);
+                }
+            }
+            InsertSource::Select(query) => visitor.visit_select(query),
+        },
+        Statement::Values(values) => {
+            for row in &values.0 {
+                visitor.visit_expression_list(row);
+            }
+        }
+        Statement::CreateTable(query) => {
+            for default in query.columns.iter().filter_map(|column| column.default.as_ref()) {
+                visitor.visit_expr(default);
+            }
+        }
+        Statement::CreateIndex(_) => {}
+    }
+}
+
+/// Visits the select list, `WHERE`, `GROUP BY`, `HAVING` and `ORDER BY` of
+/// `query`, in that order. `ORDER BY` terms name columns, so each is visited
+/// as an identifier.
+pub fn walk_select<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, query: &SelectQuery<'a>) {
+    visitor.visit_expression_list(&query.columns);
+    if let Some(where_clause) = &query.where_clause {
+        visitor.visit_expr(where_clause);
+    }
+    if let Some(group_by) = &query.group_by {
+        visitor.visit_expression_list(group_by);
+    }
+    if let Some(having) = &query.having {
+        visitor.visit_expr(having);
+    }
+    if let Some(order_by) = &query.order_by {
+        for term in &order_by.terms {
+            visitor.visit_identifier(term.column);
+        }
+    }
+}
+
+/// Dispatches `expr` to the visitor method for its variant.
+pub fn walk_expression<'a, V: Visitor<'a> + ?Sized>(visitor: &mut V, expr: &Expression<'a>) {
+    match expr {
+        Expression::Literal(literal) => visitor.visit_literal(literal),
+        Expression::Identifier(identifier) => visitor.visit_identifier(identifier),
+        Expression::Wildcard => visitor.visit_wildcard(),
+        Expression::UnaryOp((op, expr)) => visitor.visit_unary_op(*op, expr),
+        Expression::BinaryOp((left, op, right)) => visitor.visit_binary_op(left, *op, right),
+        Expression::AggregateFunction(function) => visitor.visit_aggregate(function),
+        Expression::Subquery(query) => visitor.visit_subquery(query),
+        Expression::Cast((expr, data_type)) => visitor.visit_cast(expr, data_type),
+    }
+}
+
+/// Collects every identifier referenced by an expression, in order of first use.
+#[derive(Debug, Default)]
+pub struct IdentifierCollector<'a> {
+    pub identifiers: Vec<&'a str>,
+}
+
+impl<'a> IdentifierCollector<'a> {
+    pub fn collect(expr: &Expression<'a>) -> Vec<&'a str> {
+        let mut collector = Self::default();
+        collector.visit_expr(expr);
+        collector.identifiers
+    }
+}
+
+impl<'a> Visitor<'a> for IdentifierCollector<'a> {
+    fn visit_identifier(&mut self, identifier: &'a str) {
+        if !self.identifiers.contains(&identifier) {
+            self.identifiers.push(identifier);
+        }
+    }
+}
+
+/// Collects the columns a query reads from its own table.
+///
+/// A wildcard is recorded as `"*"`, except in `COUNT(*)`, which reads no column
+/// values. Subqueries read from their own tables and are skipped.
+#[derive(Default)]
+struct ReferencedColumns<'a> {
+    columns: BTreeSet<&'a str>,
+}
+
+impl<'a> Visitor<'a> for ReferencedColumns<'a> {
+    fn visit_identifier(&mut self, identifier: &'a str) {
+        self.columns.insert(identifier);
+    }
+
+    fn visit_wildcard(&mut self) {
+        self.columns.insert("*");
+    }
+
+    fn visit_aggregate(&mut self, function: &AggregateFunction<'a>) {
+        if function.expr.as_ref() != &Expression::Wildcard {
+            self.visit_expr(&function.expr);
+        }
+    }
+
+    fn visit_subquery(&mut self, _query: &SelectQuery<'a>) {}
+}
+
+impl<'a> SelectQuery<'a> {
+    /// Returns every column referenced by the select list, `WHERE`, `GROUP BY`,
+    /// `HAVING` and `ORDER BY`, with `"*"` standing for a wildcard.
+    pub fn referenced_columns(&self) -> BTreeSet<&'a str> {
+        let mut collector = ReferencedColumns::default();
+        collector.visit_select(self);
+        collector.columns
+    }
+}
+
+impl<'a> InsertQuery<'a> {
+    /// Returns every column referenced by the inserted value expressions or by
+    /// the `SELECT` that produces the rows.
+    pub fn referenced_columns(&self) -> BTreeSet<&'a str> {
+        let mut collector = ReferencedColumns::default();
+        match &self.source {
+            InsertSource::Values(values) => {
+                for row in &values.0 {
+                    collector.visit_expression_list(row);
+                }
+            }
+            InsertSource::Select(query) => collector.visit_select(query),
+        }
+        collector.columns
+    }
+}
+
+impl<'a> UpdateQuery<'a> {
+    /// Returns every column read by the assigned expressions and `WHERE`.
+    ///
+    /// Assigned columns are only written, so they appear only if also read.
+    pub fn referenced_columns(&self) -> BTreeSet<&'a str> {
+        let mut collector = ReferencedColumns::default();
+        for assignment in &self.assignments.0 {
+            collector.visit_expr(&assignment.expression);
+        }
+        if let Some(where_clause) = &self.where_clause {
+            collector.visit_expr(where_clause);
+        }
+        collector.columns
+    }
+}
+
+impl<'a> Expression<'a> {
+    /// Rewrites the expression bottom-up, passing every node to `f` after its children.
+    ///
+    /// Expressions inside subqueries are rewritten as well, so `f` sees every
+    /// expression a [`Visitor`] would visit. A subquery's `ORDER BY` columns are
+    /// passed to `f` as identifiers and keep their old name unless `f` returns
+    /// another identifier, since `ORDER BY` can only name columns.
+    pub fn transform(self, f: &mut impl FnMut(Expression<'a>) -> Expression<'a>) -> Expression<'a> {
+        let expr = match self {
+            Expression::UnaryOp((op, expr)) => {
+                Expression::UnaryOp((op, Box::new(expr.transform(f))))
+            }
+            Expression::BinaryOp((left, op, right)) => Expression::BinaryOp((
+                Box::new(left.transform(f)),
+                op,
+                Box::new(right.transform(f)),
+            )),
+            Expression::AggregateFunction(AggregateFunction { kind, distinct, expr }) => {
+                Expression::AggregateFunction(AggregateFunction {
+                    kind,
+                    distinct,
+                    expr: Box::new(expr.transform(f)),
+                })
+            }
+            Expression::Subquery(mut query) => {
+                transform_list(&mut query.columns, f);
+                query.where_clause = query.where_clause.map(|expr| expr.transform(f));
+                if let Some(group_by) = &mut query.group_by {
+                    transform_list(group_by, f);
+                }
+                query.having = query.having.map(|expr| expr.transform(f));
+                if let Some(order_by) = &mut query.order_by {
+                    for term in &mut order_by.terms {
+                        if let Expression::Identifier(column) =
+                            f(Expression::Identifier(term.column))
+                        {
+                            term.column = column;
+                        }
+                    }
+                }
+                Expression::Subquery(query)
+            }
+            Expression::Cast((expr, data_type)) => {
+                Expression::Cast((Box::new(expr.transform(f)), data_type))
+            }
+            other => other,
+        };
+        f(expr)
+    }
+}
+
+fn transform_list<'a>(
+    list: &mut ExpressionList<'a>,
+    f: &mut impl FnMut(Expression<'a>) -> Expression<'a>,
+) {
+    list.0 = std::mem::take(&mut list.0).into_iter().map(|expr| expr.transform(f)).collect();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sql_parser::parser::Parser;
+
+    fn parse_expr(source: &str) -> Expression<'_> {
+        Parser::new(source).expr().unwrap()
+    }
+
+    #[test]
+    fn test_identifier_collector_dedupes_identifiers() {
+        let expr = parse_expr("a + b * a");
+        assert_eq!(IdentifierCollector::collect(&expr), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_identifier_collector_walks_nested_nodes() {
+        let expr = parse_expr("-(x - SUM(y * z)) > NOT w");
+        assert_eq!(IdentifierCollector::collect(&expr), vec!["x", "y", "z", "w"]);
+    }
+
+    #[test]
+    fn test_visitor_overrides_only_selected_nodes() {
+        #[derive(Default)]
+        struct Counter {
+            literals: usize,
+            binary_ops: usize,
+        }
+
+        impl<'a> Visitor<'a> for Counter {
+            fn visit_literal(&mut self, _literal: &Literal<'a>) {
+                self.literals += 1;
+            }
+
+            fn visit_binary_op(&mut self, left: &Expression<'a>, _op: Op, right: &Expression<'a>) {
+                self.binary_ops += 1;
+                self.visit_expr(left);
+                self.visit_expr(right);
+            }
+        }
+
+        let expr = parse_expr("1 + COUNT(*) * (2 - a)");
+        let mut counter = Counter::default();
+        counter.visit_expr(&expr);
+        assert_eq!(counter.literals, 2);
+        assert_eq!(counter.binary_ops, 3);
+    }
+
+    #[test]
+    fn test_visitor_collects_identifiers_in_nested_select() {
+        let s = "SELECT a, COUNT(b) FROM t WHERE c IN (SELECT d FROM u WHERE e > a) \
+                 GROUP BY a HAVING SUM(f) > 1;";
+        let statement = Parser::new(s).stmt().unwrap();
+        let mut collector = IdentifierCollector::default();
+        collector.visit_statement(&statement);
+        assert_eq!(collector.identifiers, vec!["a", "b", "c", "d", "e", "f"]);
+
+        let s = "EXPLAIN UPDATE t SET x = y + 1 WHERE z == (SELECT MAX(w) FROM u);";
+        let statement = Parser::new(s).stmt().unwrap();
+        let mut collector = IdentifierCollector::default();
+        collector.visit_statement(&statement);
+        assert_eq!(collector.identifiers, vec!["y", "z", "w"]);
+    }
+
+    #[test]
+    fn test_transform_rewrites_bottom_up() {
+        let mut visited = Vec::new();
+        let expr =
+            parse_expr("a + SUM(b) * (SELECT c FROM t WHERE d == 1)").transform(&mut |expr| {
+                visited.push(expr.to_string());
+                match expr {
+                    Expression::Identifier(name) if name != "c" => Expression::from(2),
+                    other => other,
+                }
+            });
+        assert_eq!(expr, parse_expr("2 + SUM(2) * (SELECT c FROM t WHERE 2 == 1)"));
+        assert_eq!(visited.first().map(String::as_str), Some("a"));
+        assert_eq!(visited.last(), Some(&expr.to_string()));
+    }
+
+    fn referenced_columns(source: &str) -> Vec<&str> {
+        let columns = match Parser::new(source).stmt().unwrap() {
+            Statement::Select(query) => query.referenced_columns(),
+            Statement::Insert(query) => query.referenced_columns(),
+            Statement::Update(query) => query.referenced_columns(),
+            other => panic!("unexpected statement {other:?}"),
+        };
+        columns.into_iter().collect()
+    }
+
+    #[test]
+    fn test_referenced_columns_of_select() {
+        let s = "SELECT a, SUM(b * c) FROM t WHERE d > 1 AND a < 5 \
+                 GROUP BY a HAVING MAX(e) > 1 ORDER BY f DESC;";
+        assert_eq!(referenced_columns(s), vec!["a", "b", "c", "d", "e", "f"]);
+        assert_eq!(referenced_columns("SELECT a, a + a FROM t WHERE a == 1;"), vec!["a"]);
+        assert_eq!(referenced_columns("SELECT 1 + 2;"), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_referenced_columns_with_wildcards_and_subqueries() {
+        assert_eq!(referenced_columns("SELECT * FROM t WHERE id > 3;"), vec!["*", "id"]);
+        assert_eq!(referenced_columns("SELECT COUNT(*), MAX(a) FROM t;"), vec!["a"]);
+        let s = "SELECT a FROM t WHERE b IN (SELECT c FROM u WHERE d > 1);";
+        assert_eq!(referenced_columns(s), vec!["a", "b"]);
+    }
+
+    #[test]
+    fn test_order_by_is_visited_and_transformed() {
+        let s = "INSERT INTO t (a) SELECT b FROM u ORDER BY c;";
+        assert_eq!(referenced_columns(s), vec!["b", "c"]);
+
+        let expr =
+            parse_expr("a IN (SELECT b FROM u ORDER BY b DESC, c)").transform(
+                &mut |expr| match expr {
+                    Expression::Identifier("b") => Expression::Identifier("x"),
+                    Expression::Identifier("c") => Expression::from(1),
+                    other => other,
+                },
+            );
+        assert_eq!(expr, parse_expr("a IN (SELECT x FROM u ORDER BY x DESC, c)"));
+    }
+
+    #[test]
+    fn test_referenced_columns_of_insert_and_update() {
+        assert_eq!(referenced_columns("INSERT INTO t (a, b) VALUES (1, 2);"), Vec::<&str>::new());
+        let s = "UPDATE t SET a = b + 1, c = 2 WHERE d == a;";
+        assert_eq!(referenced_columns(s), vec!["a", "b", "d"]);
+    }
+}
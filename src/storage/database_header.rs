@@ -7,7 +7,7 @@ pub(crate) const DATABASE_HEADER_PAGE_ID: PageId = 0;
 
 const MAGIC: &[u8; 8] = b"DATABAS\0";
 const FORMAT_VERSION: u16 = 2;
-const HEADER_LEN: usize = 12;
+pub(crate) const HEADER_LEN: usize = 12;
 
 /// Fixed-format database file header stored on page 0.
 pub(crate) struct DatabaseHeader;
@@ -53,6 +53,15 @@ impl DatabaseHeader {
 
         Ok(())
     }
+
+    /// Returns the page size recorded in a header prefix, if the prefix carries
+    /// the database magic.
+    pub(crate) fn stored_page_size(prefix: &[u8; HEADER_LEN]) -> Option<usize> {
+        if &prefix[0..8] != MAGIC {
+            return None;
+        }
+        Some(u16::from_le_bytes([prefix[10], prefix[11]]) as usize)
+    }
 }
 
 pub(crate) fn missing_header() -> StorageError {
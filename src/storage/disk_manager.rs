@@ -5,14 +5,31 @@ use std::{
 };
 
 use crate::core::{
-    error::{DiskManagerError, DiskManagerResult},
+    error::{DiskManagerError, DiskManagerResult, StorageResult},
     {PAGE_SIZE, PageId},
 };
+use crate::storage::database_header::{DATABASE_HEADER_PAGE_ID, DatabaseHeader, HEADER_LEN};
+
+/// Controls when the disk manager flushes writes to stable storage.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SyncMode {
+    /// Sync only when [`DiskManager::ensure_page_exists`] grows the file.
+    #[default]
+    OnExtend,
+    /// Also sync after every page write and [`DiskManager::new_page`].
+    EachWrite,
+    /// Skip syncing until an explicit [`DiskManager::sync`] call.
+    Deferred,
+}
 
 /// Reads and writes pages to and from a database file.
 pub struct DiskManager {
     file: File,
     page_count: u64,
+    page_size: usize,
+    sync_mode: SyncMode,
+    #[cfg(test)]
+    sync_count: u64,
 }
 
 impl DiskManager {
@@ -34,7 +51,30 @@ impl DiskManager {
 
     /// Open an existing database file.
     pub(crate) fn open_existing(path: &Path) -> Result<Self, DiskManagerError> {
-        Self::open_with_options(OpenOptions::new().read(true).write(true).append(false), path)
+        Self::open_with_page_size(path, PAGE_SIZE)
+    }
+
+    /// Open an existing database file that is expected to use `page_size`.
+    ///
+    /// Fails if this build cannot use `page_size`, or if the database header
+    /// records a different page size. Files without a database header are not
+    /// checked here; header validation reports them.
+    pub(crate) fn open_with_page_size(
+        path: &Path,
+        page_size: usize,
+    ) -> Result<Self, DiskManagerError> {
+        if page_size != PAGE_SIZE {
+            return Err(DiskManagerError::UnsupportedPageSize { page_size });
+        }
+
+        let mut disk_manager =
+            Self::open_with_options(OpenOptions::new().read(true).write(true).append(false), path)?;
+        if let Some(actual) = disk_manager.stored_page_size()?
+            && actual != page_size
+        {
+            return Err(DiskManagerError::PageSizeMismatch { expected: page_size, actual });
+        }
+        Ok(disk_manager)
     }
 
     fn open_with_options(options: &mut OpenOptions, path: &Path) -> Result<Self, DiskManagerError> {
@@ -49,23 +89,45 @@ impl DiskManager {
 
         let page_count = file_size / (PAGE_SIZE as u64);
 
-        Ok(Self { file, page_count })
+        Ok(Self {
+            file,
+            page_count,
+            page_size: PAGE_SIZE,
+            sync_mode: SyncMode::default(),
+            #[cfg(test)]
+            sync_count: 0,
+        })
     }
 
     pub(crate) fn page_count(&self) -> u64 {
         self.page_count
     }
 
+    /// Reads the page size recorded in the database header, if one is present.
+    pub(crate) fn stored_page_size(&mut self) -> DiskManagerResult<Option<usize>> {
+        if self.page_count == 0 {
+            return Ok(None);
+        }
+        let mut prefix = [0u8; HEADER_LEN];
+        self.file.seek(std::io::SeekFrom::Start(0))?;
+        self.file.read_exact(&mut prefix)?;
+        Ok(DatabaseHeader::stored_page_size(&prefix))
+    }
+
     pub(crate) fn ensure_page_exists(&mut self, page_id: PageId) -> DiskManagerResult<()> {
         if page_id < self.page_count {
             return Ok(());
         }
 
         let new_page_count = page_id + 1;
-        self.file.set_len(Self::page_offset(new_page_count))?;
-        self.file.sync_all()?;
+        self.file.set_len(self.page_offset(new_page_count))?;
         self.page_count = new_page_count;
-        Ok(())
+        self.sync_after_extend()
+    }
+
+    /// Sets when writes are synced to stable storage.
+    pub(crate) fn set_sync_mode(&mut self, mode: SyncMode) {
+        self.sync_mode = mode;
     }
 
     pub(crate) fn sync(&self) -> DiskManagerResult<()> {
@@ -73,14 +135,37 @@ impl DiskManager {
         Ok(())
     }
 
+    fn sync_after_extend(&mut self) -> DiskManagerResult<()> {
+        if self.sync_mode == SyncMode::Deferred {
+            return Ok(());
+        }
+        self.counted_sync()
+    }
+
+    fn sync_after_write(&mut self) -> DiskManagerResult<()> {
+        if self.sync_mode != SyncMode::EachWrite {
+            return Ok(());
+        }
+        self.counted_sync()
+    }
+
+    fn counted_sync(&mut self) -> DiskManagerResult<()> {
+        #[cfg(test)]
+        {
+            self.sync_count += 1;
+        }
+        self.sync()
+    }
+
     /// Extends the database file by one page.
     /// Returns page ID of the new page.
     pub(crate) fn new_page(&mut self) -> DiskManagerResult<PageId> {
         let page_id = self.page_count;
         let new_page_id = page_id + 1;
-        let new_file_size = Self::page_offset(new_page_id);
+        let new_file_size = self.page_offset(new_page_id);
         self.file.set_len(new_file_size)?;
         self.page_count += 1;
+        self.sync_after_write()?;
         Ok(page_id)
     }
 
@@ -93,7 +178,7 @@ impl DiskManager {
         if page_id >= self.page_count {
             return Err(DiskManagerError::InvalidPageId { page_id });
         }
-        let offset = Self::page_offset(page_id);
+        let offset = self.page_offset(page_id);
         self.file.seek(std::io::SeekFrom::Start(offset))?;
         self.file.read_exact(buf)?;
         Ok(())
@@ -108,21 +193,44 @@ impl DiskManager {
         if page_id >= self.page_count {
             return Err(DiskManagerError::InvalidPageId { page_id });
         }
-        let offset = Self::page_offset(page_id);
+        let offset = self.page_offset(page_id);
         self.file.seek(std::io::SeekFrom::Start(offset))?;
         self.file.write_all(buf)?;
-        Ok(())
+        self.sync_after_write()
+    }
+
+    /// Reads every page in page-id order, starting after the header unless `include_header`.
+    ///
+    /// The header page is checked against the database header format. Data
+    /// pages are returned as stored, since overflow pages carry no page-kind tag
+    /// to check. A page that fails to read or validate yields an `Err` item and
+    /// iteration continues with the next page.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn iter_pages(
+        &mut self,
+        include_header: bool,
+    ) -> impl Iterator<Item = StorageResult<(PageId, [u8; PAGE_SIZE])>> + '_ {
+        let first_page_id = DATABASE_HEADER_PAGE_ID + u64::from(!include_header);
+        (first_page_id..self.page_count).map(move |page_id| {
+            let mut buf = [0u8; PAGE_SIZE];
+            self.read_page(page_id, &mut buf)?;
+            if page_id == DATABASE_HEADER_PAGE_ID {
+                DatabaseHeader::validate_page(&buf)?;
+            }
+            Ok((page_id, buf))
+        })
     }
 
     /// Calculate disk offset for page `page_id`.
-    fn page_offset(page_id: PageId) -> u64 {
-        page_id * (PAGE_SIZE as u64)
+    fn page_offset(&self, page_id: PageId) -> u64 {
+        page_id * (self.page_size as u64)
     }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::core::error::{CorruptionError, CorruptionKind, StorageError};
     use fastrand::Rng;
     use tempfile::NamedTempFile;
 
@@ -153,6 +261,51 @@ mod test {
         assert_eq!(dm.page_count, 1);
     }
 
+    #[test]
+    fn iter_pages_reads_every_page_in_order() {
+        let mut rng = fastrand::Rng::new();
+        let file = NamedTempFile::new().unwrap();
+        let mut dm = DiskManager::new(file.path()).unwrap();
+        dm.new_page().unwrap();
+        dm.write_page(DATABASE_HEADER_PAGE_ID, &DatabaseHeader::encode_page()).unwrap();
+        let data_pages: Vec<_> = (0..3).map(|_| random_page_buffer(&mut rng)).collect();
+        for buf in &data_pages {
+            let page_id = dm.new_page().unwrap();
+            dm.write_page(page_id, buf).unwrap();
+        }
+
+        let pages: Vec<_> = dm.iter_pages(false).map(Result::unwrap).collect();
+        let expected: Vec<_> = (1..).zip(data_pages.iter().copied()).collect();
+        assert_eq!(pages, expected);
+
+        let ids: Vec<_> = dm.iter_pages(true).map(|page| page.unwrap().0).collect();
+        assert_eq!(ids, [0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn iter_pages_reports_a_corrupt_page_and_keeps_going() {
+        let file = NamedTempFile::new().unwrap();
+        let mut dm = DiskManager::new(file.path()).unwrap();
+        let mut header = DatabaseHeader::encode_page();
+        header[0] = b'X';
+        dm.new_page().unwrap();
+        dm.write_page(DATABASE_HEADER_PAGE_ID, &header).unwrap();
+        dm.new_page().unwrap();
+        dm.new_page().unwrap();
+
+        let pages: Vec<_> = dm.iter_pages(true).collect();
+        assert_eq!(pages.len(), 3);
+        assert!(matches!(
+            &pages[0],
+            Err(StorageError::Corruption(CorruptionError {
+                kind: CorruptionKind::InvalidDatabaseMagic { .. },
+                ..
+            }))
+        ));
+        assert_eq!(pages[1].as_ref().unwrap().0, 1);
+        assert_eq!(pages[2].as_ref().unwrap().0, 2);
+    }
+
     #[test]
     fn cannot_read_out_of_bounds_page() {
         let file = NamedTempFile::new().unwrap();
@@ -294,4 +447,90 @@ mod test {
         dm.read_page(page_id, &mut read_buf).unwrap();
         assert_eq!(read_buf, [0u8; PAGE_SIZE]);
     }
+
+    #[test]
+    fn open_with_page_size_reads_back_stored_page_size() {
+        let file = NamedTempFile::new().unwrap();
+        {
+            let mut dm = DiskManager::new(file.path()).unwrap();
+            dm.new_page().unwrap();
+            dm.write_page(0, &DatabaseHeader::encode_page()).unwrap();
+        }
+
+        let mut dm = DiskManager::open_with_page_size(file.path(), PAGE_SIZE).unwrap();
+        assert_eq!(dm.page_size, PAGE_SIZE);
+        assert_eq!(dm.stored_page_size().unwrap(), Some(PAGE_SIZE));
+    }
+
+    #[test]
+    fn open_with_page_size_rejects_mismatched_stored_page_size() {
+        let file = NamedTempFile::new().unwrap();
+        {
+            let mut dm = DiskManager::new(file.path()).unwrap();
+            dm.new_page().unwrap();
+            let mut header = DatabaseHeader::encode_page();
+            header[10..12].copy_from_slice(&8192u16.to_le_bytes());
+            dm.write_page(0, &header).unwrap();
+        }
+
+        let dm = DiskManager::open_with_page_size(file.path(), PAGE_SIZE);
+        assert!(matches!(
+            dm,
+            Err(DiskManagerError::PageSizeMismatch { expected: PAGE_SIZE, actual: 8192 })
+        ));
+    }
+
+    #[test]
+    fn open_with_page_size_rejects_unsupported_page_size() {
+        let file = NamedTempFile::new().unwrap();
+        let page_size = PAGE_SIZE * 2;
+
+        let dm = DiskManager::open_with_page_size(file.path(), page_size);
+        assert!(matches!(
+            dm,
+            Err(DiskManagerError::UnsupportedPageSize { page_size: size }) if size == page_size
+        ));
+    }
+
+    #[test]
+    fn deferred_sync_mode_persists_after_explicit_sync() {
+        let mut rng = fastrand::Rng::new();
+        let file = NamedTempFile::new().unwrap();
+        let write_bufs: Vec<[u8; PAGE_SIZE]> =
+            (0..4).map(|_| random_page_buffer(&mut rng)).collect();
+
+        let deferred_syncs = {
+            let mut dm = DiskManager::new(file.path()).unwrap();
+            dm.set_sync_mode(SyncMode::Deferred);
+            for buf in &write_bufs {
+                let page_id = dm.new_page().unwrap();
+                dm.write_page(page_id, buf).unwrap();
+            }
+            dm.sync().unwrap();
+            dm.sync_count
+        };
+        assert_eq!(deferred_syncs, 0);
+
+        let mut dm = DiskManager::new(file.path()).unwrap();
+        for (page_id, expected_buf) in write_bufs.iter().enumerate() {
+            let mut read_buf = [0u8; PAGE_SIZE];
+            dm.read_page(page_id as PageId, &mut read_buf).unwrap();
+            assert_eq!(&read_buf, expected_buf);
+        }
+
+        for (page_id, buf) in write_bufs.iter().enumerate() {
+            dm.write_page(page_id as PageId, buf).unwrap();
+        }
+        dm.new_page().unwrap();
+        assert_eq!(dm.sync_count, 0);
+        dm.ensure_page_exists(dm.page_count()).unwrap();
+        assert_eq!(dm.sync_count, 1);
+
+        dm.set_sync_mode(SyncMode::EachWrite);
+        for (page_id, buf) in write_bufs.iter().enumerate() {
+            dm.write_page(page_id as PageId, buf).unwrap();
+        }
+        dm.new_page().unwrap();
+        assert_eq!(dm.sync_count, write_bufs.len() as u64 + 2);
+    }
 }
@@ -262,6 +262,15 @@ where
         Ok(occupied_variable_bytes * 2 < usable_variable_bytes)
     }
 
+    /// Returns the fraction of the usable region below the header that is no
+    /// longer contiguous free space, from `0.0` for an empty page to `1.0`.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn fill_factor(&self) -> f32 {
+        let usable_variable_bytes = USABLE_SPACE_END - N::KIND.header_size();
+        let occupied = usable_variable_bytes - self.free_space();
+        occupied as f32 / usable_variable_bytes as f32
+    }
+
     pub(crate) fn slot_directory_end(&self) -> usize {
         N::KIND.header_size() + self.slot_count() as usize * format::SLOT_ENTRY_SIZE
     }
@@ -623,16 +632,7 @@ where
     }
 
     pub(crate) fn initialize(bytes: &'a mut [u8; PAGE_SIZE]) -> Self {
-        bytes.fill(0);
-        bytes[KIND_OFFSET] = page_kind::<N>() as u8;
-        bytes[VERSION_OFFSET] = FORMAT_VERSION;
-        format::write_u16(bytes, SLOT_COUNT_OFFSET, 0);
-        format::write_u16(bytes, CONTENT_START_OFFSET, USABLE_SPACE_END as u16);
-        format::write_optional_u16(bytes, FIRST_FREEBLOCK_OFFSET, None);
-        format::write_u16(bytes, FRAGMENTED_FREE_BYTES_OFFSET, 0);
-        format::write_optional_u64(bytes, PREV_PAGE_ID_OFFSET, None);
-        format::write_optional_u64(bytes, NEXT_PAGE_ID_OFFSET, None);
-        format::write_u64(bytes, LSN_OFFSET, ZERO_LSN);
+        write_empty_page::<N>(bytes);
         Self::new(Write { bytes })
     }
 }
@@ -643,6 +643,15 @@ impl<'a> Page<Write<'a>, Leaf> {
     pub(crate) fn init(bytes: &'a mut [u8; PAGE_SIZE]) -> Self {
         Self::initialize(bytes)
     }
+
+    /// Resets the page to an empty leaf in its existing buffer.
+    ///
+    /// Every cell is zeroed and the header is rewritten as by [`Self::initialize`],
+    /// so sibling links and the page LSN are cleared too.
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn clear(&mut self) {
+        write_empty_page::<Leaf>(self.bytes_mut());
+    }
 }
 
 impl<'a> Page<Write<'a>, Interior> {
@@ -662,6 +671,19 @@ impl<'a> Page<Write<'a>, Interior> {
     }
 }
 
+fn write_empty_page<N: NodeMarker>(bytes: &mut [u8; PAGE_SIZE]) {
+    bytes.fill(0);
+    bytes[KIND_OFFSET] = page_kind::<N>() as u8;
+    bytes[VERSION_OFFSET] = FORMAT_VERSION;
+    format::write_u16(bytes, SLOT_COUNT_OFFSET, 0);
+    format::write_u16(bytes, CONTENT_START_OFFSET, USABLE_SPACE_END as u16);
+    format::write_optional_u16(bytes, FIRST_FREEBLOCK_OFFSET, None);
+    format::write_u16(bytes, FRAGMENTED_FREE_BYTES_OFFSET, 0);
+    format::write_optional_u64(bytes, PREV_PAGE_ID_OFFSET, None);
+    format::write_optional_u64(bytes, NEXT_PAGE_ID_OFFSET, None);
+    format::write_u64(bytes, LSN_OFFSET, ZERO_LSN);
+}
+
 pub(crate) fn validate_btree_page(bytes: &[u8; PAGE_SIZE]) -> PageResult<()> {
     let Some(actual_kind) = format::PageKind::from_raw(bytes[KIND_OFFSET]) else {
         return Err(PageError::UnknownPageKind { actual: bytes[KIND_OFFSET] });
@@ -96,3 +96,66 @@ pub(crate) enum CellCorruption {
     #[error("index table-key value has invalid length {actual}")]
     InvalidIndexTableKeyValueLength { actual: usize },
 }
+
+#[cfg(test)]
+mod tests {
+    use std::error::Error;
+
+    use super::*;
+
+    #[test]
+    fn page_errors_display_each_variant() {
+        let cases = [
+            (PageError::UnknownPageKind { actual: 9 }, "unknown page kind: raw tag 9"),
+            (
+                PageError::InvalidPageKind { expected: PageKind::RawLeaf, actual: 2 },
+                "invalid page kind: expected RawLeaf, got raw tag 2",
+            ),
+            (
+                PageError::InvalidPageVersion { expected: 1, actual: 3 },
+                "invalid page version: expected 1, got 3",
+            ),
+            (
+                PageError::InvalidSlotIndex { slot_index: 4, slot_count: 2 },
+                "invalid slot index 4 for 2 slots",
+            ),
+            (
+                PageError::MalformedPage(PageCorruption::ReservedFooterNotZero),
+                "malformed page: reserved footer is not zeroed",
+            ),
+            (
+                PageError::CorruptCell { slot_index: 1, kind: CellCorruption::LengthTooSmall },
+                "corrupt cell at slot 1: cell length is smaller than the minimum header",
+            ),
+            (PageError::DuplicateKey, "duplicate key"),
+            (PageError::KeyNotFound, "key not found"),
+            (
+                PageError::PageFull { needed: 100, available: 40 },
+                "page full: need 100 bytes, only 40 available",
+            ),
+            (
+                PageError::CellTooLarge { len: 70000, max: 65535 },
+                "cell too large: 70000 bytes exceeds max 65535",
+            ),
+        ];
+
+        for (error, expected) in cases {
+            assert_eq!(error.to_string(), expected);
+        }
+    }
+
+    #[test]
+    fn page_errors_box_as_dyn_error_with_sources() {
+        let error: Box<dyn Error> = Box::new(PageError::CorruptCell {
+            slot_index: 0,
+            kind: CellCorruption::InvalidTableKeyLength { actual: 3 },
+        });
+        assert_eq!(
+            error.source().map(ToString::to_string).as_deref(),
+            Some("table key has invalid length 3")
+        );
+
+        let error: Box<dyn Error> = Box::new(PageError::DuplicateKey);
+        assert!(error.source().is_none());
+    }
+}
@@ -75,6 +75,42 @@ mod tests {
         assert!(is_overflow_page(&overflow_bytes));
     }
 
+    fn leaf_with_keys<'a>(bytes: &'a mut [u8; PAGE_SIZE], keys: &[&[u8]]) -> Page<Write<'a>, Leaf> {
+        let mut page = Page::<Write<'_>, Leaf>::init(bytes);
+        for (slot_index, key) in keys.iter().enumerate() {
+            page.insert_payload_at(slot_index as u16, key.len(), 0, None, key).unwrap();
+        }
+        page
+    }
+
+    #[test]
+    fn leaf_search_returns_slot_of_present_key() {
+        let mut bytes = [0; PAGE_SIZE];
+        let page = leaf_with_keys(&mut bytes, &[b"b", b"d", b"f"]);
+
+        assert_eq!(page.search(b"b").unwrap(), SearchResult::Found(0));
+        assert_eq!(page.search(b"d").unwrap(), SearchResult::Found(1));
+        assert_eq!(page.search(b"f").unwrap(), SearchResult::Found(2));
+    }
+
+    #[test]
+    fn leaf_search_returns_insertion_slot_for_absent_key() {
+        let mut bytes = [0; PAGE_SIZE];
+        let page = leaf_with_keys(&mut bytes, &[b"b", b"d", b"f"]);
+
+        assert_eq!(page.search(b"c").unwrap(), SearchResult::InsertAt(1));
+        assert_eq!(page.search(b"e").unwrap(), SearchResult::InsertAt(2));
+    }
+
+    #[test]
+    fn leaf_search_handles_keys_outside_stored_range() {
+        let mut bytes = [0; PAGE_SIZE];
+        let page = leaf_with_keys(&mut bytes, &[b"b", b"d", b"f"]);
+
+        assert_eq!(page.search(b"a").unwrap(), SearchResult::InsertAt(0));
+        assert_eq!(page.search(b"g").unwrap(), SearchResult::InsertAt(3));
+    }
+
     #[test]
     fn leaf_delete_removes_existing_key() {
         let mut bytes = [0; PAGE_SIZE];
@@ -171,4 +207,138 @@ mod tests {
 
         assert!(matches!(result, Err(PageError::CellTooLarge { .. })));
     }
+
+    #[test]
+    fn leaf_read_and_write_views_report_same_accounting() {
+        let mut bytes = [0; PAGE_SIZE];
+        let mut page = Page::<Write<'_>, Leaf>::init(&mut bytes);
+        let empty_free_space = page.free_space();
+        assert_eq!(empty_free_space, format::USABLE_SPACE_END - format::LEAF_HEADER_SIZE);
+
+        page.insert_payload_at(0, 5, 5, None, b"alphavalue").unwrap();
+        let cell_len = page.cell_len(0).unwrap();
+        assert_eq!(page.slot_count(), 1);
+        assert_eq!(page.free_space(), empty_free_space - format::SLOT_ENTRY_SIZE - cell_len);
+
+        let (slot_count, free_space) = (page.slot_count(), page.free_space());
+        let page = Page::<Read<'_>, Leaf>::open(&bytes).unwrap();
+        assert_eq!(page.slot_count(), slot_count);
+        assert_eq!(page.free_space(), free_space);
+    }
+
+    #[test]
+    fn interior_read_and_write_views_report_same_accounting() {
+        let mut bytes = [0; PAGE_SIZE];
+        let mut page = Page::<Write<'_>, Interior>::init(&mut bytes, 99);
+        let empty_free_space = page.free_space();
+        assert_eq!(empty_free_space, format::USABLE_SPACE_END - format::INTERIOR_HEADER_SIZE);
+
+        page.insert_payload_at(0, 7, b"middle".len(), None, b"middle").unwrap();
+        let cell_len = page.cell_len(0).unwrap();
+        assert_eq!(page.slot_count(), 1);
+        assert_eq!(page.free_space(), empty_free_space - format::SLOT_ENTRY_SIZE - cell_len);
+
+        let (slot_count, free_space) = (page.slot_count(), page.free_space());
+        let page = Page::<Read<'_>, Interior>::open(&bytes).unwrap();
+        assert_eq!(page.slot_count(), slot_count);
+        assert_eq!(page.free_space(), free_space);
+    }
+
+    /// Inserts 64-byte leaf cells until `fill_factor` reaches `target` or the page is full.
+    fn fill_leaf_to(page: &mut Page<Write<'_>, Leaf>, target: f32) {
+        let value = [7; 64];
+        for index in 0_u16.. {
+            if page.fill_factor() >= target {
+                return;
+            }
+            let key = index.to_be_bytes();
+            let mut payload = Vec::from(key);
+            payload.extend_from_slice(&value);
+            if page.insert_payload_at(index, key.len(), value.len(), None, &payload).is_err() {
+                return;
+            }
+        }
+    }
+
+    #[test]
+    fn empty_pages_have_zero_fill_factor() {
+        let mut bytes = [0; PAGE_SIZE];
+        assert_eq!(Page::<Write<'_>, Leaf>::init(&mut bytes).fill_factor(), 0.0);
+
+        let mut bytes = [0; PAGE_SIZE];
+        assert_eq!(Page::<Write<'_>, Interior>::init(&mut bytes, 99).fill_factor(), 0.0);
+    }
+
+    #[test]
+    fn half_full_leaf_has_fill_factor_near_one_half() {
+        let mut bytes = [0; PAGE_SIZE];
+        let mut page = Page::<Write<'_>, Leaf>::init(&mut bytes);
+
+        fill_leaf_to(&mut page, 0.5);
+
+        let fill_factor = page.fill_factor();
+        assert!((0.5..0.55).contains(&fill_factor), "fill factor was {fill_factor}");
+        let page = Page::<Read<'_>, Leaf>::open(&bytes).unwrap();
+        assert_eq!(page.fill_factor(), fill_factor);
+    }
+
+    #[test]
+    fn full_leaf_has_fill_factor_near_one() {
+        let mut bytes = [0; PAGE_SIZE];
+        let mut page = Page::<Write<'_>, Leaf>::init(&mut bytes);
+
+        fill_leaf_to(&mut page, 1.0);
+
+        let fill_factor = page.fill_factor();
+        assert!((0.95..=1.0).contains(&fill_factor), "fill factor was {fill_factor}");
+    }
+
+    #[test]
+    fn interior_fill_factor_grows_with_cells() {
+        let mut bytes = [0; PAGE_SIZE];
+        let mut page = Page::<Write<'_>, Interior>::init(&mut bytes, 99);
+
+        page.insert_payload_at(0, 7, b"middle".len(), None, b"middle").unwrap();
+
+        let expected = (format::SLOT_ENTRY_SIZE + page.cell_len(0).unwrap()) as f32
+            / (format::USABLE_SPACE_END - format::INTERIOR_HEADER_SIZE) as f32;
+        assert_eq!(page.fill_factor(), expected);
+    }
+
+    #[test]
+    fn cleared_leaf_matches_freshly_initialized_leaf() {
+        let mut bytes = [0; PAGE_SIZE];
+        let mut page = Page::<Write<'_>, Leaf>::init(&mut bytes);
+        let empty_free_space = page.free_space();
+        fill_leaf_to(&mut page, 0.5);
+        page.set_prev_page_id(Some(3));
+        page.set_next_page_id(Some(5));
+        assert!(page.slot_count() > 0);
+
+        page.clear();
+
+        assert_eq!(page.slot_count(), 0);
+        assert_eq!(page.free_space(), empty_free_space);
+        assert_eq!(page.first_freeblock(), None);
+        assert_eq!(page.prev_page_id(), None);
+        assert_eq!(page.next_page_id(), None);
+        assert_eq!(page.search(&0_u16.to_be_bytes()).unwrap(), SearchResult::InsertAt(0));
+
+        let mut fresh = [0; PAGE_SIZE];
+        Page::<Write<'_>, Leaf>::init(&mut fresh);
+        assert_eq!(bytes, fresh);
+    }
+
+    #[test]
+    fn cleared_leaf_accepts_new_cells() {
+        let mut bytes = [0; PAGE_SIZE];
+        let mut page = Page::<Write<'_>, Leaf>::init(&mut bytes);
+        fill_leaf_to(&mut page, 1.0);
+
+        page.clear();
+        fill_leaf_to(&mut page, 1.0);
+
+        let fill_factor = page.fill_factor();
+        assert!((0.95..=1.0).contains(&fill_factor), "fill factor was {fill_factor}");
+    }
 }
@@ -15,6 +15,10 @@
 //! while read and write guards control access to the page contents. Dropping a
 //! [`PinGuard`] decrements the frame pin count. Dirty pages are written only by
 //! explicit flushes or eviction.
+//!
+//! The frame count is chosen at construction and can be changed later with
+//! [`PageCache::resize`]. Shrinking relocates resident pages into free
+//! low-numbered frames where possible and evicts the rest.
 
 use std::{
     cell::{Cell, Ref, RefCell, RefMut},
@@ -66,7 +70,7 @@ struct CacheMeta {
 struct PageCacheInner {
     runtime: Rc<StorageRuntime>,
     meta: RefCell<CacheMeta>,
-    frames: Vec<Frame>,
+    frames: RefCell<Vec<Rc<Frame>>>,
 }
 
 /// Shared handle to the single-threaded page cache.
@@ -99,7 +103,7 @@ impl PageCache {
         frames
             .try_reserve_exact(frame_count)
             .map_err(|source| PageCacheError::FrameAllocationFailed { frame_count, source })?;
-        frames.extend((0..frame_count).map(|_| Frame::empty()));
+        frames.extend((0..frame_count).map(|_| Rc::new(Frame::empty())));
 
         Ok(Self {
             inner: Rc::new(PageCacheInner {
@@ -108,18 +112,92 @@ impl PageCache {
                     page_table: HashMap::new(),
                     replacement: ClockPolicy::new(frame_count),
                 }),
-                frames,
+                frames: RefCell::new(frames),
             }),
         })
     }
 
+    /// Changes the number of frames in the cache.
+    ///
+    /// Growing appends empty frames. Shrinking removes the highest-numbered
+    /// frames: their pages move into free remaining frames when available and
+    /// are otherwise evicted, flushing dirty data first. Returns `PinnedPage`
+    /// without changing the cache if a frame that would be removed is pinned.
+    pub(crate) fn resize(&self, new_frame_count: usize) -> PageCacheResult<()> {
+        if new_frame_count == 0 {
+            return Err(PageCacheError::InvalidFrameCount { frame_count: new_frame_count });
+        }
+
+        let frame_count = self.frame_count();
+        if new_frame_count > frame_count {
+            let mut frames = self.inner.frames.borrow_mut();
+            frames.try_reserve_exact(new_frame_count - frame_count).map_err(|source| {
+                PageCacheError::FrameAllocationFailed { frame_count: new_frame_count, source }
+            })?;
+            frames.extend((frame_count..new_frame_count).map(|_| Rc::new(Frame::empty())));
+            self.inner.meta.borrow_mut().replacement.resize(new_frame_count);
+            return Ok(());
+        }
+
+        for frame in &self.inner.frames.borrow()[new_frame_count..] {
+            if let Some(page_id) = frame.page_id.get()
+                && frame.pin_count.get() > 0
+            {
+                return Err(PageCacheError::PinnedPage { page_id });
+            }
+        }
+
+        let mut free_frame_ids: Vec<_> = (0..new_frame_count)
+            .rev()
+            .filter(|&frame_id| self.frame(frame_id).page_id.get().is_none())
+            .collect();
+
+        for frame_id in new_frame_count..frame_count {
+            let frame = self.frame(frame_id);
+            let Some(page_id) = frame.page_id.get() else {
+                continue;
+            };
+
+            if let Some(target_id) = free_frame_ids.pop() {
+                let target = self.frame(target_id);
+                *target.data.borrow_mut() = *frame.data.borrow();
+                target.page_id.set(Some(page_id));
+                target.dirty.set(frame.dirty.get());
+                target.lsn.set(frame.lsn.get());
+
+                let mut meta = self.inner.meta.borrow_mut();
+                meta.page_table.insert(page_id, target_id);
+                meta.replacement.record_insert(target_id);
+            } else {
+                self.flush_frame_if_dirty(frame_id)?;
+                self.inner.meta.borrow_mut().page_table.remove(&page_id);
+            }
+
+            frame.page_id.set(None);
+            frame.dirty.set(false);
+        }
+
+        self.inner.frames.borrow_mut().truncate(new_frame_count);
+        self.inner.meta.borrow_mut().replacement.resize(new_frame_count);
+        Ok(())
+    }
+
+    /// Returns the number of frames in the cache.
+    pub(crate) fn frame_count(&self) -> usize {
+        self.inner.frames.borrow().len()
+    }
+
+    fn frame(&self, frame_id: FrameId) -> Rc<Frame> {
+        Rc::clone(&self.inner.frames.borrow()[frame_id])
+    }
+
     /// Fetches an existing page into the cache and returns a pin guard.
     ///
     /// Cache hits update replacement state and increment pin count.
     /// Cache misses use CLOCK replacement and may evict a dirty page.
     pub(crate) fn fetch_page(&self, page_id: PageId) -> PageCacheResult<PinGuard> {
         if let Some(frame_id) = self.resident_frame_id(page_id)? {
-            let frame = &self.inner.frames[frame_id];
+            let frame = self.frame(frame_id);
             frame.pin_count.set(frame.pin_count.get().checked_add(1).expect("pin count overflow"));
             self.inner.meta.borrow_mut().replacement.record_access(frame_id);
             return Ok(PinGuard::new(Rc::clone(&self.inner), frame_id, page_id));
@@ -153,8 +231,7 @@ impl PageCache {
             return Ok(());
         };
 
-        let frame = &self.inner.frames[frame_id];
-        if frame.pin_count.get() > 0 {
+        if self.frame(frame_id).pin_count.get() > 0 {
             return Err(PageCacheError::PinnedPage { page_id });
         }
 
@@ -165,7 +242,7 @@ impl PageCache {
     ///
     /// Returns `PinnedPage` if a dirty page is pinned.
     pub(crate) fn flush_all(&self) -> PageCacheResult<()> {
-        for (frame_id, frame) in self.inner.frames.iter().enumerate() {
+        for (frame_id, frame) in self.inner.frames.borrow().iter().enumerate() {
             let page_id = frame.page_id.get();
             let pin_count = frame.pin_count.get();
             let dirty = frame.dirty.get();
@@ -198,18 +275,15 @@ impl PageCache {
     }
 
     fn validate_frame_id(&self, page_id: PageId, frame_id: FrameId) -> PageCacheResult<()> {
-        if frame_id >= self.inner.frames.len() {
-            return Err(PageCacheError::CorruptPageTableEntry {
-                page_id,
-                frame_id,
-                frame_count: self.inner.frames.len(),
-            });
+        let frame_count = self.frame_count();
+        if frame_id >= frame_count {
+            return Err(PageCacheError::CorruptPageTableEntry { page_id, frame_id, frame_count });
         }
         Ok(())
     }
 
     fn select_victim_frame(&self) -> Option<FrameId> {
-        let frames = &self.inner.frames;
+        let frames = self.inner.frames.borrow();
         self.inner
             .meta
             .borrow_mut()
@@ -221,7 +295,7 @@ impl PageCache {
     fn replace_frame(&self, frame_id: FrameId, new_page_id: PageId) -> PageCacheResult<()> {
         self.flush_frame_if_dirty(frame_id)?;
 
-        let frame = &self.inner.frames[frame_id];
+        let frame = self.frame(frame_id);
         let old_page_id = frame.page_id.get();
 
         let mut data = [0u8; PAGE_SIZE];
@@ -252,7 +326,7 @@ impl PageCache {
 
     /// Writes a dirty resident frame to disk and clears its dirty bit.
     fn flush_frame_if_dirty(&self, frame_id: FrameId) -> PageCacheResult<()> {
-        let frame = &self.inner.frames[frame_id];
+        let frame = self.frame(frame_id);
         if !frame.dirty.get() {
             return Ok(());
         }
@@ -280,7 +354,7 @@ impl PageCache {
     ) -> PageCacheResult<()> {
         for restore in restore_pages {
             let pin = self.fetch_page(restore.page_id)?;
-            let frame = &self.inner.frames[pin.frame_id];
+            let frame = &pin.frame;
             {
                 let mut data = frame.data.try_borrow_mut().map_err(|_| {
                     PageCacheError::PageMutableBorrowConflict { page_id: restore.page_id }
@@ -304,14 +378,15 @@ impl PageCache {
 /// Dropping the guard decrements the frame pin count.
 pub(crate) struct PinGuard {
     page_cache: Rc<PageCacheInner>,
-    frame_id: FrameId,
+    frame: Rc<Frame>,
     page_id: PageId,
 }
 
 impl PinGuard {
     /// Creates a new pin guard for a specific frame.
     fn new(page_cache: Rc<PageCacheInner>, frame_id: FrameId, page_id: PageId) -> Self {
-        Self { page_cache, frame_id, page_id }
+        let frame = Rc::clone(&page_cache.frames.borrow()[frame_id]);
+        Self { page_cache, frame, page_id }
     }
 
     /// Returns the page ID associated with this pin.
@@ -325,7 +400,7 @@ impl PinGuard {
     /// Multiple read guards may coexist for the same page, but immutable access
     /// fails while a write guard is active.
     pub(crate) fn read(&self) -> PageCacheResult<PageReadGuard<'_>> {
-        let frame = &self.page_cache.frames[self.frame_id];
+        let frame = &self.frame;
         let page = frame
             .data
             .try_borrow()
@@ -339,7 +414,7 @@ impl PinGuard {
     /// same frame. Acquiring a write guard marks the frame dirty even if the
     /// caller later decides not to mutate the page bytes.
     pub(crate) fn write(&self) -> PageCacheResult<PageWriteGuard<'_>> {
-        let frame = &self.page_cache.frames[self.frame_id];
+        let frame = &self.frame;
         let page = frame
             .data
             .try_borrow_mut()
@@ -361,7 +436,7 @@ impl PinGuard {
 impl Drop for PinGuard {
     /// Decrements the frame pin count when the guard leaves scope.
     fn drop(&mut self) {
-        let frame = &self.page_cache.frames[self.frame_id];
+        let frame = &self.frame;
         debug_assert!(frame.pin_count.get() > 0, "pin count underflow");
         if frame.pin_count.get() > 0 {
             frame.pin_count.set(frame.pin_count.get() - 1);
@@ -529,8 +604,8 @@ mod tests {
         let disk_manager = runtime_for_path(file.path());
         let cache = PageCache::new(disk_manager, 3).unwrap();
 
-        assert_eq!(cache.inner.frames.len(), 3);
-        for frame in &cache.inner.frames {
+        assert_eq!(cache.frame_count(), 3);
+        for frame in cache.inner.frames.borrow().iter() {
             assert_eq!(frame.page_id.get(), None);
             assert!(!frame.dirty.get());
             assert_eq!(frame.pin_count.get(), 0);
@@ -549,8 +624,8 @@ mod tests {
         assert_eq!(guard.read().unwrap().page(), &page);
         drop(guard);
 
-        assert_eq!(cache.inner.frames[0].page_id.get(), Some(0));
-        assert_eq!(cache.inner.frames[0].pin_count.get(), 0);
+        assert_eq!(cache.frame(0).page_id.get(), Some(0));
+        assert_eq!(cache.frame(0).pin_count.get(), 0);
     }
 
     #[test]
@@ -564,7 +639,7 @@ mod tests {
             let _guard = cache.fetch_page(0).unwrap();
         }
 
-        assert_eq!(cache.inner.frames[0].pin_count.get(), 0);
+        assert_eq!(cache.frame(0).pin_count.get(), 0);
     }
 
     #[test]
@@ -578,8 +653,8 @@ mod tests {
 
         assert_eq!(left.page_id(), 0);
         assert_eq!(right.page_id(), 1);
-        assert_eq!(cache.inner.frames[0].pin_count.get(), 1);
-        assert_eq!(cache.inner.frames[1].pin_count.get(), 1);
+        assert_eq!(cache.frame(0).pin_count.get(), 1);
+        assert_eq!(cache.frame(1).pin_count.get(), 1);
     }
 
     #[test]
@@ -670,7 +745,7 @@ mod tests {
             let guard = cache.fetch_page(0).unwrap();
             assert_eq!(guard.read().unwrap().page()[0], page[0]);
         }
-        assert!(!cache.inner.frames[0].dirty.get());
+        assert!(!cache.frame(0).dirty.get());
 
         {
             let guard = cache.fetch_page(0).unwrap();
@@ -678,7 +753,7 @@ mod tests {
             page.page_mut()[0] = 99;
         }
 
-        assert!(cache.inner.frames[0].dirty.get());
+        assert!(cache.frame(0).dirty.get());
     }
 
     #[test]
@@ -691,18 +766,18 @@ mod tests {
 
         {
             let _write = guard.write().unwrap();
-            assert!(cache.inner.frames[0].dirty.get());
+            assert!(cache.frame(0).dirty.get());
         }
 
-        assert!(!cache.inner.frames[0].dirty.get());
+        assert!(!cache.frame(0).dirty.get());
 
-        cache.inner.frames[0].dirty.set(true);
+        cache.frame(0).dirty.set(true);
         {
             let _write = guard.write().unwrap();
-            assert!(cache.inner.frames[0].dirty.get());
+            assert!(cache.frame(0).dirty.get());
         }
 
-        assert!(cache.inner.frames[0].dirty.get());
+        assert!(cache.frame(0).dirty.get());
     }
 
     #[test]
@@ -806,7 +881,7 @@ mod tests {
         }
 
         assert_eq!(pinned.page_id(), 0);
-        assert_eq!(cache.inner.frames[0].page_id.get(), Some(0));
+        assert_eq!(cache.frame(0).page_id.get(), Some(0));
         let page_table = &cache.inner.meta.borrow().page_table;
         assert!(page_table.contains_key(&0));
         assert!(!page_table.contains_key(&1));
@@ -826,6 +901,99 @@ mod tests {
         assert!(matches!(result, Err(PageCacheError::NoEvictableFrame)));
     }
 
+    #[test]
+    fn resize_rejects_zero_frame_count() {
+        let file = NamedTempFile::new().unwrap();
+        let cache = PageCache::new(runtime_for_path(file.path()), 2).unwrap();
+
+        let result = cache.resize(0);
+
+        assert!(matches!(result, Err(PageCacheError::InvalidFrameCount { frame_count: 0 })));
+        assert_eq!(cache.frame_count(), 2);
+    }
+
+    #[test]
+    fn resize_grows_cache_with_empty_frames() {
+        let pages = [page_with_pattern(1), page_with_pattern(2), page_with_pattern(3)];
+        let (_file, disk_manager) = create_disk_with_pages(&pages);
+        let cache = PageCache::new(disk_manager, 1).unwrap();
+        let first = cache.fetch_page(0).unwrap();
+
+        cache.resize(3).unwrap();
+        let second = cache.fetch_page(1).unwrap();
+        let third = cache.fetch_page(2).unwrap();
+
+        assert_eq!(cache.frame_count(), 3);
+        assert_eq!(cache.frame(0).page_id.get(), Some(0));
+        assert_eq!(first.read().unwrap().page(), &pages[0]);
+        assert_eq!(second.read().unwrap().page(), &pages[1]);
+        assert_eq!(third.read().unwrap().page(), &pages[2]);
+    }
+
+    #[test]
+    fn resize_shrink_moves_resident_pages_into_free_frames() {
+        let pages = [page_with_pattern(1), page_with_pattern(2), page_with_pattern(3)];
+        let (_file, disk_manager) = create_disk_with_pages(&pages);
+        let cache = PageCache::new(disk_manager, 3).unwrap();
+        for page_id in 0..3 {
+            cache.fetch_page(page_id).unwrap();
+        }
+        {
+            let guard = cache.fetch_page(2).unwrap();
+            guard.write().unwrap().page_mut()[0] = 99;
+        }
+        cache.frame(0).page_id.set(None);
+        cache.inner.meta.borrow_mut().page_table.remove(&0);
+
+        cache.resize(2).unwrap();
+
+        assert_eq!(cache.frame_count(), 2);
+        assert_eq!(cache.inner.meta.borrow().page_table.get(&2), Some(&0));
+        assert!(cache.frame(0).dirty.get());
+        let guard = cache.fetch_page(2).unwrap();
+        assert_eq!(guard.read().unwrap().page()[0], 99);
+        assert_eq!(guard.read().unwrap().page()[1..], pages[2][1..]);
+    }
+
+    #[test]
+    fn resize_shrink_evicts_pages_without_free_frames() {
+        let pages = [page_with_pattern(1), page_with_pattern(2), page_with_pattern(3)];
+        let (file, disk_manager) = create_disk_with_pages(&pages);
+        let cache = PageCache::new(disk_manager, 3).unwrap();
+        for page_id in 0..3 {
+            cache.fetch_page(page_id).unwrap();
+        }
+        {
+            let guard = cache.fetch_page(2).unwrap();
+            guard.write().unwrap().page_mut()[0] = 177;
+        }
+
+        cache.resize(2).unwrap();
+
+        assert_eq!(cache.frame_count(), 2);
+        let page_table = &cache.inner.meta.borrow().page_table;
+        assert!(page_table.contains_key(&0));
+        assert!(page_table.contains_key(&1));
+        assert!(!page_table.contains_key(&2));
+        assert_eq!(read_disk_page(file.path(), 2)[0], 177);
+    }
+
+    #[test]
+    fn resize_shrink_fails_when_removed_frame_is_pinned() {
+        let pages = [page_with_pattern(1), page_with_pattern(2)];
+        let (_file, disk_manager) = create_disk_with_pages(&pages);
+        let cache = PageCache::new(disk_manager, 2).unwrap();
+        let _first = cache.fetch_page(0).unwrap();
+        let _second = cache.fetch_page(1).unwrap();
+
+        let result = cache.resize(1);
+
+        assert!(matches!(result, Err(PageCacheError::PinnedPage { page_id: 1 })));
+        assert_eq!(cache.frame_count(), 2);
+        assert_eq!(cache.frame(1).page_id.get(), Some(1));
+        assert!(cache.inner.meta.borrow().page_table.contains_key(&1));
+    }
+
     #[test]
     fn flush_page_writes_dirty_data_and_clears_dirty_bit() {
         let page = page_with_pattern(15);
@@ -837,11 +1005,11 @@ mod tests {
             let guard = cache.fetch_page(0).unwrap();
             guard.write().unwrap().page_mut()[0] = 177;
         }
-        assert!(cache.inner.frames[0].dirty.get());
+        assert!(cache.frame(0).dirty.get());
 
         cache.flush_page(0).unwrap();
 
-        assert!(!cache.inner.frames[0].dirty.get());
+        assert!(!cache.frame(0).dirty.get());
         let flushed_page = read_disk_page(file.path(), 0);
         assert_eq!(flushed_page[0], 177);
     }
@@ -861,7 +1029,7 @@ mod tests {
 
         let flushed_page = read_disk_page(file.path(), 0);
         assert_eq!(flushed_page[PAGE_SIZE - 1], 177);
-        assert!(!cache.inner.frames[0].dirty.get());
+        assert!(!cache.frame(0).dirty.get());
     }
 
     #[test]
@@ -906,7 +1074,7 @@ mod tests {
         let flushed_page1 = read_disk_page(file.path(), 1);
         assert_eq!(flushed_page0[PAGE_SIZE - 1], 10);
         assert_eq!(flushed_page1[PAGE_SIZE - 1], 20);
-        for frame in &cache.inner.frames {
+        for frame in cache.inner.frames.borrow().iter() {
             assert!(!frame.dirty.get());
         }
     }
@@ -928,7 +1096,7 @@ mod tests {
 
         let flushed_page = read_disk_page(file.path(), 0);
         assert_eq!(flushed_page[PAGE_SIZE - 1], 177);
-        assert!(!cache.inner.frames[0].dirty.get());
+        assert!(!cache.frame(0).dirty.get());
         assert_eq!(
             read_log_record_kinds_for_test(file.path()),
             [
@@ -959,7 +1127,7 @@ mod tests {
 
         let flushed_page = read_disk_page(file.path(), 0);
         assert_eq!(flushed_page[PAGE_SIZE - 1], 222);
-        assert!(!cache.inner.frames[0].dirty.get());
+        assert!(!cache.frame(0).dirty.get());
         assert_eq!(
             read_log_record_kinds_for_test(file.path()),
             [
@@ -987,7 +1155,7 @@ mod tests {
         assert!(matches!(result, Err(PageCacheError::Transaction(_))));
         let page_on_disk = read_disk_page(file.path(), 0);
         assert_eq!(page_on_disk[PAGE_SIZE - 1], page[PAGE_SIZE - 1]);
-        assert!(cache.inner.frames[0].dirty.get());
+        assert!(cache.frame(0).dirty.get());
     }
 
     #[test]
@@ -1100,7 +1268,7 @@ mod tests {
         }
 
         assert_eq!(guard.read().unwrap().page(), &page);
-        assert!(!cache.inner.frames[0].dirty.get());
+        assert!(!cache.frame(0).dirty.get());
         assert!(runtime.commit_transaction(txn_id).is_err());
     }
 
@@ -1110,10 +1278,10 @@ mod tests {
         let disk_manager = runtime_for_path(file.path());
         let cache = PageCache::new(disk_manager, 1).unwrap();
 
-        cache.inner.frames[0].page_id.set(Some(99));
-        *cache.inner.frames[0].data.borrow_mut() = page_with_pattern(15);
-        cache.inner.frames[0].dirty.set(true);
-        cache.inner.frames[0].pin_count.set(0);
+        cache.frame(0).page_id.set(Some(99));
+        *cache.frame(0).data.borrow_mut() = page_with_pattern(15);
+        cache.frame(0).dirty.set(true);
+        cache.frame(0).pin_count.set(0);
         cache.inner.meta.borrow_mut().page_table.insert(99, 0);
 
         let result = cache.flush_page(99);
@@ -1124,7 +1292,7 @@ mod tests {
                 page_id: 99
             }))
         ));
-        assert!(cache.inner.frames[0].dirty.get());
+        assert!(cache.frame(0).dirty.get());
     }
 
     #[test]
@@ -1170,7 +1338,7 @@ mod tests {
 
         cache.flush_all().unwrap();
 
-        for frame in &cache.inner.frames {
+        for frame in cache.inner.frames.borrow().iter() {
             assert!(!frame.dirty.get());
         }
 
@@ -1205,7 +1373,7 @@ mod tests {
                 let guard = cache.fetch_page(0).unwrap();
                 guard.write().unwrap().page_mut()[0] = 144;
             }
-            assert!(cache.inner.frames[0].dirty.get());
+            assert!(cache.frame(0).dirty.get());
         }
 
         let page_on_disk = read_disk_page(file.path(), 0);
@@ -1279,7 +1447,7 @@ mod tests {
         let disk_manager = runtime_for_path(file.path());
         let cache = PageCache::new(disk_manager, 1).unwrap();
 
-        cache.inner.frames[0].pin_count.set(1);
+        cache.frame(0).pin_count.set(1);
 
         let result = cache.new_page();
         assert!(matches!(result, Err(PageCacheError::NoEvictableFrame)));
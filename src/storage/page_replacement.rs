@@ -33,6 +33,16 @@ impl BitSet {
         self.words[word_index] &= !mask;
     }
 
+    /// Changes the number of bits, clearing any bits removed by shrinking.
+    fn resize(&mut self, len: usize) {
+        for index in len..self.len {
+            self.clear(index);
+        }
+        let word_count = len.saturating_add(BITS_PER_WORD - 1) / BITS_PER_WORD;
+        self.words.resize(word_count, 0);
+        self.len = len;
+    }
+
     fn word_index_and_mask(&self, index: usize) -> (usize, u64) {
         assert!(index < self.len, "bit index out of bounds");
         let word_index = index / BITS_PER_WORD;
@@ -60,6 +70,17 @@ impl ClockPolicy {
         self.reference_bits.set(frame_id);
     }
 
+    /// Changes the number of tracked frames.
+    ///
+    /// Reference bits of removed frames are dropped and the clock hand wraps
+    /// back to the first frame if it pointed past the new end.
+    pub(crate) fn resize(&mut self, frame_count: usize) {
+        self.reference_bits.resize(frame_count);
+        if self.hand >= frame_count {
+            self.hand = 0;
+        }
+    }
+
     /// Selects a victim frame using CLOCK second-chance replacement.
     ///
     /// Pinned frames are skipped and referenced frames get one second chance.
@@ -132,6 +153,29 @@ mod tests {
         assert!(!bitset.get(65));
     }
 
+    #[test]
+    fn bitset_resize_clears_removed_bits() {
+        let mut bitset = BitSet::new(130);
+
+        bitset.set(2);
+        bitset.set(100);
+        bitset.resize(64);
+        bitset.resize(130);
+
+        assert!(bitset.get(2));
+        assert!(!bitset.get(100));
+    }
+
+    #[test]
+    fn clock_resize_wraps_hand_into_remaining_frames() {
+        let mut clock = ClockPolicy::new(4);
+        assert_eq!(clock.select_victim(|frame_id| frame_id < 3), Some(3));
+
+        clock.resize(2);
+
+        assert_eq!(clock.select_victim(|_| false), Some(0));
+    }
+
     #[test]
     fn bitset_handles_bits_across_word_boundaries() {
         let mut bitset = BitSet::new(130);
@@ -4,7 +4,7 @@ use crate::core::{PageId, error::StorageResult};
 use crate::storage::{
     btree::{TreeCursor, initialize_empty_root, validate_tree_page_formats},
     database_header::{DATABASE_HEADER_PAGE_ID, DatabaseHeader, missing_header},
-    disk_manager::DiskManager,
+    disk_manager::{DiskManager, SyncMode},
     page_cache::PageCache,
     storage_runtime::StorageRuntime,
     transaction_runtime::TransactionRuntime,
@@ -17,11 +17,13 @@ const DEFAULT_PAGE_CACHE_SIZE: usize = 16384;
 pub struct PagerOptions {
     /// Number of frames to preallocate in the page cache.
     pub cache_frames: usize,
+    /// When the database file is synced after page writes.
+    pub sync_mode: SyncMode,
 }
 
 impl Default for PagerOptions {
     fn default() -> Self {
-        Self { cache_frames: DEFAULT_PAGE_CACHE_SIZE }
+        Self { cache_frames: DEFAULT_PAGE_CACHE_SIZE, sync_mode: SyncMode::default() }
     }
 }
 
@@ -91,9 +93,10 @@ impl Pager {
 
     fn from_disk_manager(
         path: std::path::PathBuf,
-        disk_manager: DiskManager,
+        mut disk_manager: DiskManager,
         options: PagerOptions,
     ) -> StorageResult<Self> {
+        disk_manager.set_sync_mode(options.sync_mode);
         let opened_page_count = disk_manager.page_count();
         let runtime = Rc::new(StorageRuntime::new(path, disk_manager)?);
         let page_cache = PageCache::new(Rc::clone(&runtime), options.cache_frames)?;
@@ -117,6 +120,12 @@ impl Pager {
         Ok(())
     }
 
+    /// Changes the number of page cache frames.
+    pub(crate) fn resize_cache(&self, cache_frames: usize) -> StorageResult<()> {
+        self.page_cache.resize(cache_frames)?;
+        Ok(())
+    }
+
     pub(crate) fn transaction_runtime(&self) -> TransactionRuntime {
         TransactionRuntime::new(Rc::clone(&self.runtime), self.page_cache.clone())
     }
@@ -0,0 +1,49 @@
+#![cfg(feature = "serde")]
+
+use ciborium::Value;
+use databas::sql_parser::parser::Parser;
+
+/// Renders a serialized value as compact JSON-like text for snapshot comparison.
+fn render(value: &Value) -> String {
+    match value {
+        Value::Null => "null".to_owned(),
+        Value::Bool(b) => b.to_string(),
+        Value::Integer(i) => i128::from(*i).to_string(),
+        Value::Float(f) => f.to_string(),
+        Value::Text(s) => format!("{s:?}"),
+        Value::Array(items) => {
+            format!("[{}]", items.iter().map(render).collect::<Vec<_>>().join(","))
+        }
+        Value::Map(entries) => {
+            let entries = entries
+                .iter()
+                .map(|(key, value)| format!("{}:{}", render(key), render(value)))
+                .collect::<Vec<_>>();
+            format!("{{{}}}", entries.join(","))
+        }
+        other => panic!("unexpected serialized value: {other:?}"),
+    }
+}
+
+#[test]
+fn select_statement_serializes_by_borrowing() {
+    let statement = Parser::new("SELECT a FROM t WHERE a > 1;").stmt().unwrap();
+    let value = Value::serialized(&statement).unwrap();
+
+    assert_eq!(
+        render(&value),
+        concat!(
+            r#"{"Select":{"#,
+            r#""distinct":false,"#,
+            r#""columns":[{"Identifier":"a"}],"#,
+            r#""table":"t","#,
+            r#""where_clause":{"BinaryOp":[{"Identifier":"a"},"GreaterThan",{"Literal":{"Number":{"Integer":1}}}]},"#,
+            r#""group_by":null,"#,
+            r#""having":null,"#,
+            r#""order_by":null,"#,
+            r#""limit":null,"#,
+            r#""offset":null"#,
+            r#"}}"#,
+        )
+    );
+}
@@ -1,4 +1,4 @@
-use databas::sql_parser::parser::{Parser, SqlItem, stmt::Statement};
+use databas::sql_parser::parser::{Parser, SqlItem, expr::Expression, stmt::Statement};
 use hegel::TestCase;
 use hegel::generators as gs;
 
@@ -114,6 +114,29 @@ fn assert_items_round_trip(sql: &str) {
     assert_eq!(parsed, reparsed, "SQL did not round-trip: {sql}\ndisplayed as: {displayed}");
 }
 
+fn parse_expression(sql: &str) -> Expression<'_> {
+    Parser::new(sql).expr().unwrap_or_else(|err| panic!("failed to parse `{sql}`: {err:?}"))
+}
+
+fn assert_expressions_round_trip(expressions: &[&str]) {
+    for sql in expressions {
+        let parsed = parse_expression(sql);
+        let displayed = parsed.to_string();
+        let reparsed = parse_expression(&displayed);
+
+        assert_eq!(
+            parsed, reparsed,
+            "expression did not round-trip: {sql}\ndisplayed as: {displayed}"
+        );
+    }
+}
+
+fn assert_all_round_trip(statements: &[&str]) {
+    for sql in statements {
+        assert_round_trips(sql);
+    }
+}
+
 fn draw_statement(tc: &TestCase) -> String {
     match draw_index(tc, 8) {
         0 => draw_insert(tc),
@@ -405,3 +428,104 @@ fn float_literals_with_zero_fraction_keep_fractional_display() {
     assert_eq!(displayed, "SELECT 0.0;");
     assert_eq!(parsed, reparsed, "displayed SQL: {displayed}");
 }
+
+#[test]
+fn insert_statements_round_trip_through_display() {
+    assert_all_round_trip(&[
+        "INSERT INTO users (id) VALUES (1);",
+        "INSERT INTO users (id, name) VALUES (1, 'Ada');",
+        "INSERT INTO users (id, name, score) VALUES (1, 'Ada', 9.5), (2, 'Grace', -3.25);",
+        "INSERT INTO users (id, active) VALUES (1, true), (2, false), (3, NULL);",
+        "INSERT INTO totals (id, amount) VALUES (1, 2 + 3 * 4), (2, (2 + 3) * 4);",
+        "INSERT INTO totals (id, amount) VALUES (1, -5), (2, -(1 - 2));",
+    ]);
+}
+
+#[test]
+fn create_table_statements_round_trip_through_display() {
+    assert_all_round_trip(&[
+        "CREATE TABLE users (id INT PRIMARY KEY);",
+        "CREATE TABLE users (id INT PRIMARY KEY, name TEXT, score FLOAT);",
+        "CREATE TABLE users (id INT PRIMARY KEY, name TEXT NULLABLE, score FLOAT NULLABLE);",
+        "CREATE TABLE events (id INT PRIMARY KEY, kind TEXT, payload TEXT NULLABLE);",
+    ]);
+}
+
+#[test]
+fn update_statements_round_trip_through_display() {
+    assert_all_round_trip(&[
+        "UPDATE users SET name = 'Ada';",
+        "UPDATE users SET name = 'Ada', score = score + 1 WHERE id == 1;",
+        "UPDATE users SET active = NOT active WHERE score < 10 AND name != 'Grace';",
+    ]);
+}
+
+#[test]
+fn delete_statements_round_trip_through_display() {
+    assert_all_round_trip(&[
+        "DELETE FROM users;",
+        "DELETE FROM users WHERE id == 1;",
+        "DELETE FROM users WHERE score >= 3.5 OR name == 'Ada';",
+    ]);
+}
+
+#[test]
+fn mixed_expressions_keep_their_grouping_through_display() {
+    assert_expressions_round_trip(&[
+        "a + b * c",
+        "(a + b) * c",
+        "a - (b - c)",
+        "(a - b) - c",
+        "a / (b * c)",
+        "-(a + b) * c",
+        "a * -b + c",
+        "a + b < c * d",
+        "(a < b) == (c < d)",
+        "a == (b == c)",
+        "a AND b OR c",
+        "a AND (b OR c)",
+        "(a OR b) AND (c OR d)",
+        "NOT (a OR b)",
+        "NOT a OR b",
+        "NOT (a AND b) OR NOT c",
+        "NOT NOT (a OR b)",
+        "NOT (a + 1 > b)",
+        "a > 1 AND (b < 2 OR c * (d + e) >= 3)",
+    ]);
+}
+
+#[test]
+fn looser_children_are_parenthesized_in_display() {
+    assert_eq!(parse_expression("(a + b) * c").to_string(), "(a + b) * c");
+    assert_eq!(parse_expression("a + b * c").to_string(), "a + b * c");
+    assert_eq!(parse_expression("a - (b - c)").to_string(), "a - (b - c)");
+    assert_eq!(parse_expression("NOT (a OR b)").to_string(), "NOT (a OR b)");
+    assert_eq!(parse_expression("a AND (b OR c)").to_string(), "a AND (b OR c)");
+}
+
+#[test]
+fn backtick_quoted_identifiers_round_trip_through_display() {
+    assert_eq!(parse_statement("SELECT `a b` FROM t;").to_string(), r#"SELECT "a b" FROM t;"#);
+    assert_all_round_trip(&[
+        "SELECT `a b` FROM t;",
+        "SELECT `select`, `a\"b` FROM `from` WHERE `where` > 1 ORDER BY `order` DESC;",
+        "INSERT INTO `my table` (`first name`, `key`) VALUES ('Ada', 1);",
+        "UPDATE `t t` SET `set` = 1 WHERE `id` == 2;",
+        "DELETE FROM `delete`;",
+        "CREATE TABLE `table` (`int` INT PRIMARY KEY, `two words` TEXT);",
+        "CREATE INDEX `index` ON `on` (`a b`, c);",
+        "WITH `with` AS (SELECT 1) SELECT * FROM `with`;",
+    ]);
+}
+
+#[test]
+fn double_quoted_identifiers_round_trip_through_display() {
+    let sql = r#"SELECT "order", "first name" FROM "my table";"#;
+    assert_eq!(parse_statement(sql).to_string(), sql);
+    assert_all_round_trip(&[
+        sql,
+        r#"SELECT "a`b", "Mixed Case" FROM t WHERE "null" == NULL;"#,
+        r#"INSERT INTO "values" ("select", "two words") VALUES (1, 'x');"#,
+    ]);
+    assert_expressions_round_trip(&[r#""order" + 1"#, r#"COUNT(DISTINCT "group")"#, r#""true""#]);
+}